@@ -489,7 +489,9 @@ impl KomorebiNotificationState {
                 self.workspaces = workspaces;
                 self.layout = match monitor.workspaces()[focused_workspace_idx].layout() {
                     komorebi_client::Layout::Default(layout) => KomorebiLayout::Default(*layout),
-                    komorebi_client::Layout::Custom(_) => KomorebiLayout::Custom,
+                    komorebi_client::Layout::Custom(_)
+                    | komorebi_client::Layout::CustomTree(_)
+                    | komorebi_client::Layout::External(_) => KomorebiLayout::Custom,
                 };
 
                 if !*monitor.workspaces()[focused_workspace_idx].tile() {
@@ -7,6 +7,8 @@ pub use komorebi::config_generation::ApplicationConfiguration;
 pub use komorebi::container::Container;
 pub use komorebi::core::config_generation::ApplicationConfigurationGenerator;
 pub use komorebi::core::resolve_home_path;
+pub use komorebi::core::AnimationOperation;
+pub use komorebi::core::AnimationOverride;
 pub use komorebi::core::AnimationStyle;
 pub use komorebi::core::ApplicationIdentifier;
 pub use komorebi::core::Arrangement;
@@ -14,24 +16,36 @@ pub use komorebi::core::Axis;
 pub use komorebi::core::BorderImplementation;
 pub use komorebi::core::BorderStyle;
 pub use komorebi::core::CustomLayout;
+pub use komorebi::core::CustomLayoutTree;
 pub use komorebi::core::CycleDirection;
 pub use komorebi::core::DefaultLayout;
 pub use komorebi::core::Direction;
+pub use komorebi::core::FloatingPlacement;
 pub use komorebi::core::FocusFollowsMouseImplementation;
 pub use komorebi::core::HidingBehaviour;
 pub use komorebi::core::Layout;
+pub use komorebi::core::ManageAction;
 pub use komorebi::core::MoveBehaviour;
+pub use komorebi::core::NavigationBehaviour;
 pub use komorebi::core::OperationBehaviour;
 pub use komorebi::core::OperationDirection;
+pub use komorebi::core::PercentageRect;
 pub use komorebi::core::Rect;
+pub use komorebi::core::RelativeRect;
+pub use komorebi::core::SidePadding;
 pub use komorebi::core::Sizing;
 pub use komorebi::core::SocketMessage;
 pub use komorebi::core::StackbarLabel;
 pub use komorebi::core::StackbarMode;
 pub use komorebi::core::StateQuery;
 pub use komorebi::core::WindowKind;
+pub use komorebi::core::WindowSizeConstraints;
+pub use komorebi::core::WorkspaceTransition;
+pub use komorebi::external_layout::ExternalLayoutRequest;
 pub use komorebi::monitor::Monitor;
 pub use komorebi::ring::Ring;
+pub use komorebi::rule_evaluator::RuleDecision;
+pub use komorebi::rule_evaluator::RuleEvaluation;
 pub use komorebi::window::Window;
 pub use komorebi::window_manager_event::WindowManagerEvent;
 pub use komorebi::workspace::Workspace;
@@ -96,3 +110,25 @@ pub fn subscribe(name: &str) -> std::io::Result<UnixListener> {
 
     Ok(listener)
 }
+
+/// Listen for layout requests as the external layout with the given name. Each connection carries
+/// a single `ExternalLayoutRequest`, and should be answered with a JSON list of `Rect`s
+pub fn register_external_layout(name: &str) -> std::io::Result<UnixListener> {
+    let socket = DATA_DIR.join(name);
+
+    match std::fs::remove_file(&socket) {
+        Ok(()) => {}
+        Err(error) => match error.kind() {
+            std::io::ErrorKind::NotFound => {}
+            _ => {
+                return Err(error);
+            }
+        },
+    };
+
+    let listener = UnixListener::bind(&socket)?;
+
+    send_message(&SocketMessage::RegisterExternalLayout(name.to_string()))?;
+
+    Ok(listener)
+}
@@ -95,7 +95,7 @@ impl From<&komorebi_client::Workspace> for WorkspaceConfig {
     fn from(value: &komorebi_client::Workspace) -> Self {
         let layout = match value.layout() {
             Layout::Default(layout) => *layout,
-            Layout::Custom(_) => DefaultLayout::BSP,
+            Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => DefaultLayout::BSP,
         };
 
         let name = value
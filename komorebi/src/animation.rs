@@ -1,5 +1,8 @@
+use crate::core::AnimationOperation;
 use crate::core::AnimationStyle;
 use crate::core::Rect;
+use crate::core::SocketMessage;
+use crate::window_manager_event::WindowManagerEvent;
 use color_eyre::Result;
 
 use schemars::JsonSchema;
@@ -13,11 +16,117 @@ use std::time::Duration;
 use std::time::Instant;
 
 use crate::ANIMATION_DURATION;
+use crate::ANIMATION_ENABLED;
 use crate::ANIMATION_MANAGER;
+use crate::ANIMATION_OPERATION;
+use crate::ANIMATION_OVERRIDES;
 use crate::ANIMATION_STYLE;
 
 pub static ANIMATION_FPS: AtomicU64 = AtomicU64::new(60);
 
+/// The animation settings for the operation currently being handled, after applying any
+/// overrides for that operation on top of the global animation settings
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationSettings {
+    pub enabled: bool,
+    pub duration: Duration,
+    pub style: AnimationStyle,
+    pub fps: u64,
+}
+
+impl AnimationSettings {
+    pub fn current() -> Self {
+        let operation = *ANIMATION_OPERATION.lock();
+        let overrides = ANIMATION_OVERRIDES
+            .lock()
+            .get(&operation)
+            .copied()
+            .unwrap_or_default();
+
+        Self {
+            enabled: overrides
+                .enabled
+                .unwrap_or_else(|| ANIMATION_ENABLED.load(Ordering::SeqCst)),
+            duration: Duration::from_millis(
+                overrides
+                    .duration
+                    .unwrap_or_else(|| ANIMATION_DURATION.load(Ordering::SeqCst)),
+            ),
+            style: overrides.style.unwrap_or_else(|| *ANIMATION_STYLE.lock()),
+            fps: overrides
+                .fps
+                .unwrap_or_else(|| ANIMATION_FPS.load(Ordering::SeqCst))
+                .max(1),
+        }
+    }
+}
+
+/// Marks every window movement as part of an operation until the scope is dropped, after which
+/// movements are treated as reflows again
+pub struct AnimationOperationScope;
+
+impl AnimationOperationScope {
+    pub fn new(operation: AnimationOperation) -> Self {
+        *ANIMATION_OPERATION.lock() = operation;
+        Self
+    }
+}
+
+impl Drop for AnimationOperationScope {
+    fn drop(&mut self) {
+        *ANIMATION_OPERATION.lock() = AnimationOperation::default();
+    }
+}
+
+impl From<&SocketMessage> for AnimationOperation {
+    fn from(message: &SocketMessage) -> Self {
+        match message {
+            SocketMessage::MoveWindow(_)
+            | SocketMessage::CycleMoveWindow(_)
+            | SocketMessage::StackWindow(_)
+            | SocketMessage::UnstackWindow
+            | SocketMessage::StackAll
+            | SocketMessage::UnstackAll
+            | SocketMessage::Promote
+            | SocketMessage::PromoteFocus
+            | SocketMessage::PromoteWindow(_)
+            | SocketMessage::MoveContainerToWorkspaceNumber(_)
+            | SocketMessage::MoveContainerToNamedWorkspace(_)
+            | SocketMessage::CycleMoveContainerToWorkspace(_)
+            | SocketMessage::SendContainerToWorkspaceNumber(_)
+            | SocketMessage::SendContainerToNamedWorkspace(_)
+            | SocketMessage::CycleSendContainerToWorkspace(_) => Self::Movement,
+            SocketMessage::CycleFocusWorkspace(_)
+            | SocketMessage::FocusLastWorkspace
+            | SocketMessage::FocusWorkspaceNumber(_)
+            | SocketMessage::FocusWorkspaceNumbers(_)
+            | SocketMessage::FocusMonitorWorkspaceNumber(..)
+            | SocketMessage::FocusNamedWorkspace(_) => Self::WorkspaceSwitch,
+            SocketMessage::MoveContainerToMonitorNumber(_)
+            | SocketMessage::CycleMoveContainerToMonitor(_)
+            | SocketMessage::SendContainerToMonitorNumber(_)
+            | SocketMessage::CycleSendContainerToMonitor(_)
+            | SocketMessage::SendContainerToMonitorWorkspaceNumber(..)
+            | SocketMessage::MoveContainerToMonitorWorkspaceNumber(..)
+            | SocketMessage::CycleMoveWorkspaceToMonitor(_)
+            | SocketMessage::MoveWorkspaceToMonitorNumber(_)
+            | SocketMessage::SwapWorkspacesToMonitorNumber(_) => Self::MonitorMove,
+            _ => Self::Reflow,
+        }
+    }
+}
+
+impl From<&WindowManagerEvent> for AnimationOperation {
+    fn from(event: &WindowManagerEvent) -> Self {
+        match event {
+            // windows which have been dragged are either swapped with another container or moved
+            // to another monitor
+            WindowManagerEvent::MoveResizeEnd(..) => Self::Movement,
+            _ => Self::Reflow,
+        }
+    }
+}
+
 pub trait Ease {
     fn evaluate(t: f64) -> f64;
 }
@@ -370,9 +479,147 @@ impl Ease for EaseInOutBounce {
         }
     }
 }
-fn apply_ease_func(t: f64) -> f64 {
-    let style = *ANIMATION_STYLE.lock();
 
+/// A cubic bezier curve from (0, 0) to (1, 1) with two control points, as used by CSS
+pub struct CubicBezier {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl CubicBezier {
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        // the curve is only a function of time when both control points are within 0.0..=1.0 on x
+        Self {
+            x1: x1.clamp(0.0, 1.0),
+            y1,
+            x2: x2.clamp(0.0, 1.0),
+            y2,
+        }
+    }
+
+    fn sample(a1: f64, a2: f64, s: f64) -> f64 {
+        let c = 3.0 * a1;
+        let b = 3.0f64.mul_add(a2 - a1, -c);
+        let a = 1.0 - c - b;
+
+        (a.mul_add(s, b).mul_add(s, c)) * s
+    }
+
+    fn sample_derivative(a1: f64, a2: f64, s: f64) -> f64 {
+        let c = 3.0 * a1;
+        let b = 3.0f64.mul_add(a2 - a1, -c);
+        let a = 1.0 - c - b;
+
+        (3.0 * a).mul_add(s, 2.0 * b).mul_add(s, c)
+    }
+
+    /// Find the curve parameter which gives the time t on the x axis, using Newton's method and
+    /// falling back to bisection if it doesn't converge
+    fn solve_x(&self, t: f64) -> f64 {
+        const EPSILON: f64 = 1e-6;
+
+        let mut s = t;
+        for _ in 0..8 {
+            let x = Self::sample(self.x1, self.x2, s) - t;
+            if x.abs() < EPSILON {
+                return s;
+            }
+
+            let derivative = Self::sample_derivative(self.x1, self.x2, s);
+            if derivative.abs() < EPSILON {
+                break;
+            }
+
+            s -= x / derivative;
+        }
+
+        let (mut low, mut high) = (0.0, 1.0);
+        s = t;
+        while high - low > EPSILON {
+            let x = Self::sample(self.x1, self.x2, s);
+            if (x - t).abs() < EPSILON {
+                break;
+            }
+
+            if x < t {
+                low = s;
+            } else {
+                high = s;
+            }
+
+            s = (high - low).mul_add(0.5, low);
+        }
+
+        s
+    }
+
+    pub fn evaluate(&self, t: f64) -> f64 {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+
+        Self::sample(self.y1, self.y2, self.solve_x(t))
+    }
+}
+
+/// A damped harmonic oscillator with a mass of 1 which starts at rest and is released towards
+/// the end position. The duration of the animation is stretched over the time the spring takes to
+/// settle
+pub struct Spring {
+    stiffness: f64,
+    damping: f64,
+}
+
+impl Spring {
+    pub const fn new(stiffness: f64, damping: f64) -> Self {
+        Self { stiffness, damping }
+    }
+
+    pub fn evaluate(&self, t: f64) -> f64 {
+        // an undamped spring never settles, so there is nothing sensible to stretch over the
+        // duration of the animation
+        if self.stiffness <= 0.0 || self.damping <= 0.0 {
+            return Linear::evaluate(t);
+        }
+
+        if t >= 1.0 {
+            return 1.0;
+        }
+
+        let omega = self.stiffness.sqrt();
+        let zeta = self.damping / (2.0 * omega);
+
+        // the slowest rate at which the spring decays towards the end position
+        let decay = if zeta > 1.0 {
+            omega * (zeta - zeta.mul_add(zeta, -1.0).sqrt())
+        } else {
+            zeta * omega
+        };
+
+        // the time taken for the displacement to fall within 0.01% of the distance travelled
+        let settle = 10_000f64.ln() / decay;
+        let time = t * settle;
+
+        let displacement = if zeta < 1.0 {
+            let damped = omega * zeta.mul_add(-zeta, 1.0).sqrt();
+            f64::exp(-zeta * omega * time)
+                * (zeta * omega / damped).mul_add(f64::sin(damped * time), f64::cos(damped * time))
+        } else if zeta > 1.0 {
+            let root = zeta.mul_add(zeta, -1.0).sqrt();
+            let r1 = -omega * (zeta - root);
+            let r2 = -omega * (zeta + root);
+            r2.mul_add(f64::exp(r1 * time), -r1 * f64::exp(r2 * time)) / (r2 - r1)
+        } else {
+            f64::exp(-omega * time) * omega.mul_add(time, 1.0)
+        };
+
+        1.0 - displacement
+    }
+}
+
+fn apply_ease_func(t: f64, style: AnimationStyle) -> f64 {
     match style {
         AnimationStyle::Linear => Linear::evaluate(t),
         AnimationStyle::EaseInSine => EaseInSine::evaluate(t),
@@ -404,114 +651,342 @@ fn apply_ease_func(t: f64) -> f64 {
         AnimationStyle::EaseInBounce => EaseInBounce::evaluate(t),
         AnimationStyle::EaseOutBounce => EaseOutBounce::evaluate(t),
         AnimationStyle::EaseInOutBounce => EaseInOutBounce::evaluate(t),
+        AnimationStyle::CubicBezier(x1, y1, x2, y2) => CubicBezier::new(x1, y1, x2, y2).evaluate(t),
+        AnimationStyle::Spring { stiffness, damping } => {
+            Spring::new(stiffness, damping).evaluate(t)
+        }
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
-pub struct Animation {
-    pub hwnd: isize,
+/// The velocity of each component of a rect, in pixels per millisecond
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RectVelocity {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
 }
 
-impl Animation {
-    pub fn new(hwnd: isize) -> Self {
-        Self { hwnd }
+impl RectVelocity {
+    const fn components(&self) -> [f64; 4] {
+        [self.left, self.top, self.right, self.bottom]
+    }
+
+    const fn from_components([left, top, right, bottom]: [f64; 4]) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
     }
+}
+
+/// The movement of a window from one rect to another. A motion which has been retargeted while
+/// in flight starts with the velocity the window was already moving at, which is carried forward
+/// and smoothly fades out so that the window still comes to rest exactly on its target
+#[derive(Debug, Clone, Copy)]
+pub struct RectMotion {
+    pub start: Rect,
+    pub target: Rect,
+    pub initial_velocity: RectVelocity,
+    pub duration: Duration,
+    pub style: AnimationStyle,
+}
 
-    /// Returns true if the animation needs to continue
-    pub fn cancel(&mut self) -> bool {
-        if !ANIMATION_MANAGER.lock().in_progress(self.hwnd) {
-            return true;
+impl RectMotion {
+    pub fn new(start: Rect, target: Rect, duration: Duration, style: AnimationStyle) -> Self {
+        Self {
+            start,
+            target,
+            initial_velocity: RectVelocity::default(),
+            duration,
+            style,
         }
+    }
 
-        // should be more than 0
-        let cancel_idx = ANIMATION_MANAGER.lock().init_cancel(self.hwnd);
-        let max_duration = Duration::from_secs(1);
-        let spent_duration = Instant::now();
+    /// How far through the motion it is after the given amount of time, between 0.0 and 1.0
+    #[allow(clippy::cast_precision_loss)]
+    pub fn progress(&self, elapsed: Duration) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
 
-        while ANIMATION_MANAGER.lock().in_progress(self.hwnd) {
-            if spent_duration.elapsed() >= max_duration {
-                ANIMATION_MANAGER.lock().end(self.hwnd);
-            }
+        (elapsed.as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+    }
+
+    /// The position of the rect at the given progress through the motion
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn rect_at(&self, progress: f64) -> Rect {
+        if progress >= 1.0 {
+            return self.target;
+        }
+
+        let progress = progress.max(0.0);
+        let eased = apply_ease_func(progress, self.style);
+        let carried = carry(progress) * duration_ms(self.duration);
+
+        let start = rect_components(&self.start);
+        let target = rect_components(&self.target);
+        let velocity = self.initial_velocity.components();
+
+        let mut components = [0; 4];
+        for (idx, component) in components.iter_mut().enumerate() {
+            *component = velocity[idx]
+                .mul_add(
+                    carried,
+                    (target[idx] - start[idx]).mul_add(eased, start[idx]),
+                )
+                .round() as i32;
+        }
+
+        let [left, top, right, bottom] = components;
+
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// The velocity of the rect at the given progress through the motion
+    pub fn velocity_at(&self, progress: f64) -> RectVelocity {
+        if progress >= 1.0 || self.duration.is_zero() {
+            return RectVelocity::default();
+        }
 
-            std::thread::sleep(Duration::from_millis(
-                ANIMATION_DURATION.load(Ordering::SeqCst) / 2,
-            ));
+        let progress = progress.max(0.0);
+        let duration = duration_ms(self.duration);
+
+        // the ease functions are only known by their values, so their slope is estimated from
+        // either side of the current progress
+        let low = (progress - SLOPE_STEP).max(0.0);
+        let high = (progress + SLOPE_STEP).min(1.0);
+        let ease_slope =
+            (apply_ease_func(high, self.style) - apply_ease_func(low, self.style)) / (high - low);
+        let carry_slope = carry_slope(progress);
+
+        let start = rect_components(&self.start);
+        let target = rect_components(&self.target);
+        let velocity = self.initial_velocity.components();
+
+        let mut components = [0.0; 4];
+        for (idx, component) in components.iter_mut().enumerate() {
+            *component = velocity[idx].mul_add(
+                carry_slope,
+                (target[idx] - start[idx]) * ease_slope / duration,
+            );
         }
 
-        let latest_cancel_idx = ANIMATION_MANAGER.lock().latest_cancel_idx(self.hwnd);
+        RectVelocity::from_components(components)
+    }
+
+    /// A new motion towards a different target which starts from wherever this motion is at the
+    /// given progress, moving at the same velocity
+    pub fn retarget(
+        &self,
+        progress: f64,
+        target: Rect,
+        duration: Duration,
+        style: AnimationStyle,
+    ) -> Self {
+        Self {
+            start: self.rect_at(progress),
+            target,
+            initial_velocity: self.velocity_at(progress),
+            duration,
+            style,
+        }
+    }
+}
+
+const SLOPE_STEP: f64 = 0.001;
+
+/// A curve which starts with a slope of 1.0 and returns to 0.0 with a slope of 0.0 at the end of
+/// the motion, used to carry an initial velocity into a motion without moving its end point
+fn carry(t: f64) -> f64 {
+    t * (1.0 - t).powi(2)
+}
+
+fn carry_slope(t: f64) -> f64 {
+    (1.0 - t) * 3.0f64.mul_add(-t, 1.0)
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn rect_components(rect: &Rect) -> [f64; 4] {
+    [
+        f64::from(rect.left),
+        f64::from(rect.top),
+        f64::from(rect.right),
+        f64::from(rect.bottom),
+    ]
+}
 
-        ANIMATION_MANAGER.lock().end_cancel(self.hwnd);
+/// A single frame of an animation to be rendered
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationFrame {
+    pub rect: Rect,
+    pub top: bool,
+    pub finished: bool,
+}
 
-        latest_cancel_idx == cancel_idx
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Animation {
+    pub hwnd: isize,
+}
+
+impl Animation {
+    pub fn new(hwnd: isize) -> Self {
+        Self { hwnd }
     }
 
     #[allow(clippy::cast_possible_truncation)]
-    pub fn lerp(start: i32, end: i32, t: f64) -> i32 {
-        let time = apply_ease_func(t);
+    pub fn lerp(start: i32, end: i32, t: f64, style: AnimationStyle) -> i32 {
+        let time = apply_ease_func(t, style);
         f64::from(end - start)
             .mul_add(time, f64::from(start))
             .round() as i32
     }
 
-    pub fn lerp_rect(start_rect: &Rect, end_rect: &Rect, t: f64) -> Rect {
+    pub fn lerp_rect(start_rect: &Rect, end_rect: &Rect, t: f64, style: AnimationStyle) -> Rect {
         Rect {
-            left: Self::lerp(start_rect.left, end_rect.left, t),
-            top: Self::lerp(start_rect.top, end_rect.top, t),
-            right: Self::lerp(start_rect.right, end_rect.right, t),
-            bottom: Self::lerp(start_rect.bottom, end_rect.bottom, t),
+            left: Self::lerp(start_rect.left, end_rect.left, t, style),
+            top: Self::lerp(start_rect.top, end_rect.top, t, style),
+            right: Self::lerp(start_rect.right, end_rect.right, t, style),
+            bottom: Self::lerp(start_rect.bottom, end_rect.bottom, t, style),
         }
     }
 
-    #[allow(clippy::cast_precision_loss)]
+    /// Start moving the window towards the target. Returns true if a new animation has been
+    /// started which needs to be rendered with `animate`, or false if an animation which was
+    /// already in progress has been retargeted and will keep rendering on its own
+    pub fn start(
+        &self,
+        start_rect: &Rect,
+        target_rect: &Rect,
+        top: bool,
+        settings: AnimationSettings,
+    ) -> bool {
+        ANIMATION_MANAGER
+            .lock()
+            .start(self.hwnd, start_rect, target_rect, top, settings)
+    }
+
+    /// Render the frames of the animation for this window until it reaches its target, including
+    /// any retargeting that happens while it is in progress
     pub fn animate(
         &mut self,
-        duration: Duration,
-        mut render_callback: impl FnMut(f64) -> Result<()>,
+        mut render_callback: impl FnMut(AnimationFrame) -> Result<()>,
     ) -> Result<()> {
-        if ANIMATION_MANAGER.lock().in_progress(self.hwnd) {
-            let should_animate = self.cancel();
+        loop {
+            let frame_start = Instant::now();
 
-            if !should_animate {
+            let Some((frame, fps)) = ANIMATION_MANAGER.lock().frame(self.hwnd) else {
                 return Ok(());
-            }
-        }
+            };
 
-        ANIMATION_MANAGER.lock().start(self.hwnd);
-
-        let target_frame_time = Duration::from_millis(1000 / ANIMATION_FPS.load(Ordering::Relaxed));
-        let mut progress = 0.0;
-        let animation_start = Instant::now();
-
-        // start animation
-        while progress < 1.0 {
-            // check if animation is cancelled
-            if ANIMATION_MANAGER.lock().is_cancelled(self.hwnd) {
-                // cancel animation
-                ANIMATION_MANAGER.lock().cancel(self.hwnd);
-                return Ok(());
+            if frame.finished {
+                // process the last frame to set the target position
+                return render_callback(frame);
             }
 
-            let frame_start = Instant::now();
-            // calculate progress
-            progress = animation_start.elapsed().as_millis() as f64 / duration.as_millis() as f64;
-            render_callback(progress).ok();
+            render_callback(frame).ok();
 
             // sleep until next frame
+            let target_frame_time = Duration::from_millis(1000 / fps.max(1));
             let frame_time_elapsed = frame_start.elapsed();
 
             if frame_time_elapsed < target_frame_time {
                 std::thread::sleep(target_frame_time - frame_time_elapsed);
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 800,
+        bottom: 600,
+    };
+
+    const TARGET: Rect = Rect {
+        left: 1000,
+        top: 200,
+        right: 400,
+        bottom: 300,
+    };
+
+    const DURATION: Duration = Duration::from_millis(250);
+
+    #[test]
+    fn motions_start_and_end_on_their_endpoints() {
+        for style in [
+            AnimationStyle::Linear,
+            AnimationStyle::EaseInOutCubic,
+            AnimationStyle::EaseOutBounce,
+            AnimationStyle::CubicBezier(0.25, 0.1, 0.25, 1.0),
+            AnimationStyle::Spring {
+                stiffness: 170.0,
+                damping: 26.0,
+            },
+        ] {
+            let motion = RectMotion::new(START, TARGET, DURATION, style);
+
+            assert_eq!(motion.rect_at(0.0), START, "{style:?}");
+            assert_eq!(motion.rect_at(1.0), TARGET, "{style:?}");
+            assert_eq!(
+                motion.velocity_at(1.0),
+                RectVelocity::default(),
+                "{style:?}"
+            );
+        }
+    }
 
-        ANIMATION_MANAGER.lock().end(self.hwnd);
+    #[test]
+    fn retargeted_motions_keep_their_position_and_velocity() {
+        let motion = RectMotion::new(START, TARGET, DURATION, AnimationStyle::EaseInOutCubic);
+        let retargeted = motion.retarget(0.4, START, DURATION, AnimationStyle::EaseInOutCubic);
 
-        // limit progress to 1.0 if animation took longer
-        if progress > 1.0 {
-            progress = 1.0;
+        assert_eq!(retargeted.rect_at(0.0), motion.rect_at(0.4));
+        assert_eq!(retargeted.rect_at(1.0), START);
+
+        let before = motion.velocity_at(0.4).components();
+        let after = retargeted.velocity_at(0.0).components();
+
+        for (before, after) in before.iter().zip(after) {
+            assert!((before - after).abs() < 0.01, "{before} != {after}");
         }
 
-        // process animation for 1.0 to set target position
-        render_callback(progress)
+        // the motion was moving towards the old target, so it can't already be at rest
+        assert!(before.iter().any(|velocity| velocity.abs() > 1.0));
+    }
+
+    #[test]
+    fn zero_duration_motions_jump_to_their_target() {
+        let motion = RectMotion::new(START, TARGET, Duration::ZERO, AnimationStyle::Linear);
+
+        let progress = motion.progress(Duration::ZERO);
+        assert!((progress - 1.0).abs() < f64::EPSILON);
+        assert_eq!(motion.rect_at(progress), TARGET);
+        assert_eq!(motion.velocity_at(0.0), RectVelocity::default());
+
+        // a motion which is retargeted without a duration still carries no velocity forward
+        let moving = RectMotion::new(START, TARGET, DURATION, AnimationStyle::Linear);
+        let retargeted = moving.retarget(0.5, START, Duration::ZERO, AnimationStyle::Linear);
+
+        assert_eq!(retargeted.rect_at(retargeted.progress(DURATION)), START);
+        assert_eq!(retargeted.velocity_at(0.5), RectVelocity::default());
+
+        let halfway = retargeted.rect_at(0.5);
+        assert!(rect_components(&halfway).iter().all(|c| c.is_finite()));
     }
 }
@@ -2,14 +2,27 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use crate::animation::AnimationFrame;
+use crate::animation::AnimationSettings;
+use crate::animation::RectMotion;
+use crate::core::Rect;
 
 pub static ANIMATIONS_IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Clone, Copy)]
 struct AnimationState {
-    pub in_progress: bool,
-    pub cancel_idx_counter: usize,
-    pub pending_cancel_count: usize,
+    pub motion: RectMotion,
+    pub started: Instant,
+    pub top: bool,
+    pub fps: u64,
+}
+
+impl AnimationState {
+    fn progress(&self) -> f64 {
+        self.motion.progress(self.started.elapsed())
+    }
 }
 
 #[derive(Debug)]
@@ -30,79 +43,81 @@ impl AnimationManager {
         }
     }
 
-    pub fn is_cancelled(&self, hwnd: isize) -> bool {
-        if let Some(animation_state) = self.animations.get(&hwnd) {
-            animation_state.pending_cancel_count > 0
-        } else {
-            false
-        }
-    }
-
     pub fn in_progress(&self, hwnd: isize) -> bool {
-        if let Some(animation_state) = self.animations.get(&hwnd) {
-            animation_state.in_progress
-        } else {
-            false
-        }
-    }
-
-    pub fn init_cancel(&mut self, hwnd: isize) -> usize {
-        if let Some(animation_state) = self.animations.get_mut(&hwnd) {
-            animation_state.pending_cancel_count += 1;
-            animation_state.cancel_idx_counter += 1;
-
-            // return cancel idx
-            animation_state.cancel_idx_counter
-        } else {
-            0
-        }
+        self.animations.contains_key(&hwnd)
     }
 
-    pub fn latest_cancel_idx(&mut self, hwnd: isize) -> usize {
-        if let Some(animation_state) = self.animations.get_mut(&hwnd) {
-            animation_state.cancel_idx_counter
-        } else {
-            0
-        }
-    }
+    /// Start a new animation for a window, or retarget the animation already in progress from
+    /// its current position and velocity. Returns true if a new animation was started
+    pub fn start(
+        &mut self,
+        hwnd: isize,
+        start_rect: &Rect,
+        target_rect: &Rect,
+        top: bool,
+        settings: AnimationSettings,
+    ) -> bool {
+        match self.animations.entry(hwnd) {
+            Entry::Occupied(mut entry) => {
+                let state = entry.get_mut();
+                let progress = state.progress();
+
+                state.motion = state.motion.retarget(
+                    progress,
+                    *target_rect,
+                    settings.duration,
+                    settings.style,
+                );
+                state.started = Instant::now();
+                state.top = top;
+                state.fps = settings.fps;
+
+                false
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(AnimationState {
+                    motion: RectMotion::new(
+                        *start_rect,
+                        *target_rect,
+                        settings.duration,
+                        settings.style,
+                    ),
+                    started: Instant::now(),
+                    top,
+                    fps: settings.fps,
+                });
 
-    pub fn end_cancel(&mut self, hwnd: isize) {
-        if let Some(animation_state) = self.animations.get_mut(&hwnd) {
-            animation_state.pending_cancel_count -= 1;
-        }
-    }
+                ANIMATIONS_IN_PROGRESS.store(self.animations.len(), Ordering::Release);
 
-    pub fn cancel(&mut self, hwnd: isize) {
-        if let Some(animation_state) = self.animations.get_mut(&hwnd) {
-            animation_state.in_progress = false;
+                true
+            }
         }
     }
 
-    pub fn start(&mut self, hwnd: isize) {
-        if let Entry::Vacant(e) = self.animations.entry(hwnd) {
-            e.insert(AnimationState {
-                in_progress: true,
-                cancel_idx_counter: 0,
-                pending_cancel_count: 0,
-            });
+    /// The frame to render for a window right now along with the frame rate of its animation,
+    /// ending the animation once its last frame has been reached
+    pub fn frame(&mut self, hwnd: isize) -> Option<(AnimationFrame, u64)> {
+        let state = self.animations.get(&hwnd).copied()?;
+        let progress = state.progress();
+        let finished = progress >= 1.0;
 
-            ANIMATIONS_IN_PROGRESS.store(self.animations.len(), Ordering::Release);
-            return;
+        if finished {
+            self.end(hwnd);
         }
 
-        if let Some(animation_state) = self.animations.get_mut(&hwnd) {
-            animation_state.in_progress = true;
-        }
+        Option::from((
+            AnimationFrame {
+                rect: state.motion.rect_at(progress),
+                top: state.top,
+                finished,
+            },
+            state.fps,
+        ))
     }
 
     pub fn end(&mut self, hwnd: isize) {
-        if let Some(animation_state) = self.animations.get_mut(&hwnd) {
-            animation_state.in_progress = false;
-
-            if animation_state.pending_cancel_count == 0 {
-                self.animations.remove(&hwnd);
-                ANIMATIONS_IN_PROGRESS.store(self.animations.len(), Ordering::Release);
-            }
+        if self.animations.remove(&hwnd).is_some() {
+            ANIMATIONS_IN_PROGRESS.store(self.animations.len(), Ordering::Release);
         }
     }
 }
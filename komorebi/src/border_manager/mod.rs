@@ -1,33 +1,44 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
+#[cfg(windows)]
 mod border;
 
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::WindowKind;
+#[cfg(windows)]
 use crate::ring::Ring;
+#[cfg(windows)]
 use crate::workspace_reconciliator::ALT_TAB_HWND;
 use crate::Colour;
 use crate::Rgb;
+#[cfg(windows)]
 use crate::WindowManager;
+#[cfg(windows)]
 use crate::WindowsApi;
+#[cfg(windows)]
 use border::border_hwnds;
+#[cfg(windows)]
 use border::Border;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicCell;
+#[cfg(windows)]
 use crossbeam_utils::atomic::AtomicConsume;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(windows)]
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU32;
+#[cfg(windows)]
 use std::sync::atomic::Ordering;
+#[cfg(windows)]
 use std::sync::Arc;
 use std::sync::OnceLock;
 
@@ -53,9 +64,13 @@ lazy_static! {
         AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(245, 245, 165))));
 }
 
+#[cfg(windows)]
 lazy_static! {
     static ref BORDERS_MONITORS: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
     static ref BORDER_STATE: Mutex<HashMap<String, Border>> = Mutex::new(HashMap::new());
+}
+
+lazy_static! {
     static ref FOCUS_STATE: Mutex<HashMap<isize, WindowKind>> = Mutex::new(HashMap::new());
 }
 
@@ -71,6 +86,7 @@ fn event_tx() -> Sender<Notification> {
     channel().0.clone()
 }
 
+#[cfg(windows)]
 fn event_rx() -> Receiver<Notification> {
     channel().1.clone()
 }
@@ -81,6 +97,7 @@ pub fn send_notification(hwnd: Option<isize>) {
     }
 }
 
+#[cfg(windows)]
 pub fn destroy_all_borders() -> color_eyre::Result<()> {
     let mut borders = BORDER_STATE.lock();
     tracing::info!(
@@ -114,6 +131,14 @@ pub fn destroy_all_borders() -> color_eyre::Result<()> {
     Ok(())
 }
 
+/// There are no border windows without the Windows backend, so only the focus state is cleared
+#[cfg(not(windows))]
+pub fn destroy_all_borders() -> color_eyre::Result<()> {
+    FOCUS_STATE.lock().clear();
+    Ok(())
+}
+
+#[cfg(windows)]
 fn window_kind_colour(focus_kind: WindowKind) -> u32 {
     match focus_kind {
         WindowKind::Unfocused => UNFOCUSED.load(Ordering::SeqCst),
@@ -124,6 +149,7 @@ fn window_kind_colour(focus_kind: WindowKind) -> u32 {
     }
 }
 
+#[cfg(windows)]
 pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
         match handle_notifications(wm.clone()) {
@@ -137,6 +163,7 @@ pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     });
 }
 
+#[cfg(windows)]
 pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
     tracing::info!("listening");
 
@@ -1,4 +1,6 @@
 use clap::ValueEnum;
+use color_eyre::eyre::bail;
+use color_eyre::Result;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -39,4 +41,148 @@ pub enum AnimationStyle {
     EaseInBounce,
     EaseOutBounce,
     EaseInOutBounce,
+    /// A CSS-style cubic bezier curve with the control points (x1, y1) and (x2, y2), where x1 and
+    /// x2 must be between 0.0 and 1.0
+    #[value(skip)]
+    #[strum(to_string = "CubicBezier")]
+    CubicBezier(f64, f64, f64, f64),
+    /// A damped spring which settles at the end of the animation, where a lower damping relative
+    /// to the stiffness gives more oscillation
+    #[value(skip)]
+    #[strum(to_string = "Spring")]
+    Spring {
+        stiffness: f64,
+        damping: f64,
+    },
+}
+
+impl AnimationStyle {
+    /// Styles with parameters can only be used when every parameter is a finite number
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::CubicBezier(x1, y1, x2, y2)
+                if [x1, y1, x2, y2].iter().any(|value| !value.is_finite()) =>
+            {
+                bail!("the control points of a cubic bezier animation must be finite numbers");
+            }
+            Self::Spring { stiffness, damping }
+                if !stiffness.is_finite() || !damping.is_finite() =>
+            {
+                bail!("the stiffness and damping of a spring animation must be finite numbers");
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// The kinds of operation that move windows, which can each be animated differently
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+)]
+pub enum AnimationOperation {
+    /// Tiles being rearranged, eg. after a window is opened or closed or the layout is changed
+    #[default]
+    Reflow,
+    /// Windows being moved between containers, eg. when moving, swapping or stacking windows
+    Movement,
+    /// Windows being repositioned as a result of a workspace being focused
+    WorkspaceSwitch,
+    /// Windows and workspaces being moved between monitors
+    MonitorMove,
+    /// Every workspace being retiled after the connected displays have changed
+    DisplayChange,
+}
+
+/// Animation settings for a kind of operation, which fall back to the global animation settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AnimationOverride {
+    /// Enable or disable animations for this operation (default: global setting)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Set the animation duration in ms for this operation (default: global setting)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    /// Set the animation style for this operation (default: global setting)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<AnimationStyle>,
+    /// Set the animation FPS for this operation (default: global setting)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fps: Option<u64>,
+}
+
+/// How windows are moved when switching between workspaces on a monitor
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+)]
+pub enum WorkspaceTransition {
+    /// Slide the workspaces left and right, towards the workspace with the higher index
+    SlideHorizontal,
+    /// Slide the workspaces up and down, towards the workspace with the higher index
+    SlideVertical,
+    /// Fade the windows of the previous workspace out and the windows of the next workspace in
+    Fade,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn styles_with_parameters_can_be_displayed() {
+        assert_eq!(
+            AnimationStyle::CubicBezier(0.25, 0.1, 0.25, 1.0).to_string(),
+            "CubicBezier"
+        );
+
+        assert_eq!(
+            AnimationStyle::Spring {
+                stiffness: 100.0,
+                damping: 10.0
+            }
+            .to_string(),
+            "Spring"
+        );
+    }
+
+    #[test]
+    fn styles_with_non_finite_parameters_are_rejected() {
+        assert!(AnimationStyle::CubicBezier(0.25, f64::NAN, 0.25, 1.0)
+            .validate()
+            .is_err());
+
+        assert!(AnimationStyle::Spring {
+            stiffness: f64::INFINITY,
+            damping: 10.0
+        }
+        .validate()
+        .is_err());
+
+        assert!(AnimationStyle::CubicBezier(0.25, 0.1, 0.25, 1.0)
+            .validate()
+            .is_ok());
+    }
 }
@@ -1,4 +1,5 @@
 use std::num::NonZeroUsize;
+use std::ops::Range;
 
 use clap::ValueEnum;
 use schemars::JsonSchema;
@@ -11,30 +12,45 @@ use super::custom_layout::Column;
 use super::custom_layout::ColumnSplit;
 use super::custom_layout::ColumnSplitWithCapacity;
 use super::CustomLayout;
+use super::CustomLayoutTree;
 use super::DefaultLayout;
+use super::LayoutOptions;
 use super::Rect;
+use super::RelativeRect;
 
 pub trait Arrangement {
+    #[allow(clippy::too_many_arguments)]
     fn calculate(
         &self,
         area: &Rect,
         len: NonZeroUsize,
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
-        resize_dimensions: &[Option<Rect>],
+        resize_dimensions: &[Option<RelativeRect>],
+        focused_idx: usize,
+        layout_options: Option<LayoutOptions>,
+        latest_layout: &[Rect],
     ) -> Vec<Rect>;
 }
 
 impl Arrangement for DefaultLayout {
-    #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+    #[allow(
+        clippy::too_many_lines,
+        clippy::cognitive_complexity,
+        clippy::too_many_arguments
+    )]
     fn calculate(
         &self,
         area: &Rect,
         len: NonZeroUsize,
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
-        resize_dimensions: &[Option<Rect>],
+        resize_dimensions: &[Option<RelativeRect>],
+        focused_idx: usize,
+        layout_options: Option<LayoutOptions>,
+        latest_layout: &[Rect],
     ) -> Vec<Rect> {
+        let resize_dimensions = &absolute_resize_dimensions(area, resize_dimensions);
         let len = usize::from(len);
         let mut dimensions = match self {
             Self::BSP => recursive_fibonacci(
@@ -97,23 +113,29 @@ impl Arrangement for DefaultLayout {
             Self::VerticalStack => {
                 let mut layouts: Vec<Rect> = vec![];
 
-                let primary_right = match len {
-                    1 => area.right,
-                    _ => area.right / 2,
+                let master_count = layout_options.unwrap_or_default().master_count().min(len);
+                let stack_count = len - master_count;
+
+                let primary_right = match stack_count {
+                    0 => area.right,
+                    _ => master_size(area.right, layout_options),
                 };
 
                 let main_left = area.left;
                 let stack_left = area.left + primary_right;
 
                 if len >= 1 {
-                    layouts.push(Rect {
-                        left: main_left,
-                        top: area.top,
-                        right: primary_right,
-                        bottom: area.bottom,
-                    });
+                    layouts.append(&mut rows(
+                        &Rect {
+                            left: main_left,
+                            top: area.top,
+                            right: primary_right,
+                            bottom: area.bottom,
+                        },
+                        master_count,
+                    ));
 
-                    if len > 1 {
+                    if stack_count > 0 {
                         layouts.append(&mut rows(
                             &Rect {
                                 left: stack_left,
@@ -121,12 +143,13 @@ impl Arrangement for DefaultLayout {
                                 right: area.right - primary_right,
                                 bottom: area.bottom,
                             },
-                            len - 1,
+                            stack_count,
                         ));
                     }
                 }
 
-                let adjustment = calculate_vertical_stack_adjustment(resize_dimensions);
+                let adjustment =
+                    calculate_vertical_stack_adjustment(resize_dimensions, master_count);
                 layouts
                     .iter_mut()
                     .zip(adjustment.iter())
@@ -141,14 +164,18 @@ impl Arrangement for DefaultLayout {
                     layout_flip,
                     Some(Axis::Horizontal | Axis::HorizontalAndVertical)
                 ) {
-                    if let 2.. = len {
-                        let (primary, rest) = layouts.split_at_mut(1);
-                        let primary = &mut primary[0];
+                    if let 1.. = stack_count {
+                        let (master, stack) = layouts.split_at_mut(master_count);
+                        let stack_left = master[0].left;
+                        let master_left = stack_left + stack[0].right;
+
+                        for rect in stack.iter_mut() {
+                            rect.left = stack_left;
+                        }
 
-                        for rect in rest.iter_mut() {
-                            rect.left = primary.left;
+                        for rect in master.iter_mut() {
+                            rect.left = master_left;
                         }
-                        primary.left = rest[0].left + rest[0].right;
                     }
                 }
 
@@ -156,8 +183,14 @@ impl Arrangement for DefaultLayout {
                     layout_flip,
                     Some(Axis::Vertical | Axis::HorizontalAndVertical)
                 ) {
-                    if let 3.. = len {
-                        rows_reverse(&mut layouts[1..]);
+                    let (master, stack) = layouts.split_at_mut(master_count);
+
+                    if let 2.. = master.len() {
+                        rows_reverse(master);
+                    }
+
+                    if let 2.. = stack.len() {
+                        rows_reverse(stack);
                     }
                 }
 
@@ -167,25 +200,31 @@ impl Arrangement for DefaultLayout {
                 // Shamelessly borrowed from LeftWM: https://github.com/leftwm/leftwm/commit/f673851745295ae7584a102535566f559d96a941
                 let mut layouts: Vec<Rect> = vec![];
 
-                let primary_width = match len {
-                    1 => area.right,
-                    _ => area.right / 2,
+                let master_count = layout_options.unwrap_or_default().master_count().min(len);
+                let stack_count = len - master_count;
+
+                let primary_width = match stack_count {
+                    0 => area.right,
+                    _ => master_size(area.right, layout_options),
                 };
 
-                let primary_left = match len {
-                    1 => 0,
+                let primary_left = match stack_count {
+                    0 => 0,
                     _ => area.right - primary_width,
                 };
 
                 if len >= 1 {
-                    layouts.push(Rect {
-                        left: area.left + primary_left,
-                        top: area.top,
-                        right: primary_width,
-                        bottom: area.bottom,
-                    });
+                    layouts.append(&mut rows(
+                        &Rect {
+                            left: area.left + primary_left,
+                            top: area.top,
+                            right: primary_width,
+                            bottom: area.bottom,
+                        },
+                        master_count,
+                    ));
 
-                    if len > 1 {
+                    if stack_count > 0 {
                         layouts.append(&mut rows(
                             &Rect {
                                 left: area.left,
@@ -193,12 +232,13 @@ impl Arrangement for DefaultLayout {
                                 right: primary_left,
                                 bottom: area.bottom,
                             },
-                            len - 1,
+                            stack_count,
                         ));
                     }
                 }
 
-                let adjustment = calculate_right_vertical_stack_adjustment(resize_dimensions);
+                let adjustment =
+                    calculate_right_vertical_stack_adjustment(resize_dimensions, master_count);
                 layouts
                     .iter_mut()
                     .zip(adjustment.iter())
@@ -213,13 +253,17 @@ impl Arrangement for DefaultLayout {
                     layout_flip,
                     Some(Axis::Horizontal | Axis::HorizontalAndVertical)
                 ) {
-                    if let 2.. = len {
-                        let (primary, rest) = layouts.split_at_mut(1);
-                        let primary = &mut primary[0];
+                    if let 1.. = stack_count {
+                        let (master, stack) = layouts.split_at_mut(master_count);
+                        let master_left = stack[0].left;
+                        let stack_left = master_left + master[0].right;
+
+                        for rect in master.iter_mut() {
+                            rect.left = master_left;
+                        }
 
-                        primary.left = rest[0].left;
-                        for rect in rest.iter_mut() {
-                            rect.left = primary.left + primary.right;
+                        for rect in stack.iter_mut() {
+                            rect.left = stack_left;
                         }
                     }
                 }
@@ -228,8 +272,14 @@ impl Arrangement for DefaultLayout {
                     layout_flip,
                     Some(Axis::Vertical | Axis::HorizontalAndVertical)
                 ) {
-                    if let 3.. = len {
-                        rows_reverse(&mut layouts[1..]);
+                    let (master, stack) = layouts.split_at_mut(master_count);
+
+                    if let 2.. = master.len() {
+                        rows_reverse(master);
+                    }
+
+                    if let 2.. = stack.len() {
+                        rows_reverse(stack);
                     }
                 }
 
@@ -238,23 +288,29 @@ impl Arrangement for DefaultLayout {
             Self::HorizontalStack => {
                 let mut layouts: Vec<Rect> = vec![];
 
-                let bottom = match len {
-                    1 => area.bottom,
-                    _ => area.bottom / 2,
+                let master_count = layout_options.unwrap_or_default().master_count().min(len);
+                let stack_count = len - master_count;
+
+                let bottom = match stack_count {
+                    0 => area.bottom,
+                    _ => master_size(area.bottom, layout_options),
                 };
 
                 let main_top = area.top;
                 let stack_top = area.top + bottom;
 
                 if len >= 1 {
-                    layouts.push(Rect {
-                        left: area.left,
-                        top: main_top,
-                        right: area.right,
-                        bottom,
-                    });
+                    layouts.append(&mut columns(
+                        &Rect {
+                            left: area.left,
+                            top: main_top,
+                            right: area.right,
+                            bottom,
+                        },
+                        master_count,
+                    ));
 
-                    if len > 1 {
+                    if stack_count > 0 {
                         layouts.append(&mut columns(
                             &Rect {
                                 left: area.left,
@@ -262,12 +318,13 @@ impl Arrangement for DefaultLayout {
                                 right: area.right,
                                 bottom: area.bottom - bottom,
                             },
-                            len - 1,
+                            stack_count,
                         ));
                     }
                 }
 
-                let adjustment = calculate_horizontal_stack_adjustment(resize_dimensions);
+                let adjustment =
+                    calculate_horizontal_stack_adjustment(resize_dimensions, master_count);
                 layouts
                     .iter_mut()
                     .zip(adjustment.iter())
@@ -282,14 +339,18 @@ impl Arrangement for DefaultLayout {
                     layout_flip,
                     Some(Axis::Vertical | Axis::HorizontalAndVertical)
                 ) {
-                    if let 2.. = len {
-                        let (primary, rest) = layouts.split_at_mut(1);
-                        let primary = &mut primary[0];
+                    if let 1.. = stack_count {
+                        let (master, stack) = layouts.split_at_mut(master_count);
+                        let stack_top = master[0].top;
+                        let master_top = stack_top + stack[0].bottom;
 
-                        for rect in rest.iter_mut() {
-                            rect.top = primary.top;
+                        for rect in stack.iter_mut() {
+                            rect.top = stack_top;
+                        }
+
+                        for rect in master.iter_mut() {
+                            rect.top = master_top;
                         }
-                        primary.top = rest[0].top + rest[0].bottom;
                     }
                 }
 
@@ -297,8 +358,14 @@ impl Arrangement for DefaultLayout {
                     layout_flip,
                     Some(Axis::Horizontal | Axis::HorizontalAndVertical)
                 ) {
-                    if let 3.. = len {
-                        columns_reverse(&mut layouts[1..]);
+                    let (master, stack) = layouts.split_at_mut(master_count);
+
+                    if let 2.. = master.len() {
+                        columns_reverse(master);
+                    }
+
+                    if let 2.. = stack.len() {
+                        columns_reverse(stack);
                     }
                 }
 
@@ -416,55 +483,221 @@ impl Arrangement for DefaultLayout {
 
                 layouts
             }
-            #[allow(
-                clippy::cast_precision_loss,
-                clippy::cast_possible_truncation,
-                clippy::cast_possible_wrap
-            )]
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
             Self::Grid => {
                 // Shamelessly lifted from LeftWM
                 // https://github.com/leftwm/leftwm/blob/18675067b8450e520ef75db2ebbb0d973aa1199e/leftwm-core/src/layouts/grid_horizontal.rs
-                let mut layouts: Vec<Rect> = vec![];
-                layouts.resize(len, Rect::default());
+                let grid_columns = Self::grid_columns(len);
+                let mut layouts = vec![Rect::default(); len];
 
-                let len = len as i32;
+                let win_width = area.right / grid_columns.len() as i32;
 
-                let num_cols = (len as f32).sqrt().ceil() as i32;
-                let mut iter = layouts.iter_mut().enumerate().peekable();
+                for (col, rows) in grid_columns.iter().enumerate() {
+                    let win_height = area.bottom / rows.len() as i32;
 
-                for col in 0..num_cols {
-                    let iter_peek = iter.peek().map(|x| x.0).unwrap_or_default() as i32;
-                    let remaining_windows = len - iter_peek;
-                    let remaining_columns = num_cols - col;
-                    let num_rows_in_this_col = remaining_windows / remaining_columns;
+                    for (row, idx) in rows.clone().enumerate() {
+                        layouts[idx] = Rect {
+                            left: area.left + win_width * col as i32,
+                            top: area.top + win_height * row as i32,
+                            right: win_width,
+                            bottom: win_height,
+                        };
+                    }
+                }
 
-                    let win_height = area.bottom / num_rows_in_this_col;
-                    let win_width = area.right / num_cols;
+                let adjustment = calculate_column_groups_adjustment(
+                    resize_dimensions,
+                    &grid_columns
+                        .into_iter()
+                        .map(|rows| (rows, false))
+                        .collect::<Vec<_>>(),
+                    layouts.len(),
+                );
+                layouts
+                    .iter_mut()
+                    .zip(adjustment.iter())
+                    .for_each(|(layout, adjustment)| {
+                        layout.top += adjustment.top;
+                        layout.bottom += adjustment.bottom;
+                        layout.left += adjustment.left;
+                        layout.right += adjustment.right;
+                    });
 
-                    for row in 0..num_rows_in_this_col {
-                        if let Some((_idx, win)) = iter.next() {
-                            let mut left = area.left + win_width * col;
-                            let mut top = area.top + win_height * row;
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Horizontal | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.left = area.left + area.right - (rect.left - area.left) - rect.right;
+                    }
+                }
 
-                            match layout_flip {
-                                Some(Axis::Horizontal) => {
-                                    left = area.right - win_width * (col + 1) + area.left;
-                                }
-                                Some(Axis::Vertical) => {
-                                    top = area.bottom - win_height * (row + 1) + area.top;
-                                }
-                                Some(Axis::HorizontalAndVertical) => {
-                                    left = area.right - win_width * (col + 1) + area.left;
-                                    top = area.bottom - win_height * (row + 1) + area.top;
-                                }
-                                None => {} // No flip
-                            }
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Vertical | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.top = area.top + area.bottom - (rect.top - area.top) - rect.bottom;
+                    }
+                }
 
-                            win.bottom = win_height;
-                            win.right = win_width;
-                            win.left = left;
-                            win.top = top;
-                        }
+                layouts
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            Self::CenteredMaster => {
+                let layout_options = layout_options.unwrap_or_default();
+                let (left_stack, right_stack) = layout_options.centered_master_fill().stacks(len);
+
+                let centre_width = match len {
+                    1 => area.right,
+                    _ => (area.right / 100) * layout_options.centre_width_percentage() as i32,
+                };
+
+                // When one of the stacks is empty the master column takes over its space
+                let side_width = (area.right - centre_width) / 2;
+                let left_width = if left_stack.is_empty() { 0 } else { side_width };
+                let right_width = if right_stack.is_empty() {
+                    0
+                } else {
+                    side_width
+                };
+
+                let mut layouts = vec![Rect::default(); len];
+                layouts[0] = Rect {
+                    left: area.left + left_width,
+                    top: area.top,
+                    right: area.right - left_width - right_width,
+                    bottom: area.bottom,
+                };
+
+                if !left_stack.is_empty() {
+                    let stack_rows = rows(
+                        &Rect {
+                            left: area.left,
+                            top: area.top,
+                            right: left_width,
+                            bottom: area.bottom,
+                        },
+                        left_stack.len(),
+                    );
+
+                    for (idx, rect) in left_stack.iter().zip(stack_rows) {
+                        layouts[*idx] = rect;
+                    }
+                }
+
+                if !right_stack.is_empty() {
+                    let stack_rows = rows(
+                        &Rect {
+                            left: area.left + area.right - right_width,
+                            top: area.top,
+                            right: right_width,
+                            bottom: area.bottom,
+                        },
+                        right_stack.len(),
+                    );
+
+                    for (idx, rect) in right_stack.iter().zip(stack_rows) {
+                        layouts[*idx] = rect;
+                    }
+                }
+
+                let adjustment = calculate_centered_master_adjustment(
+                    resize_dimensions,
+                    &left_stack,
+                    &right_stack,
+                );
+                layouts
+                    .iter_mut()
+                    .zip(adjustment.iter())
+                    .for_each(|(layout, adjustment)| {
+                        layout.top += adjustment.top;
+                        layout.bottom += adjustment.bottom;
+                        layout.left += adjustment.left;
+                        layout.right += adjustment.right;
+                    });
+
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Horizontal | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.left = area.left + area.right - (rect.left - area.left) - rect.right;
+                    }
+                }
+
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Vertical | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.top = area.top + area.bottom - (rect.top - area.top) - rect.bottom;
+                    }
+                }
+
+                layouts
+            }
+            Self::Dwindle | Self::Spiral => {
+                let mut layouts = binary_split(
+                    area,
+                    len,
+                    layout_options.unwrap_or_default().split_ratio(),
+                    matches!(self, Self::Spiral),
+                );
+
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Horizontal | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.left = area.left + area.right - (rect.left - area.left) - rect.right;
+                    }
+                }
+
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Vertical | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.top = area.top + area.bottom - (rect.top - area.top) - rect.bottom;
+                    }
+                }
+
+                layouts
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            Self::Scrolling => {
+                let visible_columns = layout_options
+                    .unwrap_or_default()
+                    .scrolling_columns()
+                    .min(len);
+
+                let column_width = area.right / visible_columns as i32;
+                let first_visible = scrolling_viewport_start(
+                    area,
+                    len,
+                    visible_columns,
+                    focused_idx,
+                    latest_layout,
+                );
+
+                // Every container gets a column of the same width on an infinite horizontal strip,
+                // columns which fall outside of the viewport are hidden by the workspace
+                let mut layouts: Vec<Rect> = (0..len)
+                    .map(|i| Rect {
+                        left: area.left + column_width * (i as i32 - first_visible as i32),
+                        top: area.top,
+                        right: column_width,
+                        bottom: area.bottom,
+                    })
+                    .collect();
+
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Horizontal | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.left = area.left + area.right - (rect.left - area.left) - rect.right;
                     }
                 }
 
@@ -481,20 +714,36 @@ impl Arrangement for DefaultLayout {
 }
 
 impl Arrangement for CustomLayout {
+    #[allow(clippy::too_many_arguments)]
     fn calculate(
         &self,
         area: &Rect,
         len: NonZeroUsize,
         container_padding: Option<i32>,
         _layout_flip: Option<Axis>,
-        _resize_dimensions: &[Option<Rect>],
+        resize_dimensions: &[Option<RelativeRect>],
+        _focused_idx: usize,
+        _layout_options: Option<LayoutOptions>,
+        _latest_layout: &[Rect],
     ) -> Vec<Rect> {
+        let resize_dimensions = &absolute_resize_dimensions(area, resize_dimensions);
         let mut dimensions = vec![];
         let container_count = len.get();
 
         if container_count < self.len() {
             let mut layouts = columns(area, container_count);
             dimensions.append(&mut layouts);
+
+            let adjustment = calculate_columns_adjustment(resize_dimensions);
+            dimensions
+                .iter_mut()
+                .zip(adjustment.iter())
+                .for_each(|(layout, adjustment)| {
+                    layout.top += adjustment.top;
+                    layout.bottom += adjustment.bottom;
+                    layout.left += adjustment.left;
+                    layout.right += adjustment.right;
+                });
         } else {
             let count_map = self.column_container_counts();
 
@@ -529,6 +778,9 @@ impl Arrangement for CustomLayout {
                 // has not yet been met, this loop should not run for that final
                 // tertiary column
                 if idx < self.len() - offset.unwrap_or(0) {
+                    // Each column starts where the last container of the previous column ends,
+                    // which is the right edge of that column whether it is split horizontally
+                    // or vertically
                     let column_area = if idx == 0 {
                         Self::column_area_with_last(self.len(), area, primary_right, None, offset)
                     } else {
@@ -536,7 +788,7 @@ impl Arrangement for CustomLayout {
                             self.len(),
                             area,
                             primary_right,
-                            Option::from(dimensions[self.first_container_idx(idx - 1)]),
+                            Option::from(dimensions[self.first_container_idx(idx) - 1]),
                             offset,
                         )
                     };
@@ -549,7 +801,7 @@ impl Arrangement for CustomLayout {
                                 Self::main_column_area(
                                     area,
                                     primary_right,
-                                    Option::from(dimensions[self.first_container_idx(idx - 1)]),
+                                    Option::from(dimensions[self.first_container_idx(idx) - 1]),
                                 )
                             };
 
@@ -573,7 +825,7 @@ impl Arrangement for CustomLayout {
                                 self.len(),
                                 area,
                                 primary_right,
-                                Option::from(dimensions[self.first_container_idx(idx - 1)]),
+                                Option::from(dimensions[self.first_container_idx(idx) - 1]),
                                 offset,
                             );
 
@@ -593,6 +845,62 @@ impl Arrangement for CustomLayout {
                     }
                 }
             }
+
+            let adjustment = calculate_column_groups_adjustment(
+                resize_dimensions,
+                &self.column_ranges(container_count),
+                dimensions.len(),
+            );
+            dimensions
+                .iter_mut()
+                .zip(adjustment.iter())
+                .for_each(|(layout, adjustment)| {
+                    layout.top += adjustment.top;
+                    layout.bottom += adjustment.bottom;
+                    layout.left += adjustment.left;
+                    layout.right += adjustment.right;
+                });
+        }
+
+        dimensions
+            .iter_mut()
+            .for_each(|l| l.add_padding(container_padding.unwrap_or_default()));
+
+        dimensions
+    }
+}
+
+impl Arrangement for CustomLayoutTree {
+    #[allow(clippy::too_many_arguments)]
+    fn calculate(
+        &self,
+        area: &Rect,
+        len: NonZeroUsize,
+        container_padding: Option<i32>,
+        layout_flip: Option<Axis>,
+        _resize_dimensions: &[Option<RelativeRect>],
+        _focused_idx: usize,
+        _layout_options: Option<LayoutOptions>,
+        _latest_layout: &[Rect],
+    ) -> Vec<Rect> {
+        let mut dimensions = self.container_areas(area, len.get());
+
+        if matches!(
+            layout_flip,
+            Some(Axis::Horizontal | Axis::HorizontalAndVertical)
+        ) {
+            for rect in &mut dimensions {
+                rect.left = area.left + area.right - (rect.left - area.left) - rect.right;
+            }
+        }
+
+        if matches!(
+            layout_flip,
+            Some(Axis::Vertical | Axis::HorizontalAndVertical)
+        ) {
+            for rect in &mut dimensions {
+                rect.top = area.top + area.bottom - (rect.top - area.top) - rect.bottom;
+            }
         }
 
         dimensions
@@ -621,6 +929,81 @@ pub enum Axis {
     HorizontalAndVertical,
 }
 
+/// Every container except the last takes `split_ratio` of the remaining area, which is then split
+/// again for the next container. Dwindle alternates between placing containers on the left and at
+/// the top so that the remaining area is always towards the bottom-right, and Spiral rotates the
+/// position of each container clockwise around the remaining area
+#[must_use]
+pub(crate) fn binary_split(area: &Rect, len: usize, split_ratio: f32, spiral: bool) -> Vec<Rect> {
+    let mut remaining = *area;
+    let mut layouts = vec![];
+
+    for idx in 0..len {
+        if idx == len - 1 {
+            layouts.push(remaining);
+            break;
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+        let width = (remaining.right as f32 * split_ratio) as i32;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+        let height = (remaining.bottom as f32 * split_ratio) as i32;
+
+        let step = if spiral { idx % 4 } else { idx % 2 };
+        match step {
+            // On the left of the remaining area
+            0 => {
+                layouts.push(Rect {
+                    right: width,
+                    ..remaining
+                });
+                remaining.left += width;
+                remaining.right -= width;
+            }
+            // At the top of the remaining area
+            1 => {
+                layouts.push(Rect {
+                    bottom: height,
+                    ..remaining
+                });
+                remaining.top += height;
+                remaining.bottom -= height;
+            }
+            // On the right of the remaining area
+            2 => {
+                layouts.push(Rect {
+                    left: remaining.left + remaining.right - width,
+                    right: width,
+                    ..remaining
+                });
+                remaining.right -= width;
+            }
+            // At the bottom of the remaining area
+            _ => {
+                layouts.push(Rect {
+                    top: remaining.top + remaining.bottom - height,
+                    bottom: height,
+                    ..remaining
+                });
+                remaining.bottom -= height;
+            }
+        }
+    }
+
+    layouts
+}
+
+/// Resize dimensions are stored relative to the work area and applied in pixels
+fn absolute_resize_dimensions(
+    area: &Rect,
+    resize_dimensions: &[Option<RelativeRect>],
+) -> Vec<Option<Rect>> {
+    resize_dimensions
+        .iter()
+        .map(|resize| resize.map(|resize| resize.to_rect(area)))
+        .collect()
+}
+
 #[must_use]
 fn columns(area: &Rect, len: usize) -> Vec<Rect> {
     #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
@@ -679,6 +1062,44 @@ fn rows_reverse(rows: &mut [Rect]) {
     }
 }
 
+/// Works out the size of the master area of a stack layout along the given dimension
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn master_size(dimension: i32, layout_options: Option<LayoutOptions>) -> i32 {
+    (dimension as f32 * layout_options.unwrap_or_default().master_ratio()) as i32
+}
+
+/// Works out the index of the first column in the viewport of a scrolling layout. The previous
+/// viewport is recovered from the latest layout so that it only scrolls when the focused column
+/// would otherwise be outside of it
+fn scrolling_viewport_start(
+    area: &Rect,
+    len: usize,
+    visible_columns: usize,
+    focused_idx: usize,
+    latest_layout: &[Rect],
+) -> usize {
+    let previous_start = latest_layout
+        .iter()
+        .take(len)
+        .position(|rect| {
+            let centre = rect.left + (rect.right / 2);
+            centre >= area.left && centre < area.left + area.right
+        })
+        .unwrap_or_default();
+
+    let focused_idx = focused_idx.min(len.saturating_sub(1));
+
+    let start = if focused_idx < previous_start {
+        focused_idx
+    } else if focused_idx >= previous_start + visible_columns {
+        focused_idx + 1 - visible_columns
+    } else {
+        previous_start
+    };
+
+    start.min(len.saturating_sub(visible_columns))
+}
+
 fn calculate_resize_adjustments(resize_dimensions: &[Option<Rect>]) -> Vec<Option<Rect>> {
     let mut resize_adjustments = resize_dimensions.to_vec();
 
@@ -922,44 +1343,46 @@ fn calculate_rows_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
     result
 }
 
-fn calculate_vertical_stack_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
+fn calculate_vertical_stack_adjustment(
+    resize_dimensions: &[Option<Rect>],
+    master_count: usize,
+) -> Vec<Rect> {
     let len = resize_dimensions.len();
     let mut result = vec![Rect::default(); len];
     match len {
         // One container can't be resized
         0 | 1 => (),
         _ => {
-            let (master, stack) = result.split_at_mut(1);
-            let primary = &mut master[0];
+            let master_count = master_count.min(len);
+            let (master, stack) = result.split_at_mut(master_count);
+
+            // Handle master column on the left
+            for (i, rect) in resize_dimensions[..master_count].iter().enumerate() {
+                if let Some(rect) = rect {
+                    if !stack.is_empty() {
+                        master.iter_mut().for_each(|vertical_element| {
+                            resize_right(vertical_element, rect.right)
+                        });
+                        stack
+                            .iter_mut()
+                            .for_each(|vertical_element| resize_left(vertical_element, rect.right));
+                    }
 
-            if let Some(resize) = resize_dimensions[0] {
-                resize_right(primary, resize.right);
-                for s in &mut *stack {
-                    resize_left(s, resize.right);
+                    resize_column_neighbours(master, i, rect);
                 }
             }
 
             // Handle stack on the right
-            for (i, rect) in resize_dimensions[1..].iter().enumerate() {
+            for (i, rect) in resize_dimensions[master_count..].iter().enumerate() {
                 if let Some(rect) = rect {
-                    resize_right(primary, rect.left);
+                    master
+                        .iter_mut()
+                        .for_each(|vertical_element| resize_right(vertical_element, rect.left));
                     stack
                         .iter_mut()
                         .for_each(|vertical_element| resize_left(vertical_element, rect.left));
 
-                    // Containers in stack except first can be resized up displacing container
-                    // above them
-                    if i != 0 {
-                        resize_bottom(&mut stack[i - 1], rect.top);
-                        resize_top(&mut stack[i], rect.top);
-                    }
-
-                    // Containers in stack except last can be resized down displacing container
-                    // below them
-                    if i != stack.len() - 1 {
-                        resize_bottom(&mut stack[i], rect.bottom);
-                        resize_top(&mut stack[i + 1], rect.bottom);
-                    }
+                    resize_column_neighbours(stack, i, rect);
                 }
             }
         }
@@ -968,44 +1391,46 @@ fn calculate_vertical_stack_adjustment(resize_dimensions: &[Option<Rect>]) -> Ve
     result
 }
 
-fn calculate_right_vertical_stack_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
+fn calculate_right_vertical_stack_adjustment(
+    resize_dimensions: &[Option<Rect>],
+    master_count: usize,
+) -> Vec<Rect> {
     let len = resize_dimensions.len();
     let mut result = vec![Rect::default(); len];
     match len {
         // One container can't be resized
         0 | 1 => (),
         _ => {
-            let (master, stack) = result.split_at_mut(1);
-            let primary = &mut master[0];
+            let master_count = master_count.min(len);
+            let (master, stack) = result.split_at_mut(master_count);
+
+            // Handle master column on the right
+            for (i, rect) in resize_dimensions[..master_count].iter().enumerate() {
+                if let Some(rect) = rect {
+                    if !stack.is_empty() {
+                        master
+                            .iter_mut()
+                            .for_each(|vertical_element| resize_left(vertical_element, rect.left));
+                        stack
+                            .iter_mut()
+                            .for_each(|vertical_element| resize_right(vertical_element, rect.left));
+                    }
 
-            if let Some(resize) = resize_dimensions[0] {
-                resize_left(primary, resize.left);
-                for s in &mut *stack {
-                    resize_right(s, resize.left);
+                    resize_column_neighbours(master, i, rect);
                 }
             }
 
             // Handle stack on the left
-            for (i, rect) in resize_dimensions[1..].iter().enumerate() {
+            for (i, rect) in resize_dimensions[master_count..].iter().enumerate() {
                 if let Some(rect) = rect {
-                    resize_left(primary, rect.right);
+                    master
+                        .iter_mut()
+                        .for_each(|vertical_element| resize_left(vertical_element, rect.right));
                     stack
                         .iter_mut()
                         .for_each(|vertical_element| resize_right(vertical_element, rect.right));
 
-                    // Containers in stack except first can be resized up displacing container
-                    // above them
-                    if i != 0 {
-                        resize_bottom(&mut stack[i - 1], rect.top);
-                        resize_top(&mut stack[i], rect.top);
-                    }
-
-                    // Containers in stack except last can be resized down displacing container
-                    // below them
-                    if i != stack.len() - 1 {
-                        resize_bottom(&mut stack[i], rect.bottom);
-                        resize_top(&mut stack[i + 1], rect.bottom);
-                    }
+                    resize_column_neighbours(stack, i, rect);
                 }
             }
         }
@@ -1014,37 +1439,45 @@ fn calculate_right_vertical_stack_adjustment(resize_dimensions: &[Option<Rect>])
     result
 }
 
-fn calculate_horizontal_stack_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
+fn calculate_horizontal_stack_adjustment(
+    resize_dimensions: &[Option<Rect>],
+    master_count: usize,
+) -> Vec<Rect> {
     let len = resize_dimensions.len();
     let mut result = vec![Rect::default(); len];
     match len {
         0 | 1 => (),
         _ => {
-            let (primary, rest) = result.split_at_mut(1);
-            let primary = &mut primary[0];
-            if let Some(resize_primary) = resize_dimensions[0] {
-                resize_bottom(primary, resize_primary.bottom);
+            let master_count = master_count.min(len);
+            let (master, stack) = result.split_at_mut(master_count);
+
+            // Handle master row at the top
+            for (i, rect) in resize_dimensions[..master_count].iter().enumerate() {
+                if let Some(rect) = rect {
+                    if !stack.is_empty() {
+                        master.iter_mut().for_each(|horizontal_element| {
+                            resize_bottom(horizontal_element, rect.bottom);
+                        });
+                        stack.iter_mut().for_each(|horizontal_element| {
+                            resize_top(horizontal_element, rect.bottom)
+                        });
+                    }
 
-                for horizontal_element in &mut *rest {
-                    resize_top(horizontal_element, resize_primary.bottom);
+                    resize_row_neighbours(master, i, rect);
                 }
             }
 
-            for (i, rect) in resize_dimensions[1..].iter().enumerate() {
+            // Handle stack at the bottom
+            for (i, rect) in resize_dimensions[master_count..].iter().enumerate() {
                 if let Some(rect) = rect {
-                    resize_bottom(primary, rect.top);
-                    rest.iter_mut()
-                        .for_each(|vertical_element| resize_top(vertical_element, rect.top));
-
-                    if i != 0 {
-                        resize_right(&mut rest[i - 1], rect.left);
-                        resize_left(&mut rest[i], rect.left);
-                    }
+                    master
+                        .iter_mut()
+                        .for_each(|horizontal_element| resize_bottom(horizontal_element, rect.top));
+                    stack
+                        .iter_mut()
+                        .for_each(|horizontal_element| resize_top(horizontal_element, rect.top));
 
-                    if i != rest.len() - 1 {
-                        resize_right(&mut rest[i], rect.right);
-                        resize_left(&mut rest[i + 1], rect.right);
-                    }
+                    resize_row_neighbours(stack, i, rect);
                 }
             }
         }
@@ -1053,6 +1486,34 @@ fn calculate_horizontal_stack_adjustment(resize_dimensions: &[Option<Rect>]) ->
     result
 }
 
+/// Containers in a column except the first can be resized up displacing the container above
+/// them, and containers except the last can be resized down displacing the container below them
+fn resize_column_neighbours(column: &mut [Rect], i: usize, rect: &Rect) {
+    if i != 0 {
+        resize_bottom(&mut column[i - 1], rect.top);
+        resize_top(&mut column[i], rect.top);
+    }
+
+    if i != column.len() - 1 {
+        resize_bottom(&mut column[i], rect.bottom);
+        resize_top(&mut column[i + 1], rect.bottom);
+    }
+}
+
+/// Containers in a row except the first can be resized left displacing the container before
+/// them, and containers except the last can be resized right displacing the container after them
+fn resize_row_neighbours(row: &mut [Rect], i: usize, rect: &Rect) {
+    if i != 0 {
+        resize_right(&mut row[i - 1], rect.left);
+        resize_left(&mut row[i], rect.left);
+    }
+
+    if i != row.len() - 1 {
+        resize_right(&mut row[i], rect.right);
+        resize_left(&mut row[i + 1], rect.right);
+    }
+}
+
 fn calculate_ultrawide_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
     let len = resize_dimensions.len();
     let mut result = vec![Rect::default(); len];
@@ -1126,6 +1587,142 @@ fn calculate_ultrawide_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rec
     result
 }
 
+fn calculate_centered_master_adjustment(
+    resize_dimensions: &[Option<Rect>],
+    left_stack: &[usize],
+    right_stack: &[usize],
+) -> Vec<Rect> {
+    let len = resize_dimensions.len();
+    let mut result = vec![Rect::default(); len];
+    match len {
+        // One container can't be resized
+        0 | 1 => (),
+        _ => {
+            // Container 0 is in the centre
+            if let Some(resize_primary) = resize_dimensions[0] {
+                if !left_stack.is_empty() {
+                    resize_left(&mut result[0], resize_primary.left);
+                    for idx in left_stack {
+                        resize_right(&mut result[*idx], resize_primary.left);
+                    }
+                }
+
+                if !right_stack.is_empty() {
+                    resize_right(&mut result[0], resize_primary.right);
+                    for idx in right_stack {
+                        resize_left(&mut result[*idx], resize_primary.right);
+                    }
+                }
+            }
+
+            // Handle stack on the left
+            for (i, idx) in left_stack.iter().enumerate() {
+                if let Some(rect) = resize_dimensions[*idx] {
+                    resize_left(&mut result[0], rect.right);
+                    for idx in left_stack {
+                        resize_right(&mut result[*idx], rect.right);
+                    }
+
+                    resize_stack_neighbours(&mut result, left_stack, i, &rect);
+                }
+            }
+
+            // Handle stack on the right
+            for (i, idx) in right_stack.iter().enumerate() {
+                if let Some(rect) = resize_dimensions[*idx] {
+                    resize_right(&mut result[0], rect.left);
+                    for idx in right_stack {
+                        resize_left(&mut result[*idx], rect.left);
+                    }
+
+                    resize_stack_neighbours(&mut result, right_stack, i, &rect);
+                }
+            }
+        }
+    };
+
+    result
+}
+
+/// Like `resize_column_neighbours`, for a column made up of the containers at the given indices
+fn resize_stack_neighbours(result: &mut [Rect], stack: &[usize], i: usize, rect: &Rect) {
+    if i != 0 {
+        resize_bottom(&mut result[stack[i - 1]], rect.top);
+        resize_top(&mut result[stack[i]], rect.top);
+    }
+
+    if i != stack.len() - 1 {
+        resize_bottom(&mut result[stack[i]], rect.bottom);
+        resize_top(&mut result[stack[i + 1]], rect.bottom);
+    }
+}
+
+/// Adjustments for layouts made up of columns which each hold a contiguous range of containers.
+/// Containers in a column are stacked on top of each other, unless the column is marked as
+/// vertical in which case they are placed side-by-side, and the boundaries between columns are
+/// shared by every container along that edge
+fn calculate_column_groups_adjustment(
+    resize_dimensions: &[Option<Rect>],
+    columns: &[(Range<usize>, bool)],
+    len: usize,
+) -> Vec<Rect> {
+    let mut result = vec![Rect::default(); len];
+
+    // The containers along the left and right edges of a column
+    let left_edge = |(range, vertical): &(Range<usize>, bool)| {
+        if *vertical {
+            range.start..range.start + 1
+        } else {
+            range.clone()
+        }
+    };
+    let right_edge = |(range, vertical): &(Range<usize>, bool)| {
+        if *vertical {
+            range.end - 1..range.end
+        } else {
+            range.clone()
+        }
+    };
+
+    for (c, column) in columns.iter().enumerate() {
+        let (range, vertical) = column;
+
+        for i in range.clone() {
+            let Some(Some(rect)) = resize_dimensions.get(i) else {
+                continue;
+            };
+
+            let position = i - range.start;
+
+            if *vertical {
+                resize_row_neighbours(&mut result[range.clone()], position, rect);
+            } else {
+                resize_column_neighbours(&mut result[range.clone()], position, rect);
+            }
+
+            if c != 0 && left_edge(column).contains(&i) {
+                result[right_edge(&columns[c - 1])]
+                    .iter_mut()
+                    .for_each(|adjustment| resize_right(adjustment, rect.left));
+                result[left_edge(column)]
+                    .iter_mut()
+                    .for_each(|adjustment| resize_left(adjustment, rect.left));
+            }
+
+            if c != columns.len() - 1 && right_edge(column).contains(&i) {
+                result[right_edge(column)]
+                    .iter_mut()
+                    .for_each(|adjustment| resize_right(adjustment, rect.right));
+                result[left_edge(&columns[c + 1])]
+                    .iter_mut()
+                    .for_each(|adjustment| resize_left(adjustment, rect.right));
+            }
+        }
+    }
+
+    result
+}
+
 fn resize_left(rect: &mut Rect, resize: i32) {
     rect.left += resize / 2;
     rect.right += -resize / 2;
@@ -1143,3 +1740,108 @@ fn resize_top(rect: &mut Rect, resize: i32) {
 fn resize_bottom(rect: &mut Rect, resize: i32) {
     rect.bottom += resize / 2;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Columns of the given width, with the column at `start` aligned to the left of the area
+    fn scrolled_columns(len: usize, width: i32, start: usize) -> Vec<Rect> {
+        (0..len)
+            .map(|i| {
+                #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+                let offset = (i as i32 - start as i32) * width;
+                rect(offset, 0, width, 600)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dwindle_places_the_remaining_area_towards_the_bottom_right() {
+        let area = rect(0, 0, 1000, 800);
+
+        assert_eq!(binary_split(&area, 1, 0.5, false), vec![area]);
+        assert_eq!(
+            binary_split(&area, 4, 0.5, false),
+            vec![
+                rect(0, 0, 500, 800),
+                rect(500, 0, 500, 400),
+                rect(500, 400, 250, 400),
+                rect(750, 400, 250, 400),
+            ]
+        );
+        assert_eq!(
+            binary_split(&area, 2, 0.75, false),
+            vec![rect(0, 0, 750, 800), rect(750, 0, 250, 800)]
+        );
+    }
+
+    #[test]
+    fn spiral_rotates_containers_clockwise_around_the_remaining_area() {
+        assert_eq!(
+            binary_split(&rect(0, 0, 1000, 800), 5, 0.5, true),
+            vec![
+                rect(0, 0, 500, 800),
+                rect(500, 0, 500, 400),
+                rect(750, 400, 250, 400),
+                rect(500, 600, 250, 200),
+                rect(500, 400, 250, 200),
+            ]
+        );
+    }
+
+    #[test]
+    fn master_size_is_clamped_to_the_master_ratio_limits() {
+        let options = |master_ratio: f32| {
+            Option::from(LayoutOptions {
+                master_ratio: Option::from(master_ratio),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(master_size(1000, None), 500);
+        assert_eq!(master_size(1000, options(0.7)), 700);
+        assert_eq!(master_size(1000, options(0.0)), 100);
+        assert_eq!(master_size(1000, options(1.5)), 900);
+    }
+
+    #[test]
+    fn scrolling_viewport_follows_the_focused_column() {
+        let area = rect(0, 0, 900, 600);
+
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 1, &[]), 0);
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 4, &[]), 2);
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 5, &[]), 3);
+    }
+
+    #[test]
+    fn scrolling_viewport_only_scrolls_when_the_focused_column_is_outside_of_it() {
+        let area = rect(0, 0, 900, 600);
+        let latest_layout = scrolled_columns(6, 300, 2);
+
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 3, &latest_layout), 2);
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 4, &latest_layout), 2);
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 1, &latest_layout), 1);
+        assert_eq!(scrolling_viewport_start(&area, 6, 3, 5, &latest_layout), 3);
+    }
+
+    #[test]
+    fn scrolling_viewport_stays_within_the_columns() {
+        let area = rect(0, 0, 900, 600);
+        let latest_layout = scrolled_columns(6, 300, 3);
+
+        // columns were removed since the latest layout
+        assert_eq!(scrolling_viewport_start(&area, 4, 3, 3, &latest_layout), 1);
+        assert_eq!(scrolling_viewport_start(&area, 2, 3, 1, &latest_layout), 0);
+        assert_eq!(scrolling_viewport_start(&area, 4, 3, 10, &[]), 1);
+    }
+}
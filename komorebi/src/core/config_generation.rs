@@ -56,7 +56,46 @@ impl ApplicationOptions {
 #[serde(untagged)]
 pub enum MatchingRule {
     Simple(IdWithIdentifier),
+    /// Matches when every identifier matches
     Composite(Vec<IdWithIdentifier>),
+    /// Matches when every nested rule matches
+    All {
+        all: Vec<MatchingRule>,
+    },
+    /// Matches when at least one nested rule matches
+    Any {
+        any: Vec<MatchingRule>,
+    },
+    /// Matches when the nested rule does not match
+    Not {
+        not: Box<MatchingRule>,
+    },
+}
+
+impl MatchingRule {
+    /// Every identifier in the rule, including those nested inside of other rules
+    pub fn identifiers(&self) -> Vec<&IdWithIdentifier> {
+        match self {
+            Self::Simple(identifier) => vec![identifier],
+            Self::Composite(identifiers) => identifiers.iter().collect(),
+            Self::All { all: rules } | Self::Any { any: rules } => {
+                rules.iter().flat_map(Self::identifiers).collect()
+            }
+            Self::Not { not: rule } => rule.identifiers(),
+        }
+    }
+
+    /// Every identifier in the rule, including those nested inside of other rules
+    pub fn identifiers_mut(&mut self) -> Vec<&mut IdWithIdentifier> {
+        match self {
+            Self::Simple(identifier) => vec![identifier],
+            Self::Composite(identifiers) => identifiers.iter_mut().collect(),
+            Self::All { all: rules } | Self::Any { any: rules } => {
+                rules.iter_mut().flat_map(Self::identifiers_mut).collect()
+            }
+            Self::Not { not: rule } => rule.identifiers_mut(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -87,6 +126,10 @@ pub enum MatchingStrategy {
     DoesNotStartWith,
     DoesNotEqual,
     DoesNotContain,
+    EqualsIgnoreCase,
+    ContainsIgnoreCase,
+    Glob,
+    WordBoundary,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
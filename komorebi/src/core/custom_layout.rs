@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::BufReader;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::ops::Range;
 use std::path::Path;
 
 use color_eyre::eyre::anyhow;
@@ -96,15 +97,6 @@ impl CustomLayout {
             return false;
         };
 
-        // Vertical column splits aren't supported at the moment
-        for column in self.iter() {
-            match column {
-                Column::Tertiary(ColumnSplit::Vertical)
-                | Column::Secondary(Some(ColumnSplitWithCapacity::Vertical(_))) => return false,
-                _ => {}
-            }
-        }
-
         // The final column must not have a fixed capacity
         match self.last() {
             Some(Column::Tertiary(_)) => {}
@@ -122,10 +114,62 @@ impl CustomLayout {
             }
         }
 
+        // Columns with a fixed capacity must be able to hold at least one container
+        for column in self.iter() {
+            if let Column::Secondary(Some(
+                ColumnSplitWithCapacity::Horizontal(0) | ColumnSplitWithCapacity::Vertical(0),
+            )) = column
+            {
+                return false;
+            }
+        }
+
         // There must only be one primary and one tertiary column
         matches!(primaries, 1) && matches!(tertiaries, 1)
     }
 
+    /// Whether the containers in the column at the given index are split into side-by-side
+    /// sub-columns
+    #[must_use]
+    pub fn is_vertically_split(&self, col_idx: usize) -> bool {
+        matches!(
+            self.get(col_idx),
+            Some(
+                Column::Secondary(Some(ColumnSplitWithCapacity::Vertical(_)))
+                    | Column::Tertiary(ColumnSplit::Vertical)
+            )
+        )
+    }
+
+    /// The range of container indices in each column for the given number of containers, and
+    /// whether the containers in that column are placed side-by-side. When there are fewer
+    /// containers than columns, every container gets a column of its own
+    #[must_use]
+    pub fn column_ranges(&self, len: usize) -> Vec<(Range<usize>, bool)> {
+        if len < self.len() {
+            return (0..len).map(|idx| (idx..idx + 1, false)).collect();
+        }
+
+        let count_map = self.column_container_counts();
+        let mut ranges = vec![];
+        let mut start = 0;
+
+        for idx in 0..self.len() {
+            if start >= len {
+                break;
+            }
+
+            // The tertiary column is not in the count map and takes whatever is left
+            let count = count_map.get(&idx).copied().unwrap_or_else(|| len - start);
+
+            let end = std::cmp::min(start + count, len);
+            ranges.push((start..end, self.is_vertically_split(idx)));
+            start = end;
+        }
+
+        ranges
+    }
+
     pub(crate) fn column_container_counts(&self) -> HashMap<usize, usize> {
         let mut count_map = HashMap::new();
 
@@ -274,3 +318,89 @@ pub enum ColumnSplitWithCapacity {
     Horizontal(usize),
     Vertical(usize),
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::core::Arrangement;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    #[test]
+    fn columns_with_zero_capacity_are_rejected() {
+        let layouts = [
+            vec![
+                Column::Secondary(Some(ColumnSplitWithCapacity::Horizontal(0))),
+                Column::Primary(None),
+                Column::Tertiary(ColumnSplit::Horizontal),
+            ],
+            vec![
+                Column::Primary(None),
+                Column::Secondary(Some(ColumnSplitWithCapacity::Vertical(0))),
+                Column::Tertiary(ColumnSplit::Horizontal),
+            ],
+            vec![
+                Column::Primary(None),
+                Column::Secondary(Some(ColumnSplitWithCapacity::Horizontal(2))),
+                Column::Secondary(Some(ColumnSplitWithCapacity::Horizontal(0))),
+                Column::Tertiary(ColumnSplit::Vertical),
+            ],
+        ];
+
+        for columns in layouts {
+            assert!(!CustomLayout(columns).is_valid());
+        }
+
+        assert!(CustomLayout(vec![
+            Column::Primary(None),
+            Column::Secondary(Some(ColumnSplitWithCapacity::Vertical(2))),
+            Column::Tertiary(ColumnSplit::Horizontal),
+        ])
+        .is_valid());
+    }
+
+    #[test]
+    fn vertically_split_columns_place_containers_side_by_side() {
+        let layout = CustomLayout(vec![
+            Column::Primary(None),
+            Column::Secondary(Some(ColumnSplitWithCapacity::Vertical(2))),
+            Column::Tertiary(ColumnSplit::Horizontal),
+        ]);
+
+        assert_eq!(
+            layout.column_ranges(5),
+            vec![(0..1, false), (1..3, true), (3..5, false)]
+        );
+
+        let dimensions = layout.calculate(
+            &rect(0, 0, 1200, 600),
+            NonZeroUsize::new(5).unwrap(),
+            None,
+            None,
+            &[],
+            0,
+            None,
+            &[],
+        );
+
+        assert_eq!(
+            dimensions,
+            vec![
+                rect(0, 0, 400, 600),
+                rect(400, 0, 200, 600),
+                rect(600, 0, 200, 600),
+                rect(800, 0, 400, 300),
+                rect(800, 300, 400, 300),
+            ]
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::custom_layout::ColumnSplit;
+use super::OperationDirection;
+use super::Rect;
+
+/// A custom layout described as a recursive tree of splits, where every leaf holds a fixed number
+/// of containers, except for the overflow leaf which takes any containers that don't fit elsewhere
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct CustomLayoutTree {
+    /// The node which covers the whole work area
+    pub root: LayoutNode,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(tag = "node")]
+pub enum LayoutNode {
+    /// An area which is divided between child nodes
+    Split {
+        /// How the area is divided; Horizontal stacks the children in rows and Vertical places
+        /// them side-by-side in columns
+        split: ColumnSplit,
+        /// The relative size of each child (default: equal sizes)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ratios: Option<Vec<f32>>,
+        /// The child nodes, from top to bottom or from left to right
+        children: Vec<LayoutNode>,
+    },
+    /// An area which holds containers
+    Leaf {
+        /// The maximum number of containers in this leaf (default: 1)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        capacity: Option<usize>,
+        /// How the containers in this leaf are arranged (default: Horizontal)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        split: Option<ColumnSplit>,
+        /// Whether containers beyond the capacity of every leaf should be placed in this leaf
+        #[serde(default)]
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        overflow: bool,
+    },
+}
+
+impl CustomLayoutTree {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let layout: Self = match path.extension() {
+            Some(extension) if extension == "yaml" || extension == "yml" => {
+                serde_yaml::from_reader(BufReader::new(File::open(path)?))?
+            }
+            Some(extension) if extension == "json" => {
+                serde_json::from_reader(BufReader::new(File::open(path)?))?
+            }
+            _ => return Err(anyhow!("custom layouts must be json or yaml files")),
+        };
+
+        if !layout.is_valid() {
+            bail!("the layout file provided was invalid");
+        }
+
+        Ok(layout)
+    }
+
+    /// Tree layouts are stored as a single object, whereas column layouts are stored as a list
+    pub fn is_tree_layout_file<P: AsRef<Path>>(path: P) -> Result<bool> {
+        let path = path.as_ref();
+        let reader = BufReader::new(File::open(path)?);
+
+        Ok(match path.extension() {
+            Some(extension) if extension == "yaml" || extension == "yml" => {
+                serde_yaml::from_reader::<_, serde_yaml::Value>(reader)?.is_mapping()
+            }
+            _ => serde_json::from_reader::<_, serde_json::Value>(reader)?.is_object(),
+        })
+    }
+
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        // There must be exactly one overflow leaf
+        let overflow_leaves = self
+            .leaves()
+            .iter()
+            .filter(|(_, _, overflow)| *overflow)
+            .count();
+
+        overflow_leaves == 1 && self.root.is_valid()
+    }
+
+    /// Every leaf in depth-first order, as (capacity, split, overflow)
+    #[must_use]
+    pub fn leaves(&self) -> Vec<(usize, ColumnSplit, bool)> {
+        let mut leaves = vec![];
+        self.root.collect_leaves(&mut leaves);
+        leaves
+    }
+
+    /// The number of containers placed in each leaf, in depth-first order. Leaves are filled up
+    /// to their capacity in order, and whatever remains is placed in the overflow leaf
+    #[must_use]
+    pub fn leaf_container_counts(&self, len: usize) -> Vec<usize> {
+        let leaves = self.leaves();
+        let mut remaining = len;
+
+        let mut counts: Vec<usize> = leaves
+            .iter()
+            .map(|(capacity, _, _)| {
+                let count = (*capacity).min(remaining);
+                remaining -= count;
+                count
+            })
+            .collect();
+
+        if let Some(overflow) = leaves.iter().position(|(_, _, overflow)| *overflow) {
+            counts[overflow] += remaining;
+        }
+
+        counts
+    }
+
+    /// The areas of every container for the given number of containers, before padding and
+    /// layout flips are applied
+    #[must_use]
+    pub fn container_areas(&self, area: &Rect, len: usize) -> Vec<Rect> {
+        let counts = self.leaf_container_counts(len);
+        let mut leaf_idx = 0;
+        let mut areas = vec![];
+
+        self.root.arrange(area, &counts, &mut leaf_idx, &mut areas);
+
+        areas
+    }
+
+    /// The closest container in the given direction, based on the positions of the containers in
+    /// a reference area
+    #[must_use]
+    pub fn neighbour(
+        &self,
+        op_direction: OperationDirection,
+        idx: usize,
+        count: usize,
+    ) -> Option<usize> {
+        let reference = Rect {
+            left: 0,
+            top: 0,
+            right: 10_000,
+            bottom: 10_000,
+        };
+
+        op_direction.closest_rect(idx, &self.container_areas(&reference, count))
+    }
+}
+
+impl LayoutNode {
+    fn is_valid(&self) -> bool {
+        match self {
+            Self::Split {
+                ratios, children, ..
+            } => {
+                if children.is_empty() {
+                    return false;
+                }
+
+                if let Some(ratios) = ratios {
+                    if ratios.len() != children.len()
+                        || ratios
+                            .iter()
+                            .any(|ratio| !ratio.is_finite() || *ratio <= 0.0)
+                    {
+                        return false;
+                    }
+                }
+
+                children.iter().all(Self::is_valid)
+            }
+            // A leaf must be able to hold at least one container
+            Self::Leaf { capacity, .. } => !matches!(capacity, Some(0)),
+        }
+    }
+
+    fn collect_leaves(&self, leaves: &mut Vec<(usize, ColumnSplit, bool)>) {
+        match self {
+            Self::Split { children, .. } => {
+                for child in children {
+                    child.collect_leaves(leaves);
+                }
+            }
+            Self::Leaf {
+                capacity,
+                split,
+                overflow,
+            } => leaves.push((
+                capacity.unwrap_or(1),
+                split.unwrap_or(ColumnSplit::Horizontal),
+                *overflow,
+            )),
+        }
+    }
+
+    fn leaf_count(&self) -> usize {
+        match self {
+            Self::Split { children, .. } => children.iter().map(Self::leaf_count).sum(),
+            Self::Leaf { .. } => 1,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn arrange(&self, area: &Rect, counts: &[usize], leaf_idx: &mut usize, areas: &mut Vec<Rect>) {
+        match self {
+            Self::Split {
+                split,
+                ratios,
+                children,
+            } => {
+                // Children without any containers are collapsed so that the remaining children
+                // can use their space
+                let mut offset = *leaf_idx;
+                let active: Vec<(usize, f32)> = children
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, child)| {
+                        let leaf_count = child.leaf_count();
+                        let containers: usize = counts[offset..offset + leaf_count].iter().sum();
+                        offset += leaf_count;
+
+                        let ratio = ratios.as_ref().map_or(1.0, |ratios| ratios[i]);
+                        (containers > 0).then_some((i, ratio))
+                    })
+                    .collect();
+
+                let total_ratio: f32 = active.iter().map(|(_, ratio)| ratio).sum();
+                let total_size = match split {
+                    ColumnSplit::Horizontal => area.bottom,
+                    ColumnSplit::Vertical => area.right,
+                };
+
+                let mut position = 0;
+                let mut active = active.iter().peekable();
+
+                for (i, child) in children.iter().enumerate() {
+                    let Some((_, ratio)) = active.next_if(|(active_idx, _)| *active_idx == i)
+                    else {
+                        // Skip over the leaves of collapsed children
+                        *leaf_idx += child.leaf_count();
+                        continue;
+                    };
+
+                    // The last child takes whatever is left to avoid gaps from rounding
+                    let size = if active.peek().is_none() {
+                        total_size - position
+                    } else {
+                        (total_size as f32 * (ratio / total_ratio)) as i32
+                    };
+
+                    let child_area = match split {
+                        ColumnSplit::Horizontal => Rect {
+                            left: area.left,
+                            top: area.top + position,
+                            right: area.right,
+                            bottom: size,
+                        },
+                        ColumnSplit::Vertical => Rect {
+                            left: area.left + position,
+                            top: area.top,
+                            right: size,
+                            bottom: area.bottom,
+                        },
+                    };
+
+                    child.arrange(&child_area, counts, leaf_idx, areas);
+                    position += size;
+                }
+            }
+            Self::Leaf { split, .. } => {
+                let count = counts[*leaf_idx];
+                *leaf_idx += 1;
+
+                if count == 0 {
+                    return;
+                }
+
+                #[allow(clippy::cast_possible_wrap)]
+                let count = count as i32;
+
+                for i in 0..count {
+                    areas.push(match split.unwrap_or(ColumnSplit::Horizontal) {
+                        ColumnSplit::Horizontal => {
+                            let bottom = area.bottom / count;
+                            Rect {
+                                left: area.left,
+                                top: area.top + bottom * i,
+                                right: area.right,
+                                bottom: if i == count - 1 {
+                                    area.bottom - bottom * i
+                                } else {
+                                    bottom
+                                },
+                            }
+                        }
+                        ColumnSplit::Vertical => {
+                            let right = area.right / count;
+                            Rect {
+                                left: area.left + right * i,
+                                top: area.top,
+                                right: if i == count - 1 {
+                                    area.right - right * i
+                                } else {
+                                    right
+                                },
+                                bottom: area.bottom,
+                            }
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    const fn leaf(capacity: Option<usize>, overflow: bool) -> LayoutNode {
+        LayoutNode::Leaf {
+            capacity,
+            split: None,
+            overflow,
+        }
+    }
+
+    /// A two-thirds overflow column on the left, and two single container rows on the right
+    fn layout() -> CustomLayoutTree {
+        CustomLayoutTree {
+            root: LayoutNode::Split {
+                split: ColumnSplit::Vertical,
+                ratios: Option::from(vec![2.0, 1.0]),
+                children: vec![
+                    leaf(Option::from(2), true),
+                    LayoutNode::Split {
+                        split: ColumnSplit::Horizontal,
+                        ratios: None,
+                        children: vec![leaf(None, false), leaf(None, false)],
+                    },
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn ratios_must_be_finite_and_positive() {
+        assert!(layout().is_valid());
+
+        for ratio in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let mut layout = layout();
+            if let LayoutNode::Split { ratios, .. } = &mut layout.root {
+                *ratios = Option::from(vec![ratio, 1.0]);
+            }
+
+            assert!(!layout.is_valid(), "{ratio} should be rejected");
+        }
+    }
+
+    #[test]
+    fn leaves_are_filled_in_order_before_the_overflow_leaf() {
+        let layout = layout();
+
+        assert_eq!(layout.leaf_container_counts(0), vec![0, 0, 0]);
+        assert_eq!(layout.leaf_container_counts(1), vec![1, 0, 0]);
+        assert_eq!(layout.leaf_container_counts(3), vec![2, 1, 0]);
+        assert_eq!(layout.leaf_container_counts(6), vec![4, 1, 1]);
+    }
+
+    #[test]
+    fn children_without_containers_are_collapsed() {
+        let layout = layout();
+        let area = rect(0, 0, 1000, 301);
+
+        assert_eq!(layout.container_areas(&area, 1), vec![area]);
+
+        // the bottom row on the right is empty, so the top row takes its space
+        assert_eq!(
+            layout.container_areas(&area, 3),
+            vec![
+                rect(0, 0, 666, 150),
+                rect(0, 150, 666, 151),
+                rect(666, 0, 334, 301),
+            ]
+        );
+    }
+
+    #[test]
+    fn remainders_go_to_the_last_container() {
+        let layout = layout();
+
+        assert_eq!(
+            layout.container_areas(&rect(0, 0, 1000, 301), 6),
+            vec![
+                rect(0, 0, 666, 75),
+                rect(0, 75, 666, 75),
+                rect(0, 150, 666, 75),
+                rect(0, 225, 666, 76),
+                rect(666, 0, 334, 150),
+                rect(666, 150, 334, 151),
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbours_follow_the_arranged_containers() {
+        let layout = layout();
+
+        assert_eq!(layout.neighbour(OperationDirection::Right, 0, 4), Some(2));
+        assert_eq!(layout.neighbour(OperationDirection::Right, 1, 4), Some(3));
+        assert_eq!(layout.neighbour(OperationDirection::Down, 2, 4), Some(3));
+        assert_eq!(layout.neighbour(OperationDirection::Left, 3, 4), Some(1));
+        assert_eq!(layout.neighbour(OperationDirection::Up, 0, 4), None);
+
+        // with the bottom row on the right collapsed, both containers on the left share it
+        assert_eq!(layout.neighbour(OperationDirection::Left, 2, 3), Some(0));
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use clap::ValueEnum;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -9,6 +11,111 @@ use super::OperationDirection;
 use super::Rect;
 use super::Sizing;
 
+pub const DEFAULT_SCROLLING_COLUMNS: usize = 3;
+pub const DEFAULT_CENTRE_WIDTH_PERCENTAGE: usize = 50;
+pub const DEFAULT_MASTER_COUNT: usize = 1;
+pub const DEFAULT_MASTER_RATIO: f32 = 0.5;
+pub const MASTER_RATIO_STEP: f32 = 0.05;
+const MINIMUM_MASTER_RATIO: f32 = 0.1;
+const MAXIMUM_MASTER_RATIO: f32 = 0.9;
+pub const DEFAULT_SPLIT_RATIO: f32 = 0.5;
+const MINIMUM_SPLIT_RATIO: f32 = 0.1;
+const MAXIMUM_SPLIT_RATIO: f32 = 0.9;
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct LayoutOptions {
+    /// Options related to the Scrolling layout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scrolling: Option<ScrollingLayoutOptions>,
+    /// Options related to the CenteredMaster layout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub centered_master: Option<CenteredMasterLayoutOptions>,
+    /// Number of containers in the master area of the VerticalStack, RightMainVerticalStack and HorizontalStack layouts (default: 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_count: Option<usize>,
+    /// Proportion of the work area given to the master area of the VerticalStack, RightMainVerticalStack and HorizontalStack layouts (default: 0.5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_ratio: Option<f32>,
+    /// Proportion of the remaining area given to each container in the Dwindle and Spiral layouts (default: 0.5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_ratio: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ScrollingLayoutOptions {
+    /// Desired number of visible columns (default: 3)
+    pub columns: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct CenteredMasterLayoutOptions {
+    /// Width of the centre column as a percentage of the work area (default: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub centre_width_percentage: Option<usize>,
+    /// How containers are distributed between the left and right stacks (default: Alternate)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<CenteredMasterFill>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum CenteredMasterFill {
+    /// New containers alternate between the left and the right stacks
+    #[default]
+    Alternate,
+    /// The left stack is filled with the first half of the containers and the right stack with the rest
+    Balanced,
+}
+
+impl CenteredMasterFill {
+    /// Returns the indices of the containers in the left and the right stacks, from top to bottom
+    #[must_use]
+    pub fn stacks(self, len: usize) -> (Vec<usize>, Vec<usize>) {
+        let stack_len = len.saturating_sub(1);
+
+        (1..len).partition(|idx| match self {
+            Self::Alternate => (idx - 1) % 2 == 0,
+            Self::Balanced => idx - 1 < stack_len.div_ceil(2),
+        })
+    }
+}
+
+impl LayoutOptions {
+    pub fn scrolling_columns(&self) -> usize {
+        self.scrolling
+            .map_or(DEFAULT_SCROLLING_COLUMNS, |scrolling| scrolling.columns)
+            .max(1)
+    }
+
+    pub fn centre_width_percentage(&self) -> usize {
+        self.centered_master
+            .and_then(|centered_master| centered_master.centre_width_percentage)
+            .unwrap_or(DEFAULT_CENTRE_WIDTH_PERCENTAGE)
+            .clamp(10, 90)
+    }
+
+    pub fn centered_master_fill(&self) -> CenteredMasterFill {
+        self.centered_master
+            .and_then(|centered_master| centered_master.fill)
+            .unwrap_or_default()
+    }
+
+    pub fn master_count(&self) -> usize {
+        self.master_count.unwrap_or(DEFAULT_MASTER_COUNT).max(1)
+    }
+
+    pub fn master_ratio(&self) -> f32 {
+        self.master_ratio
+            .unwrap_or(DEFAULT_MASTER_RATIO)
+            .clamp(MINIMUM_MASTER_RATIO, MAXIMUM_MASTER_RATIO)
+    }
+
+    pub fn split_ratio(&self) -> f32 {
+        self.split_ratio
+            .unwrap_or(DEFAULT_SPLIT_RATIO)
+            .clamp(MINIMUM_SPLIT_RATIO, MAXIMUM_SPLIT_RATIO)
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -31,38 +138,99 @@ pub enum DefaultLayout {
     UltrawideVerticalStack,
     Grid,
     RightMainVerticalStack,
+    Scrolling,
+    CenteredMaster,
+    Dwindle,
+    Spiral,
     // NOTE: If any new layout is added, please make sure to register the same in `DefaultLayout::cycle`
 }
 
 impl DefaultLayout {
-    pub fn leftmost_index(&self, len: usize) -> usize {
+    /// The range of container indices in each column of the Grid layout; columns are filled from
+    /// left to right, with later columns taking any extra rows
+    #[must_use]
+    pub fn grid_columns(len: usize) -> Vec<Range<usize>> {
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let num_cols = (len as f32).sqrt().ceil() as usize;
+        let mut columns = vec![];
+        let mut start = 0;
+
+        for col in 0..num_cols {
+            let remaining_windows = len - start;
+            let remaining_columns = num_cols - col;
+            let num_rows_in_this_col = remaining_windows / remaining_columns;
+
+            columns.push(start..start + num_rows_in_this_col);
+            start += num_rows_in_this_col;
+        }
+
+        columns
+    }
+
+    pub fn leftmost_index(&self, len: usize, layout_options: Option<LayoutOptions>) -> usize {
         match self {
-            Self::UltrawideVerticalStack | Self::RightMainVerticalStack => match len {
+            Self::UltrawideVerticalStack => match len {
                 n if n > 1 => 1,
                 _ => 0,
             },
+            Self::RightMainVerticalStack => {
+                let master_count = layout_options.unwrap_or_default().master_count();
+                match len {
+                    n if n > master_count => master_count,
+                    _ => 0,
+                }
+            }
+            Self::CenteredMaster => {
+                let (left, _) = layout_options
+                    .unwrap_or_default()
+                    .centered_master_fill()
+                    .stacks(len);
+
+                left.first().copied().unwrap_or_default()
+            }
             DefaultLayout::BSP
             | DefaultLayout::Columns
             | DefaultLayout::Rows
             | DefaultLayout::VerticalStack
             | DefaultLayout::HorizontalStack
-            | DefaultLayout::Grid => 0,
+            | DefaultLayout::Grid
+            | DefaultLayout::Scrolling
+            | DefaultLayout::Dwindle
+            | DefaultLayout::Spiral => 0,
         }
     }
 
-    pub fn rightmost_index(&self, len: usize) -> usize {
+    pub fn rightmost_index(&self, len: usize, layout_options: Option<LayoutOptions>) -> usize {
         match self {
             DefaultLayout::BSP
             | DefaultLayout::Columns
             | DefaultLayout::Rows
             | DefaultLayout::VerticalStack
             | DefaultLayout::HorizontalStack
-            | DefaultLayout::Grid => len.saturating_sub(1),
+            | DefaultLayout::Grid
+            | DefaultLayout::Scrolling => len.saturating_sub(1),
             DefaultLayout::UltrawideVerticalStack => match len {
                 2 => 0,
                 _ => len.saturating_sub(1),
             },
             DefaultLayout::RightMainVerticalStack => 0,
+            // The second container is always at the top of the right edge
+            DefaultLayout::Dwindle | DefaultLayout::Spiral => match len {
+                n if n > 1 => 1,
+                _ => 0,
+            },
+            DefaultLayout::CenteredMaster => {
+                let (_, right) = layout_options
+                    .unwrap_or_default()
+                    .centered_master_fill()
+                    .stacks(len);
+
+                right.first().copied().unwrap_or_default()
+            }
         }
     }
 
@@ -85,97 +253,13 @@ impl DefaultLayout {
                 | Self::RightMainVerticalStack
                 | Self::HorizontalStack
                 | Self::UltrawideVerticalStack
+                | Self::CenteredMaster
+                | Self::Grid
         ) {
             return None;
         };
 
-        let max_divisor = 1.005;
-        let mut r = resize.unwrap_or_default();
-
-        let resize_delta = delta;
-
-        match edge {
-            OperationDirection::Left => match sizing {
-                Sizing::Increase => {
-                    // Some final checks to make sure the user can't infinitely resize to
-                    // the point of pushing other windows out of bounds
-
-                    // Note: These checks cannot take into account the changes made to the
-                    // edges of adjacent windows at operation time, so it is still possible
-                    // to push windows out of bounds by maxing out an Increase Left on a
-                    // Window with index 1, and then maxing out a Decrease Right on a Window
-                    // with index 0. I don't think it's worth trying to defensively program
-                    // against this; if people end up in this situation they are better off
-                    // just hitting the retile command
-                    let diff = ((r.left + -resize_delta) as f32).abs();
-                    let max = unaltered.right as f32 / max_divisor;
-                    if diff < max {
-                        r.left += -resize_delta;
-                    }
-                }
-                Sizing::Decrease => {
-                    let diff = ((r.left - -resize_delta) as f32).abs();
-                    let max = unaltered.right as f32 / max_divisor;
-                    if diff < max {
-                        r.left -= -resize_delta;
-                    }
-                }
-            },
-            OperationDirection::Up => match sizing {
-                Sizing::Increase => {
-                    let diff = ((r.top + resize_delta) as f32).abs();
-                    let max = unaltered.bottom as f32 / max_divisor;
-                    if diff < max {
-                        r.top += -resize_delta;
-                    }
-                }
-                Sizing::Decrease => {
-                    let diff = ((r.top - resize_delta) as f32).abs();
-                    let max = unaltered.bottom as f32 / max_divisor;
-                    if diff < max {
-                        r.top -= -resize_delta;
-                    }
-                }
-            },
-            OperationDirection::Right => match sizing {
-                Sizing::Increase => {
-                    let diff = ((r.right + resize_delta) as f32).abs();
-                    let max = unaltered.right as f32 / max_divisor;
-                    if diff < max {
-                        r.right += resize_delta;
-                    }
-                }
-                Sizing::Decrease => {
-                    let diff = ((r.right - resize_delta) as f32).abs();
-                    let max = unaltered.right as f32 / max_divisor;
-                    if diff < max {
-                        r.right -= resize_delta;
-                    }
-                }
-            },
-            OperationDirection::Down => match sizing {
-                Sizing::Increase => {
-                    let diff = ((r.bottom + resize_delta) as f32).abs();
-                    let max = unaltered.bottom as f32 / max_divisor;
-                    if diff < max {
-                        r.bottom += resize_delta;
-                    }
-                }
-                Sizing::Decrease => {
-                    let diff = ((r.bottom - resize_delta) as f32).abs();
-                    let max = unaltered.bottom as f32 / max_divisor;
-                    if diff < max {
-                        r.bottom -= resize_delta;
-                    }
-                }
-            },
-        };
-
-        if r.eq(&Rect::default()) {
-            None
-        } else {
-            Option::from(r)
-        }
+        resize_edge(unaltered, resize, edge, sizing, delta)
     }
 
     #[must_use]
@@ -188,13 +272,21 @@ impl DefaultLayout {
             Self::HorizontalStack => Self::UltrawideVerticalStack,
             Self::UltrawideVerticalStack => Self::Grid,
             Self::Grid => Self::RightMainVerticalStack,
-            Self::RightMainVerticalStack => Self::BSP,
+            Self::RightMainVerticalStack => Self::Scrolling,
+            Self::Scrolling => Self::CenteredMaster,
+            Self::CenteredMaster => Self::Dwindle,
+            Self::Dwindle => Self::Spiral,
+            Self::Spiral => Self::BSP,
         }
     }
 
     #[must_use]
     pub const fn cycle_previous(self) -> Self {
         match self {
+            Self::Spiral => Self::Dwindle,
+            Self::Dwindle => Self::CenteredMaster,
+            Self::CenteredMaster => Self::Scrolling,
+            Self::Scrolling => Self::RightMainVerticalStack,
             Self::RightMainVerticalStack => Self::Grid,
             Self::Grid => Self::UltrawideVerticalStack,
             Self::UltrawideVerticalStack => Self::HorizontalStack,
@@ -202,7 +294,139 @@ impl DefaultLayout {
             Self::VerticalStack => Self::Rows,
             Self::Rows => Self::Columns,
             Self::Columns => Self::BSP,
-            Self::BSP => Self::RightMainVerticalStack,
+            Self::BSP => Self::Spiral,
+        }
+    }
+}
+
+/// Apply a resize of the given edge to the existing resize adjustments of a container, returning
+/// `None` if there is no longer any adjustment
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn resize_edge(
+    unaltered: &Rect,
+    resize: &Option<Rect>,
+    edge: OperationDirection,
+    sizing: Sizing,
+    delta: i32,
+) -> Option<Rect> {
+    let max_divisor = 1.005;
+    let mut r = resize.unwrap_or_default();
+
+    let resize_delta = delta;
+
+    match edge {
+        OperationDirection::Left => match sizing {
+            Sizing::Increase => {
+                // Some final checks to make sure the user can't infinitely resize to
+                // the point of pushing other windows out of bounds
+
+                // Note: These checks cannot take into account the changes made to the
+                // edges of adjacent windows at operation time, so it is still possible
+                // to push windows out of bounds by maxing out an Increase Left on a
+                // Window with index 1, and then maxing out a Decrease Right on a Window
+                // with index 0. I don't think it's worth trying to defensively program
+                // against this; if people end up in this situation they are better off
+                // just hitting the retile command
+                let diff = ((r.left + -resize_delta) as f32).abs();
+                let max = unaltered.right as f32 / max_divisor;
+                if diff < max {
+                    r.left += -resize_delta;
+                }
+            }
+            Sizing::Decrease => {
+                let diff = ((r.left - -resize_delta) as f32).abs();
+                let max = unaltered.right as f32 / max_divisor;
+                if diff < max {
+                    r.left -= -resize_delta;
+                }
+            }
+        },
+        OperationDirection::Up => match sizing {
+            Sizing::Increase => {
+                let diff = ((r.top + resize_delta) as f32).abs();
+                let max = unaltered.bottom as f32 / max_divisor;
+                if diff < max {
+                    r.top += -resize_delta;
+                }
+            }
+            Sizing::Decrease => {
+                let diff = ((r.top - resize_delta) as f32).abs();
+                let max = unaltered.bottom as f32 / max_divisor;
+                if diff < max {
+                    r.top -= -resize_delta;
+                }
+            }
+        },
+        OperationDirection::Right => match sizing {
+            Sizing::Increase => {
+                let diff = ((r.right + resize_delta) as f32).abs();
+                let max = unaltered.right as f32 / max_divisor;
+                if diff < max {
+                    r.right += resize_delta;
+                }
+            }
+            Sizing::Decrease => {
+                let diff = ((r.right - resize_delta) as f32).abs();
+                let max = unaltered.right as f32 / max_divisor;
+                if diff < max {
+                    r.right -= resize_delta;
+                }
+            }
+        },
+        OperationDirection::Down => match sizing {
+            Sizing::Increase => {
+                let diff = ((r.bottom + resize_delta) as f32).abs();
+                let max = unaltered.bottom as f32 / max_divisor;
+                if diff < max {
+                    r.bottom += resize_delta;
+                }
+            }
+            Sizing::Decrease => {
+                let diff = ((r.bottom - resize_delta) as f32).abs();
+                let max = unaltered.bottom as f32 / max_divisor;
+                if diff < max {
+                    r.bottom -= resize_delta;
+                }
+            }
+        },
+    };
+
+    if r.eq(&Rect::default()) {
+        None
+    } else {
+        Option::from(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alternate_fill_switches_stacks_for_every_container() {
+        let (left, right) = CenteredMasterFill::Alternate.stacks(6);
+        assert_eq!(left, vec![1, 3, 5]);
+        assert_eq!(right, vec![2, 4]);
+    }
+
+    #[test]
+    fn balanced_fill_puts_the_extra_container_on_the_left() {
+        let (left, right) = CenteredMasterFill::Balanced.stacks(6);
+        assert_eq!(left, vec![1, 2, 3]);
+        assert_eq!(right, vec![4, 5]);
+
+        let (left, right) = CenteredMasterFill::Balanced.stacks(5);
+        assert_eq!(left, vec![1, 2]);
+        assert_eq!(right, vec![3, 4]);
+    }
+
+    #[test]
+    fn only_the_master_container_leaves_both_stacks_empty() {
+        for fill in [CenteredMasterFill::Alternate, CenteredMasterFill::Balanced] {
+            assert_eq!(fill.stacks(0), (vec![], vec![]));
+            assert_eq!(fill.stacks(1), (vec![], vec![]));
+            assert_eq!(fill.stacks(2), (vec![1], vec![]));
         }
     }
 }
@@ -1,9 +1,13 @@
+use super::arrangement::binary_split;
 use super::custom_layout::Column;
 use super::custom_layout::ColumnSplit;
 use super::custom_layout::ColumnSplitWithCapacity;
 use super::custom_layout::CustomLayout;
+use super::CustomLayoutTree;
 use super::DefaultLayout;
+use super::LayoutOptions;
 use super::OperationDirection;
+use super::Rect;
 
 pub trait Direction {
     fn index_in_direction(
@@ -11,6 +15,7 @@ pub trait Direction {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize>;
 
     fn is_valid_direction(
@@ -18,30 +23,35 @@ pub trait Direction {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> bool;
     fn up_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
     fn down_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
     fn left_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
     fn right_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
 }
 
@@ -51,32 +61,53 @@ impl Direction for DefaultLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize> {
         match op_direction {
             OperationDirection::Left => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.left_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.left_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Right => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.right_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.right_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Up => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.up_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.up_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Down => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.down_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.down_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
@@ -89,50 +120,87 @@ impl Direction for DefaultLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> bool {
         if count < 2 {
             return false;
         }
 
+        // The master area of the stack layouts is a column (or a row for HorizontalStack) of
+        // containers, followed by the stack
+        let masters = master_count(layout_options, count);
+
         match op_direction {
             OperationDirection::Up => match self {
                 Self::BSP => idx != 0 && idx != 1,
-                Self::Columns => false,
-                Self::Rows | Self::HorizontalStack => idx != 0,
-                Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != 1,
+                Self::Columns | Self::Scrolling => false,
+                Self::Rows => idx != 0,
+                Self::HorizontalStack => idx >= masters,
+                Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != masters,
                 Self::UltrawideVerticalStack => idx > 2,
+                Self::CenteredMaster => {
+                    centered_master_neighbour(op_direction, idx, count, layout_options).is_some()
+                }
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Dwindle | Self::Spiral => {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                        .is_some()
+                }
             },
             OperationDirection::Down => match self {
                 Self::BSP => idx != count - 1 && idx % 2 != 0,
-                Self::Columns => false,
+                Self::Columns | Self::Scrolling => false,
                 Self::Rows => idx != count - 1,
-                Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != count - 1,
-                Self::HorizontalStack => idx == 0,
+                Self::VerticalStack | Self::RightMainVerticalStack => {
+                    idx != masters - 1 && idx != count - 1
+                }
+                Self::HorizontalStack => idx < masters && count > masters,
                 Self::UltrawideVerticalStack => idx > 1 && idx != count - 1,
+                Self::CenteredMaster => {
+                    centered_master_neighbour(op_direction, idx, count, layout_options).is_some()
+                }
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Dwindle | Self::Spiral => {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                        .is_some()
+                }
             },
             OperationDirection::Left => match self {
                 Self::BSP => idx != 0,
-                Self::Columns | Self::VerticalStack => idx != 0,
-                Self::RightMainVerticalStack => idx == 0,
+                Self::Columns | Self::Scrolling => idx != 0,
+                Self::VerticalStack => idx >= masters,
+                Self::RightMainVerticalStack => idx < masters && count > masters,
                 Self::Rows => false,
-                Self::HorizontalStack => idx != 0 && idx != 1,
+                Self::HorizontalStack => idx != 0 && idx != masters,
                 Self::UltrawideVerticalStack => idx != 1,
+                Self::CenteredMaster => {
+                    centered_master_neighbour(op_direction, idx, count, layout_options).is_some()
+                }
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Dwindle | Self::Spiral => {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                        .is_some()
+                }
             },
             OperationDirection::Right => match self {
                 Self::BSP => idx % 2 == 0 && idx != count - 1,
-                Self::Columns => idx != count - 1,
+                Self::Columns | Self::Scrolling => idx != count - 1,
                 Self::Rows => false,
-                Self::VerticalStack => idx == 0,
-                Self::RightMainVerticalStack => idx != 0,
-                Self::HorizontalStack => idx != 0 && idx != count - 1,
+                Self::VerticalStack => idx < masters && count > masters,
+                Self::RightMainVerticalStack => idx >= masters,
+                Self::HorizontalStack => idx != masters - 1 && idx != count - 1,
                 Self::UltrawideVerticalStack => match count {
                     2 => idx != 0,
                     _ => idx < 2,
                 },
+                Self::CenteredMaster => {
+                    centered_master_neighbour(op_direction, idx, count, layout_options).is_some()
+                }
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Dwindle | Self::Spiral => {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                        .is_some()
+                }
             },
         }
     }
@@ -142,6 +210,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP => {
@@ -151,13 +220,25 @@ impl Direction for DefaultLayout {
                     idx - 2
                 }
             }
-            Self::Columns => unreachable!(),
+            Self::Columns | Self::Scrolling => unreachable!(),
             Self::Rows
             | Self::VerticalStack
             | Self::UltrawideVerticalStack
             | Self::RightMainVerticalStack => idx - 1,
-            Self::HorizontalStack => 0,
+            Self::HorizontalStack => master_neighbour(layout_options, idx, count),
+            Self::CenteredMaster => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    centered_master_neighbour(op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
             Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Dwindle | Self::Spiral => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
         }
     }
 
@@ -166,6 +247,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP
@@ -173,9 +255,21 @@ impl Direction for DefaultLayout {
             | Self::VerticalStack
             | Self::UltrawideVerticalStack
             | Self::RightMainVerticalStack => idx + 1,
-            Self::Columns => unreachable!(),
-            Self::HorizontalStack => 1,
+            Self::Columns | Self::Scrolling => unreachable!(),
+            Self::HorizontalStack => stack_neighbour(layout_options, idx, count),
+            Self::CenteredMaster => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    centered_master_neighbour(op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
             Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Dwindle | Self::Spiral => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
         }
     }
 
@@ -184,6 +278,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP => {
@@ -193,16 +288,28 @@ impl Direction for DefaultLayout {
                     idx - 1
                 }
             }
-            Self::Columns | Self::HorizontalStack => idx - 1,
+            Self::Columns | Self::HorizontalStack | Self::Scrolling => idx - 1,
             Self::Rows => unreachable!(),
-            Self::VerticalStack => 0,
-            Self::RightMainVerticalStack => 1,
+            Self::VerticalStack => master_neighbour(layout_options, idx, count),
+            Self::RightMainVerticalStack => stack_neighbour(layout_options, idx, count),
             Self::UltrawideVerticalStack => match idx {
                 0 => 1,
                 1 => unreachable!(),
                 _ => 0,
             },
+            Self::CenteredMaster => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    centered_master_neighbour(op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
             Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Dwindle | Self::Spiral => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
         }
     }
 
@@ -211,22 +318,197 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
-            Self::BSP | Self::Columns | Self::HorizontalStack => idx + 1,
+            Self::BSP | Self::Columns | Self::HorizontalStack | Self::Scrolling => idx + 1,
             Self::Rows => unreachable!(),
-            Self::VerticalStack => 1,
-            Self::RightMainVerticalStack => 0,
+            Self::VerticalStack => stack_neighbour(layout_options, idx, count),
+            Self::RightMainVerticalStack => master_neighbour(layout_options, idx, count),
             Self::UltrawideVerticalStack => match idx {
                 1 => 0,
                 0 => 2,
                 _ => unreachable!(),
             },
+            Self::CenteredMaster => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    centered_master_neighbour(op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
             Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Dwindle | Self::Spiral => op_direction
+                .zip(count)
+                .and_then(|(op_direction, count)| {
+                    binary_split_neighbour(*self, op_direction, idx, count, layout_options)
+                })
+                .unwrap_or_default(),
         }
     }
 }
 
+/// The neighbour of a container in the given direction in the Dwindle and Spiral layouts, based on
+/// the positions of the containers in a reference area
+fn binary_split_neighbour(
+    layout: DefaultLayout,
+    op_direction: OperationDirection,
+    idx: usize,
+    count: usize,
+    layout_options: Option<LayoutOptions>,
+) -> Option<usize> {
+    let reference = Rect {
+        left: 0,
+        top: 0,
+        right: 10_000,
+        bottom: 10_000,
+    };
+
+    let areas = binary_split(
+        &reference,
+        count,
+        layout_options.unwrap_or_default().split_ratio(),
+        matches!(layout, DefaultLayout::Spiral),
+    );
+
+    op_direction.closest_rect(idx, &areas)
+}
+
+/// The neighbour of a container in the given direction in the CenteredMaster layout, where the
+/// master is in the centre column, between the left and the right stacks
+fn centered_master_neighbour(
+    op_direction: OperationDirection,
+    idx: usize,
+    count: usize,
+    layout_options: Option<LayoutOptions>,
+) -> Option<usize> {
+    let (left_stack, right_stack) = layout_options
+        .unwrap_or_default()
+        .centered_master_fill()
+        .stacks(count);
+
+    let stack_position = |stack: &[usize]| stack.iter().position(|i| *i == idx);
+
+    match op_direction {
+        OperationDirection::Left => match idx {
+            0 => left_stack.first().copied(),
+            _ if right_stack.contains(&idx) => Some(0),
+            _ => None,
+        },
+        OperationDirection::Right => match idx {
+            0 => right_stack.first().copied(),
+            _ if left_stack.contains(&idx) => Some(0),
+            _ => None,
+        },
+        OperationDirection::Up => [left_stack.as_slice(), right_stack.as_slice()]
+            .iter()
+            .find_map(|&stack| {
+                stack_position(stack)
+                    .and_then(|position| position.checked_sub(1))
+                    .map(|position| stack[position])
+            }),
+        OperationDirection::Down => [left_stack.as_slice(), right_stack.as_slice()]
+            .iter()
+            .find_map(|&stack| {
+                stack_position(stack).and_then(|position| stack.get(position + 1).copied())
+            }),
+    }
+}
+
+fn master_count(layout_options: Option<LayoutOptions>, count: usize) -> usize {
+    layout_options
+        .unwrap_or_default()
+        .master_count()
+        .min(count)
+        .max(1)
+}
+
+/// The container in the master area adjacent to a container in the stack, matched by position
+fn master_neighbour(
+    layout_options: Option<LayoutOptions>,
+    idx: usize,
+    count: Option<usize>,
+) -> usize {
+    let masters = master_count(layout_options, count.unwrap_or_default());
+    (idx - masters).min(masters - 1)
+}
+
+/// The container in the stack adjacent to a container in the master area, matched by position
+fn stack_neighbour(
+    layout_options: Option<LayoutOptions>,
+    idx: usize,
+    count: Option<usize>,
+) -> usize {
+    let count = count.unwrap_or_default();
+    let masters = master_count(layout_options, count);
+    masters + idx.min(count.saturating_sub(masters + 1))
+}
+
+impl Direction for CustomLayoutTree {
+    fn index_in_direction(
+        &self,
+        op_direction: OperationDirection,
+        idx: usize,
+        count: usize,
+        _layout_options: Option<LayoutOptions>,
+    ) -> Option<usize> {
+        self.neighbour(op_direction, idx, count)
+    }
+
+    fn is_valid_direction(
+        &self,
+        op_direction: OperationDirection,
+        idx: usize,
+        count: usize,
+        _layout_options: Option<LayoutOptions>,
+    ) -> bool {
+        self.neighbour(op_direction, idx, count).is_some()
+    }
+
+    fn up_index(
+        &self,
+        _op_direction: Option<OperationDirection>,
+        idx: usize,
+        count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
+    ) -> usize {
+        self.neighbour(OperationDirection::Up, idx, count.unwrap_or_default())
+            .unwrap_or(idx)
+    }
+
+    fn down_index(
+        &self,
+        _op_direction: Option<OperationDirection>,
+        idx: usize,
+        count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
+    ) -> usize {
+        self.neighbour(OperationDirection::Down, idx, count.unwrap_or_default())
+            .unwrap_or(idx)
+    }
+
+    fn left_index(
+        &self,
+        _op_direction: Option<OperationDirection>,
+        idx: usize,
+        count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
+    ) -> usize {
+        self.neighbour(OperationDirection::Left, idx, count.unwrap_or_default())
+            .unwrap_or(idx)
+    }
+
+    fn right_index(
+        &self,
+        _op_direction: Option<OperationDirection>,
+        idx: usize,
+        count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
+    ) -> usize {
+        self.neighbour(OperationDirection::Right, idx, count.unwrap_or_default())
+            .unwrap_or(idx)
+    }
+}
+
 struct GridItem {
     state: GridItemState,
     row: usize,
@@ -348,36 +630,42 @@ impl Direction for CustomLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize> {
         if count <= self.len() {
-            return DefaultLayout::Columns.index_in_direction(op_direction, idx, count);
+            return DefaultLayout::Columns.index_in_direction(
+                op_direction,
+                idx,
+                count,
+                layout_options,
+            );
         }
 
         match op_direction {
             OperationDirection::Left => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.left_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.left_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
             }
             OperationDirection::Right => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.right_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.right_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
             }
             OperationDirection::Up => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.up_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.up_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
             }
             OperationDirection::Down => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.down_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.down_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
@@ -390,15 +678,27 @@ impl Direction for CustomLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> bool {
         if count <= self.len() {
-            return DefaultLayout::Columns.is_valid_direction(op_direction, idx, count);
+            return DefaultLayout::Columns.is_valid_direction(
+                op_direction,
+                idx,
+                count,
+                layout_options,
+            );
         }
 
         match op_direction {
-            OperationDirection::Left => idx != 0 && self.column_for_container_idx(idx) != 0,
+            // Containers in vertically split columns can move between the sub-columns of the
+            // first and the last columns
+            OperationDirection::Left => {
+                idx != 0 && (self.column_for_container_idx(idx) != 0 || self.is_vertically_split(0))
+            }
             OperationDirection::Right => {
-                idx != count - 1 && self.column_for_container_idx(idx) != self.len() - 1
+                idx != count - 1
+                    && (self.column_for_container_idx(idx) != self.len() - 1
+                        || self.is_vertically_split(self.len() - 1))
             }
             OperationDirection::Up => {
                 if idx == 0 {
@@ -436,6 +736,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         idx - 1
     }
@@ -445,6 +746,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         idx + 1
     }
@@ -454,9 +756,17 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         let column_idx = self.column_for_container_idx(idx);
-        if column_idx - 1 == 0 {
+        if self.is_vertically_split(column_idx)
+            && self.column_for_container_idx(idx - 1) == column_idx
+        {
+            idx - 1
+        } else if self.is_vertically_split(column_idx - 1) {
+            // The rightmost sub-column of the previous column
+            self.first_container_idx(column_idx) - 1
+        } else if column_idx - 1 == 0 {
             0
         } else {
             self.first_container_idx(column_idx - 1)
@@ -468,8 +778,45 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         let column_idx = self.column_for_container_idx(idx);
-        self.first_container_idx(column_idx + 1)
+        if self.is_vertically_split(column_idx)
+            && self.column_for_container_idx(idx + 1) == column_idx
+        {
+            idx + 1
+        } else {
+            self.first_container_idx(column_idx + 1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(master_count: usize) -> Option<LayoutOptions> {
+        Option::from(LayoutOptions {
+            master_count: Option::from(master_count),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn stack_containers_move_to_the_master_at_the_same_position() {
+        assert_eq!(master_neighbour(options(2), 2, Some(5)), 0);
+        assert_eq!(master_neighbour(options(2), 3, Some(5)), 1);
+        // stack containers beyond the last master move to the last master
+        assert_eq!(master_neighbour(options(2), 4, Some(5)), 1);
+        assert_eq!(master_neighbour(None, 3, Some(5)), 0);
+    }
+
+    #[test]
+    fn master_containers_move_to_the_stack_at_the_same_position() {
+        assert_eq!(stack_neighbour(options(2), 0, Some(5)), 2);
+        assert_eq!(stack_neighbour(options(2), 1, Some(5)), 3);
+        // masters beyond the last stack container move to the last stack container
+        assert_eq!(stack_neighbour(options(2), 1, Some(3)), 2);
+        assert_eq!(stack_neighbour(None, 0, Some(5)), 1);
     }
 }
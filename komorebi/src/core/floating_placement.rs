@@ -0,0 +1,162 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::Rect;
+
+/// A position and size where each value is a percentage of the width or height of the work area
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct PercentageRect {
+    /// Distance of the left edge from the left of the work area
+    pub left: f32,
+    /// Distance of the top edge from the top of the work area
+    pub top: f32,
+    /// Width of the window
+    pub width: f32,
+    /// Height of the window
+    pub height: f32,
+}
+
+/// Where a window is placed within the work area of a monitor when it starts floating
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub enum FloatingPlacement {
+    /// In the centre of the work area, keeping the size of the window
+    Center,
+    /// In the top left corner of the work area, keeping the size of the window
+    TopLeft,
+    /// In the top right corner of the work area, keeping the size of the window
+    TopRight,
+    /// In the bottom left corner of the work area, keeping the size of the window
+    BottomLeft,
+    /// In the bottom right corner of the work area, keeping the size of the window
+    BottomRight,
+    /// At a position and size relative to the work area
+    Rect(PercentageRect),
+}
+
+impl FloatingPlacement {
+    /// The position of a window of the given size when it is placed in the work area. Windows
+    /// which are larger than the work area are shrunk to fit inside of it
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn rect(&self, window: &Rect, work_area: &Rect) -> Rect {
+        let width = window.right.min(work_area.right);
+        let height = window.bottom.min(work_area.bottom);
+
+        let right = work_area.left + work_area.right - width;
+        let bottom = work_area.top + work_area.bottom - height;
+
+        let (left, top) = match self {
+            Self::Center => (
+                work_area.left + (work_area.right - width) / 2,
+                work_area.top + (work_area.bottom - height) / 2,
+            ),
+            Self::TopLeft => (work_area.left, work_area.top),
+            Self::TopRight => (right, work_area.top),
+            Self::BottomLeft => (work_area.left, bottom),
+            Self::BottomRight => (right, bottom),
+            Self::Rect(rect) => {
+                let percentage = |value: f32, total: i32| {
+                    (value.clamp(0.0, 100.0) / 100.0 * total as f32).round() as i32
+                };
+
+                return Rect {
+                    left: work_area.left + percentage(rect.left, work_area.right),
+                    top: work_area.top + percentage(rect.top, work_area.bottom),
+                    right: percentage(rect.width, work_area.right),
+                    bottom: percentage(rect.height, work_area.bottom),
+                };
+            }
+        };
+
+        Rect {
+            left,
+            top,
+            right: width,
+            bottom: height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    const WORK_AREA: Rect = Rect {
+        left: 100,
+        top: 50,
+        right: 1000,
+        bottom: 800,
+    };
+
+    #[test]
+    fn windows_keep_their_size_in_the_corners_and_centre() {
+        let window = rect(0, 0, 400, 300);
+
+        let placements = [
+            (FloatingPlacement::Center, rect(400, 300, 400, 300)),
+            (FloatingPlacement::TopLeft, rect(100, 50, 400, 300)),
+            (FloatingPlacement::TopRight, rect(700, 50, 400, 300)),
+            (FloatingPlacement::BottomLeft, rect(100, 550, 400, 300)),
+            (FloatingPlacement::BottomRight, rect(700, 550, 400, 300)),
+        ];
+
+        for (placement, expected) in placements {
+            assert_eq!(
+                placement.rect(&window, &WORK_AREA),
+                expected,
+                "{placement:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn windows_larger_than_the_work_area_are_shrunk_to_fit() {
+        let window = rect(0, 0, 1200, 900);
+
+        assert_eq!(
+            FloatingPlacement::Center.rect(&window, &WORK_AREA),
+            WORK_AREA
+        );
+        assert_eq!(
+            FloatingPlacement::BottomRight.rect(&window, &WORK_AREA),
+            WORK_AREA
+        );
+    }
+
+    #[test]
+    fn percentages_are_relative_to_the_work_area() {
+        let window = rect(0, 0, 400, 300);
+
+        let placement = FloatingPlacement::Rect(PercentageRect {
+            left: 10.0,
+            top: 25.0,
+            width: 50.0,
+            height: 50.0,
+        });
+
+        assert_eq!(
+            placement.rect(&window, &WORK_AREA),
+            rect(200, 250, 500, 400)
+        );
+
+        // percentages outside of 0-100 are clamped
+        let placement = FloatingPlacement::Rect(PercentageRect {
+            left: -10.0,
+            top: 0.0,
+            width: 150.0,
+            height: 100.0,
+        });
+
+        assert_eq!(placement.rect(&window, &WORK_AREA), WORK_AREA);
+    }
+}
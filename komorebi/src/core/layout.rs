@@ -1,32 +1,80 @@
+use std::path::Path;
+
+use color_eyre::Result;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::default_layout::resize_edge;
 use super::Arrangement;
 use super::CustomLayout;
+use super::CustomLayoutTree;
 use super::DefaultLayout;
 use super::Direction;
+use super::OperationDirection;
+use super::Rect;
+use super::Sizing;
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum Layout {
     Default(DefaultLayout),
     Custom(CustomLayout),
+    CustomTree(CustomLayoutTree),
+    /// A layout calculated by the external process registered under this name
+    External(String),
 }
 
 impl Layout {
+    /// Loads a custom layout file, which can either be a list of columns or a tree of splits
+    pub fn from_custom_layout_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        if CustomLayoutTree::is_tree_layout_file(path)? {
+            Ok(Layout::CustomTree(CustomLayoutTree::from_path(path)?))
+        } else {
+            Ok(Layout::Custom(CustomLayout::from_path(path)?))
+        }
+    }
+
+    /// External layouts are only known to the process which calculates them, so BSP is used as a
+    /// stand-in wherever the positions of containers have to be worked out locally
     #[must_use]
     pub fn as_boxed_direction(&self) -> Box<dyn Direction> {
         match self {
             Layout::Default(layout) => Box::new(*layout),
             Layout::Custom(layout) => Box::new(layout.clone()),
+            Layout::CustomTree(layout) => Box::new(layout.clone()),
+            Layout::External(_) => Box::new(DefaultLayout::BSP),
         }
     }
 
+    /// Workspaces request the arrangement of an external layout from its process, so BSP is also
+    /// the stand-in here
     #[must_use]
     pub fn as_boxed_arrangement(&self) -> Box<dyn Arrangement> {
         match self {
             Layout::Default(layout) => Box::new(*layout),
             Layout::Custom(layout) => Box::new(layout.clone()),
+            Layout::CustomTree(layout) => Box::new(layout.clone()),
+            Layout::External(_) => Box::new(DefaultLayout::BSP),
+        }
+    }
+
+    /// The new resize adjustment of a container after resizing the given edge, or `None` if the
+    /// layout does not support resizing
+    #[must_use]
+    pub fn resize(
+        &self,
+        unaltered: &Rect,
+        resize: &Option<Rect>,
+        edge: OperationDirection,
+        sizing: Sizing,
+        delta: i32,
+    ) -> Option<Rect> {
+        match self {
+            Layout::Default(layout) => layout.resize(unaltered, resize, edge, sizing, delta),
+            Layout::Custom(_) => resize_edge(unaltered, resize, edge, sizing, delta),
+            Layout::CustomTree(_) | Layout::External(_) => None,
         }
     }
 }
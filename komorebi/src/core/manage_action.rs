@@ -0,0 +1,22 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An action which is taken on a window when it is first managed, before the workspace is laid
+/// out again
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum ManageAction {
+    /// Open the window in a monocle container
+    Monocle,
+    /// Maximize the window
+    Maximize,
+    /// Stack the window in the first other container on the workspace which has a window of the
+    /// same application
+    StackWithSameExe,
+    /// Make the window transparent with this alpha value (0-255) instead of fully opaque
+    Transparency(u8),
+    /// Remove the title bar of the window, which is restored when komorebi stops
+    RemoveTitleBar,
+    /// Move the container of the window to this index in the layout
+    LayoutIndex(usize),
+}
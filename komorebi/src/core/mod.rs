@@ -1,6 +1,7 @@
 #![warn(clippy::all)]
 #![allow(clippy::missing_errors_doc, clippy::use_self, clippy::doc_markdown)]
 
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -14,27 +15,47 @@ use serde::Serialize;
 use strum::Display;
 use strum::EnumString;
 
+pub use animation::AnimationOperation;
+pub use animation::AnimationOverride;
 pub use animation::AnimationStyle;
+pub use animation::WorkspaceTransition;
 pub use arrangement::Arrangement;
 pub use arrangement::Axis;
 pub use custom_layout::CustomLayout;
+pub use custom_layout_tree::CustomLayoutTree;
+pub use custom_layout_tree::LayoutNode;
 pub use cycle_direction::CycleDirection;
+pub use default_layout::CenteredMasterFill;
+pub use default_layout::CenteredMasterLayoutOptions;
 pub use default_layout::DefaultLayout;
+pub use default_layout::LayoutOptions;
+pub use default_layout::ScrollingLayoutOptions;
 pub use direction::Direction;
+pub use floating_placement::FloatingPlacement;
+pub use floating_placement::PercentageRect;
 pub use layout::Layout;
+pub use manage_action::ManageAction;
 pub use operation_direction::OperationDirection;
 pub use rect::Rect;
+pub use rect::RelativeRect;
+pub use rect::SavedResizeDimensions;
+pub use rect::SidePadding;
+pub use size_constraints::WindowSizeConstraints;
 
 pub mod animation;
 pub mod arrangement;
 pub mod config_generation;
 pub mod custom_layout;
+pub mod custom_layout_tree;
 pub mod cycle_direction;
 pub mod default_layout;
 pub mod direction;
+pub mod floating_placement;
 pub mod layout;
+pub mod manage_action;
 pub mod operation_direction;
 pub mod rect;
+pub mod size_constraints;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(tag = "type", content = "content")]
@@ -82,15 +103,22 @@ pub enum SocketMessage {
     ToggleCrossMonitorMoveBehaviour,
     CrossMonitorMoveBehaviour(MoveBehaviour),
     UnmanagedWindowOperationBehaviour(OperationBehaviour),
+    NavigationBehaviour(NavigationBehaviour),
     // Current Workspace Commands
     ManageFocusedWindow,
     UnmanageFocusedWindow,
     AdjustContainerPadding(Sizing, i32),
     AdjustWorkspacePadding(Sizing, i32),
+    AdjustInnerGap(Sizing, i32),
+    ToggleSmartGaps,
     ChangeLayout(DefaultLayout),
     CycleLayout(CycleDirection),
     ChangeLayoutCustom(PathBuf),
+    ChangeLayoutExternal(String),
     FlipLayout(Axis),
+    ScrollingLayoutColumns(NonZeroUsize),
+    AdjustMasterCount(Sizing),
+    AdjustMasterRatio(Sizing),
     ToggleWorkspaceWindowContainerBehaviour,
     ToggleWorkspaceFloatOverride,
     // Monitor and Workspace Commands
@@ -121,6 +149,12 @@ pub enum SocketMessage {
     WorkspacePadding(usize, usize, i32),
     NamedWorkspacePadding(String, i32),
     FocusedWorkspacePadding(i32),
+    WorkspaceInnerGap(usize, usize, i32),
+    NamedWorkspaceInnerGap(String, i32),
+    WorkspaceOuterGap(usize, usize, SidePadding),
+    NamedWorkspaceOuterGap(String, SidePadding),
+    WorkspaceSmartGaps(usize, usize, bool),
+    NamedWorkspaceSmartGaps(String, bool),
     WorkspaceTiling(usize, usize, bool),
     NamedWorkspaceTiling(String, bool),
     WorkspaceName(usize, usize, String),
@@ -199,12 +233,15 @@ pub enum SocketMessage {
     RemoveSubscriberSocket(String),
     AddSubscriberPipe(String),
     RemoveSubscriberPipe(String),
+    RegisterExternalLayout(String),
+    UnregisterExternalLayout(String),
     ApplicationSpecificConfigurationSchema,
     NotificationSchema,
     SocketSchema,
     StaticConfigSchema,
     GenerateStaticConfig,
     DebugWindow(isize),
+    EvaluateRules(String, String, String, String),
 }
 
 impl SocketMessage {
@@ -398,6 +435,16 @@ pub enum CrossBoundaryBehaviour {
     Monitor,
 }
 
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
+)]
+pub enum NavigationBehaviour {
+    /// Find the container in a direction based on its index in the layout
+    Layout,
+    /// Find the container in a direction based on the positions of the containers on screen
+    Geometric,
+}
+
 #[derive(
     Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
 )]
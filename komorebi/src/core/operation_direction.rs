@@ -9,6 +9,8 @@ use strum::EnumString;
 
 use super::direction::Direction;
 use super::Axis;
+use super::LayoutOptions;
+use super::Rect;
 
 #[derive(
     Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
@@ -31,6 +33,47 @@ impl OperationDirection {
         }
     }
 
+    /// The index of the closest rect in this direction from the rect at the given index. Only rects
+    /// which are entirely in this direction and which share part of an edge with the origin are
+    /// considered, and ties in distance go to the rect with the largest shared edge
+    #[must_use]
+    pub fn closest_rect(self, idx: usize, rects: &[Rect]) -> Option<usize> {
+        let origin = rects.get(idx)?;
+
+        rects
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != idx)
+            .filter_map(|(i, candidate)| {
+                let (distance, overlap) = match self {
+                    Self::Left => (
+                        origin.left - (candidate.left + candidate.right),
+                        segment_overlap(origin.top, origin.bottom, candidate.top, candidate.bottom),
+                    ),
+                    Self::Right => (
+                        candidate.left - (origin.left + origin.right),
+                        segment_overlap(origin.top, origin.bottom, candidate.top, candidate.bottom),
+                    ),
+                    Self::Up => (
+                        origin.top - (candidate.top + candidate.bottom),
+                        segment_overlap(origin.left, origin.right, candidate.left, candidate.right),
+                    ),
+                    Self::Down => (
+                        candidate.top - (origin.top + origin.bottom),
+                        segment_overlap(origin.left, origin.right, candidate.left, candidate.right),
+                    ),
+                };
+
+                (distance >= 0 && overlap > 0).then_some((i, distance, overlap))
+            })
+            .min_by(|(_, a_distance, a_overlap), (_, b_distance, b_overlap)| {
+                a_distance
+                    .cmp(b_distance)
+                    .then_with(|| b_overlap.cmp(a_overlap))
+            })
+            .map(|(i, _, _)| i)
+    }
+
     fn flip(self, layout_flip: Option<Axis>) -> Self {
         layout_flip.map_or(self, |flip| match self {
             Self::Left => match flip {
@@ -59,7 +102,66 @@ impl OperationDirection {
         layout_flip: Option<Axis>,
         idx: usize,
         len: NonZeroUsize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize> {
-        layout.index_in_direction(self.flip(layout_flip), idx, len.get())
+        layout.index_in_direction(self.flip(layout_flip), idx, len.get(), layout_options)
+    }
+}
+
+/// The length of the overlap between two segments given as (start, length)
+fn segment_overlap(a_start: i32, a_length: i32, b_start: i32, b_length: i32) -> i32 {
+    (a_start + a_length).min(b_start + b_length) - a_start.max(b_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    #[test]
+    fn closest_rect_prefers_the_largest_shared_edge() {
+        let rects = [
+            rect(0, 0, 500, 1000),
+            rect(500, 0, 500, 300),
+            rect(500, 300, 500, 700),
+        ];
+
+        assert_eq!(OperationDirection::Right.closest_rect(0, &rects), Some(2));
+        assert_eq!(OperationDirection::Left.closest_rect(1, &rects), Some(0));
+        assert_eq!(OperationDirection::Down.closest_rect(1, &rects), Some(2));
+        assert_eq!(OperationDirection::Up.closest_rect(2, &rects), Some(1));
+    }
+
+    #[test]
+    fn closest_rect_prefers_the_shortest_distance() {
+        let rects = [
+            rect(0, 0, 100, 100),
+            rect(300, 0, 100, 100),
+            rect(150, 0, 100, 50),
+        ];
+
+        assert_eq!(OperationDirection::Right.closest_rect(0, &rects), Some(2));
+        assert_eq!(OperationDirection::Left.closest_rect(1, &rects), Some(2));
+    }
+
+    #[test]
+    fn closest_rect_ignores_rects_which_do_not_share_an_edge() {
+        let rects = [rect(0, 0, 100, 100), rect(100, 100, 100, 100)];
+
+        // only the corners touch
+        assert_eq!(OperationDirection::Right.closest_rect(0, &rects), None);
+        assert_eq!(OperationDirection::Down.closest_rect(0, &rects), None);
+        // rects behind the origin are never candidates
+        assert_eq!(OperationDirection::Left.closest_rect(0, &rects), None);
+        // neither is an origin which doesn't exist
+        assert_eq!(OperationDirection::Right.closest_rect(2, &rects), None);
     }
 }
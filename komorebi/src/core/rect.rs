@@ -1,9 +1,10 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(windows)]
 use windows::Win32::Foundation::RECT;
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, JsonSchema)]
 pub struct Rect {
     /// The left point in a Win32 Rect
     pub left: i32,
@@ -15,6 +16,55 @@ pub struct Rect {
     pub bottom: i32,
 }
 
+/// Padding applied separately to each side of a rect
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+pub struct SidePadding {
+    /// Padding applied to the top of a rect
+    pub top: i32,
+    /// Padding applied to the bottom of a rect
+    pub bottom: i32,
+    /// Padding applied to the left of a rect
+    pub left: i32,
+    /// Padding applied to the right of a rect
+    pub right: i32,
+}
+
+impl SidePadding {
+    #[must_use]
+    pub const fn uniform(padding: i32) -> Self {
+        Self {
+            top: padding,
+            bottom: padding,
+            left: padding,
+            right: padding,
+        }
+    }
+
+    /// split a gap between two adjacent rects so that half of it is applied to each of them; the
+    /// extra pixel of an odd gap goes to the bottom and right sides
+    #[must_use]
+    pub const fn split_gap(gap: i32) -> Self {
+        Self {
+            top: gap / 2,
+            bottom: gap - gap / 2,
+            left: gap / 2,
+            right: gap - gap / 2,
+        }
+    }
+
+    /// reduce each side of self by the same side of the given padding.
+    #[must_use]
+    pub const fn shrink(&self, padding: Self) -> Self {
+        Self {
+            top: self.top - padding.top,
+            bottom: self.bottom - padding.bottom,
+            left: self.left - padding.left,
+            right: self.right - padding.right,
+        }
+    }
+}
+
+#[cfg(windows)]
 impl From<RECT> for Rect {
     fn from(rect: RECT) -> Self {
         Self {
@@ -26,6 +76,7 @@ impl From<RECT> for Rect {
     }
 }
 
+#[cfg(windows)]
 impl From<Rect> for RECT {
     fn from(rect: Rect) -> Self {
         Self {
@@ -51,6 +102,14 @@ impl Rect {
         }
     }
 
+    /// decrease the size of self by a separate padding amount on each side.
+    pub fn add_side_padding(&mut self, padding: SidePadding) {
+        self.left += padding.left;
+        self.top += padding.top;
+        self.right -= padding.left + padding.right;
+        self.bottom -= padding.top + padding.bottom;
+    }
+
     /// increase the size of self by the margin amount.
     pub fn add_margin(&mut self, margin: i32) {
         self.left -= margin;
@@ -85,6 +144,7 @@ impl Rect {
         }
     }
 
+    #[cfg(windows)]
     #[must_use]
     pub const fn rect(&self) -> RECT {
         RECT {
@@ -95,3 +155,74 @@ impl Rect {
         }
     }
 }
+
+/// A resize adjustment where each edge is stored as a fraction of the width or height of the work
+/// area, so that it can be applied to work areas of any resolution
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RelativeRect {
+    /// The adjustment of the left edge as a fraction of the work area width
+    pub left: f32,
+    /// The adjustment of the top edge as a fraction of the work area height
+    pub top: f32,
+    /// The adjustment of the right edge as a fraction of the work area width
+    pub right: f32,
+    /// The adjustment of the bottom edge as a fraction of the work area height
+    pub bottom: f32,
+}
+
+impl RelativeRect {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_rect(rect: &Rect, area: &Rect) -> Self {
+        let fraction = |value: i32, total: i32| {
+            if total == 0 {
+                0.0
+            } else {
+                value as f32 / total as f32
+            }
+        };
+
+        Self {
+            left: fraction(rect.left, area.right),
+            top: fraction(rect.top, area.bottom),
+            right: fraction(rect.right, area.right),
+            bottom: fraction(rect.bottom, area.bottom),
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn to_rect(&self, area: &Rect) -> Rect {
+        Rect {
+            left: (self.left * area.right as f32).round() as i32,
+            top: (self.top * area.bottom as f32).round() as i32,
+            right: (self.right * area.right as f32).round() as i32,
+            bottom: (self.bottom * area.bottom as f32).round() as i32,
+        }
+    }
+}
+
+/// Resize dimensions as they are written to disk by the save commands. Older versions of komorebi
+/// saved pixel values, which are still accepted so that they can be migrated when loaded
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum SavedResizeDimensions {
+    // Pixel values are integers and relative values are always written with a decimal point, so
+    // pixel values must be tried first
+    Pixels(Vec<Option<Rect>>),
+    Relative(Vec<Option<RelativeRect>>),
+}
+
+impl SavedResizeDimensions {
+    /// Pixel values are assumed to have been saved on a work area of the given size
+    #[must_use]
+    pub fn into_relative(self, area: &Rect) -> Vec<Option<RelativeRect>> {
+        match self {
+            Self::Pixels(resize_dimensions) => resize_dimensions
+                .iter()
+                .map(|resize| resize.map(|rect| RelativeRect::from_rect(&rect, area)))
+                .collect(),
+            Self::Relative(resize_dimensions) => resize_dimensions,
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::Rect;
+
+/// The smallest size, in pixels, that an unconstrained window will be shrunk to in order to make
+/// space for a constrained neighbour
+const MINIMUM_UNCONSTRAINED_SIZE: i32 = 100;
+
+/// Size limits, in pixels, for the windows of an application
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct WindowSizeConstraints {
+    /// Minimum width of the window (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_width: Option<i32>,
+    /// Maximum width of the window (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<i32>,
+    /// Minimum height of the window (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<i32>,
+    /// Maximum height of the window (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<i32>,
+    /// Fixed ratio of the width of the window to its height, eg. 1.7778 for 16:9 (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<f32>,
+}
+
+impl WindowSizeConstraints {
+    /// The aspect ratio, if it is usable
+    fn ratio(&self) -> Option<f32> {
+        self.aspect_ratio
+            .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+    }
+
+    /// The smallest window size along an axis which still meets every minimum once the window
+    /// has been shrunk to its aspect ratio
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn minimum(&self, horizontal: bool) -> Option<i32> {
+        let (minimum, other_minimum) = if horizontal {
+            (self.min_width, self.min_height)
+        } else {
+            (self.min_height, self.min_width)
+        };
+
+        let from_ratio = self.ratio().zip(other_minimum).map(|(ratio, other)| {
+            if horizontal {
+                (other as f32 * ratio).ceil() as i32
+            } else {
+                (other as f32 / ratio).ceil() as i32
+            }
+        });
+
+        match (minimum, from_ratio) {
+            (Some(minimum), Some(from_ratio)) => Option::from(minimum.max(from_ratio)),
+            (minimum, from_ratio) => minimum.or(from_ratio),
+        }
+    }
+
+    const fn maximum(&self, horizontal: bool) -> Option<i32> {
+        if horizontal {
+            self.max_width
+        } else {
+            self.max_height
+        }
+    }
+
+    /// The largest window size which fits within the given size while respecting the maximums
+    /// and the aspect ratio
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn fit(&self, width: i32, height: i32) -> (i32, i32) {
+        let mut width = self.max_width.map_or(width, |max| width.min(max));
+        let mut height = self.max_height.map_or(height, |max| height.min(max));
+
+        if let Some(ratio) = self.ratio() {
+            if width as f32 > height as f32 * ratio {
+                width = (height as f32 * ratio) as i32;
+            } else {
+                height = (width as f32 / ratio) as i32;
+            }
+        }
+
+        (width, height)
+    }
+}
+
+/// Adjust the tiles calculated by a layout so that the windows inside them respect their size
+/// constraints. Edges shared between tiles are moved to give constrained tiles the space they need
+/// at the expense of their neighbours, and tiles which are still larger than their windows allow
+/// are shrunk around their centre. The insets are the horizontal and vertical space taken up by
+/// padding and borders in each tile. Returns the indices of the tiles which are still too small
+pub fn apply_size_constraints(
+    area: &Rect,
+    layouts: &mut [Rect],
+    constraints: &[Option<WindowSizeConstraints>],
+    insets: &[(i32, i32)],
+) -> Vec<usize> {
+    if constraints.iter().all(Option::is_none) {
+        return vec![];
+    }
+
+    let inset = |idx: usize, horizontal: bool| {
+        insets
+            .get(idx)
+            .map_or(0, |(x, y)| if horizontal { *x } else { *y })
+    };
+
+    let constraint = |idx: usize| constraints.get(idx).copied().flatten();
+
+    for horizontal in [true, false] {
+        let minimums: Vec<i32> = (0..layouts.len())
+            .map(|idx| {
+                constraint(idx)
+                    .and_then(|constraint| constraint.minimum(horizontal))
+                    .unwrap_or(MINIMUM_UNCONSTRAINED_SIZE)
+                    + inset(idx, horizontal)
+            })
+            .collect();
+
+        for idx in 0..layouts.len() {
+            let Some(constraint) = constraint(idx) else {
+                continue;
+            };
+
+            let (_, size) = span(&layouts[idx], horizontal);
+
+            if constraint.minimum(horizontal).is_some() && size < minimums[idx] {
+                resize(
+                    area,
+                    layouts,
+                    &minimums,
+                    idx,
+                    minimums[idx] - size,
+                    horizontal,
+                );
+            } else if let Some(maximum) = constraint.maximum(horizontal) {
+                let excess = size - (maximum + inset(idx, horizontal));
+                if excess > 0 {
+                    resize(area, layouts, &minimums, idx, -excess, horizontal);
+                }
+            }
+        }
+    }
+
+    let mut unsatisfied = vec![];
+
+    for (idx, layout) in layouts.iter_mut().enumerate() {
+        let Some(constraint) = constraint(idx) else {
+            continue;
+        };
+
+        let (inset_x, inset_y) = (inset(idx, true), inset(idx, false));
+        let (width, height) = constraint.fit(
+            (layout.right - inset_x).max(0),
+            (layout.bottom - inset_y).max(0),
+        );
+
+        if constraint.min_width.is_some_and(|min| width < min)
+            || constraint.min_height.is_some_and(|min| height < min)
+        {
+            unsatisfied.push(idx);
+        }
+
+        // Whatever space the window can't use is split evenly around it
+        layout.left += (layout.right - inset_x - width) / 2;
+        layout.top += (layout.bottom - inset_y - height) / 2;
+        layout.right = width + inset_x;
+        layout.bottom = height + inset_y;
+    }
+
+    unsatisfied
+}
+
+/// Grow (or shrink, for a negative delta) a tile by moving its far edge and then, for whatever
+/// remains, its near edge. Edges on the boundary of the area are never moved, and no tile which is
+/// shrunk as a result is allowed to fall below its minimum size
+fn resize(
+    area: &Rect,
+    layouts: &mut [Rect],
+    minimums: &[i32],
+    idx: usize,
+    delta: i32,
+    horizontal: bool,
+) {
+    let (area_start, area_size) = span(area, horizontal);
+    let mut remaining = delta;
+
+    for near in [false, true] {
+        if remaining == 0 {
+            break;
+        }
+
+        let (start, size) = span(&layouts[idx], horizontal);
+        let (line, direction) = if near {
+            (start, -remaining.signum())
+        } else {
+            (start + size, remaining.signum())
+        };
+
+        if line == area_start || line == area_start + area_size {
+            continue;
+        }
+
+        let amount = remaining.abs().min(line_capacity(
+            layouts, minimums, line, direction, horizontal,
+        ));
+
+        move_line(layouts, line, amount * direction, horizontal);
+        remaining -= amount * remaining.signum();
+    }
+}
+
+/// How far an edge can be moved in a direction before any of the tiles it shrinks falls below its
+/// minimum size
+fn line_capacity(
+    layouts: &[Rect],
+    minimums: &[i32],
+    line: i32,
+    direction: i32,
+    horizontal: bool,
+) -> i32 {
+    layouts
+        .iter()
+        .zip(minimums)
+        .filter_map(|(layout, minimum)| {
+            let (start, size) = span(layout, horizontal);
+            let shrinks =
+                (direction > 0 && start == line) || (direction < 0 && start + size == line);
+
+            shrinks.then_some((size - minimum).max(0))
+        })
+        .min()
+        .unwrap_or(i32::MAX)
+}
+
+/// Move an edge along with every tile that starts or ends on it, which keeps the tiles covering
+/// the same area without overlapping
+fn move_line(layouts: &mut [Rect], line: i32, delta: i32, horizontal: bool) {
+    for layout in layouts {
+        let (start, size) = if horizontal {
+            (&mut layout.left, &mut layout.right)
+        } else {
+            (&mut layout.top, &mut layout.bottom)
+        };
+
+        if *start + *size == line {
+            *size += delta;
+        } else if *start == line {
+            *start += delta;
+            *size -= delta;
+        }
+    }
+}
+
+const fn span(rect: &Rect, horizontal: bool) -> (i32, i32) {
+    if horizontal {
+        (rect.left, rect.right)
+    } else {
+        (rect.top, rect.bottom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    fn min_width(min_width: i32) -> Option<WindowSizeConstraints> {
+        Option::from(WindowSizeConstraints {
+            min_width: Option::from(min_width),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn minimum_is_met_by_moving_a_shared_edge() {
+        let area = rect(0, 0, 1000, 500);
+        let mut layouts = vec![rect(0, 0, 500, 500), rect(500, 0, 500, 500)];
+
+        let unsatisfied = apply_size_constraints(&area, &mut layouts, &[min_width(700), None], &[]);
+
+        assert!(unsatisfied.is_empty());
+        assert_eq!(layouts, vec![rect(0, 0, 700, 500), rect(700, 0, 300, 500)]);
+    }
+
+    #[test]
+    fn unmet_minimum_is_reported_by_index() {
+        let area = rect(0, 0, 1200, 500);
+        let mut layouts = vec![
+            rect(0, 0, 400, 500),
+            rect(400, 0, 400, 500),
+            rect(800, 0, 400, 500),
+        ];
+
+        let unsatisfied =
+            apply_size_constraints(&area, &mut layouts, &[None, min_width(1100), None], &[]);
+
+        // both neighbours are shrunk down to the smallest unconstrained size, which still isn't
+        // enough space
+        assert_eq!(unsatisfied, vec![1]);
+        assert_eq!(
+            layouts,
+            vec![
+                rect(0, 0, 100, 500),
+                rect(100, 0, 1000, 500),
+                rect(1100, 0, 100, 500)
+            ]
+        );
+    }
+
+    #[test]
+    fn maximum_shrinks_the_tile_around_its_centre() {
+        let area = rect(0, 0, 1000, 500);
+        let mut layouts = vec![area];
+        let constraints = [Option::from(WindowSizeConstraints {
+            max_width: Option::from(600),
+            max_height: Option::from(400),
+            ..Default::default()
+        })];
+
+        let unsatisfied = apply_size_constraints(&area, &mut layouts, &constraints, &[]);
+
+        assert!(unsatisfied.is_empty());
+        assert_eq!(layouts, vec![rect(200, 50, 600, 400)]);
+    }
+
+    #[test]
+    fn aspect_ratio_fits_the_window_within_the_tile() {
+        let area = rect(0, 0, 1600, 800);
+        let mut layouts = vec![rect(0, 0, 800, 800), rect(800, 0, 800, 800)];
+        let constraints = [
+            Option::from(WindowSizeConstraints {
+                aspect_ratio: Option::from(2.0),
+                ..Default::default()
+            }),
+            Option::from(WindowSizeConstraints {
+                aspect_ratio: Option::from(0.5),
+                ..Default::default()
+            }),
+        ];
+
+        let unsatisfied = apply_size_constraints(&area, &mut layouts, &constraints, &[]);
+
+        assert!(unsatisfied.is_empty());
+        assert_eq!(
+            layouts,
+            vec![rect(0, 200, 800, 400), rect(1000, 0, 400, 800)]
+        );
+    }
+
+    #[test]
+    fn work_area_edges_never_move() {
+        let area = rect(0, 0, 1000, 500);
+        let mut layouts = vec![area];
+
+        let unsatisfied = apply_size_constraints(&area, &mut layouts, &[min_width(1200)], &[]);
+
+        assert_eq!(unsatisfied, vec![0]);
+        assert_eq!(layouts, vec![area]);
+
+        // a tile against the edge of the work area can only grow away from it
+        let mut layouts = vec![rect(0, 0, 500, 500), rect(500, 0, 500, 500)];
+
+        let unsatisfied = apply_size_constraints(&area, &mut layouts, &[None, min_width(700)], &[]);
+
+        assert!(unsatisfied.is_empty());
+        assert_eq!(layouts, vec![rect(0, 0, 300, 500), rect(300, 0, 700, 500)]);
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::num::NonZeroUsize;
+#[cfg(not(windows))]
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+#[cfg(windows)]
+use uds_windows::UnixStream;
+
+use crate::core::Arrangement;
+use crate::core::Axis;
+use crate::core::DefaultLayout;
+use crate::core::LayoutOptions;
+use crate::core::Rect;
+use crate::core::RelativeRect;
+use crate::WindowManager;
+
+pub static EXTERNAL_LAYOUT_TIMEOUT: AtomicU64 = AtomicU64::new(100);
+
+lazy_static! {
+    pub static ref EXTERNAL_LAYOUTS: Arc<Mutex<HashMap<String, PathBuf>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref RESPONSES: Arc<Mutex<HashMap<(String, Rect), ExternalLayoutResponse>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+pub struct Notification(ExternalLayoutRequest);
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+/// The latest request sent to an external layout for a work area, and the rects from the latest
+/// successful response
+#[derive(Debug, Default)]
+struct ExternalLayoutResponse {
+    request: Option<ExternalLayoutRequest>,
+    layouts: Vec<Rect>,
+    failed: bool,
+}
+
+/// Forget every response from an external layout, so that it is asked again the next time that a
+/// workspace using it is updated
+pub fn clear_responses(name: &str) {
+    RESPONSES.lock().retain(|(layout, _), _| layout != name);
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+/// External layout processes are only ever contacted from this thread, so that the window manager
+/// is never locked while waiting for them. Workspaces are updated again once a response arrives
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    for Notification(request) in receiver {
+        let result = ExternalLayout::from(request.layout.clone()).request(&request);
+
+        {
+            let mut responses = RESPONSES.lock();
+            let Some(response) = responses.get_mut(&(request.layout.clone(), request.work_area))
+            else {
+                continue;
+            };
+
+            // A newer request has been sent since this one, so this response is already stale
+            if response.request.as_ref() != Some(&request) {
+                continue;
+            }
+
+            match result {
+                Ok(layouts) => {
+                    response.layouts = layouts;
+                    response.failed = false;
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "falling back to BSP as the external layout {} could not be used: {error}",
+                        request.layout
+                    );
+
+                    response.failed = true;
+                }
+            }
+        }
+
+        wm.lock().retile_all(true)?;
+    }
+
+    Ok(())
+}
+
+/// The request sent to an external layout process, which should reply with a JSON list of one
+/// `Rect` for each container before closing the connection
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ExternalLayoutRequest {
+    /// The name the external layout was registered under
+    pub layout: String,
+    /// The area that the containers should be arranged in
+    pub work_area: Rect,
+    /// The number of containers to arrange
+    pub container_count: usize,
+    /// The index of the focused container
+    pub focused_idx: usize,
+    /// The resize adjustment in pixels of each container, if it has been resized
+    pub resize_dimensions: Vec<Option<Rect>>,
+    /// The axis that the layout has been flipped on, if any
+    pub layout_flip: Option<Axis>,
+}
+
+/// A layout calculated by an external process, which is asked for the position of every
+/// container whenever the request for a workspace changes. The rects from the previous response
+/// are used while waiting for an answer if they still fit the number of containers, and BSP is
+/// used otherwise, or whenever the process can't be reached, doesn't respond in time or responds
+/// with the wrong number of containers
+#[derive(Debug, Clone)]
+pub struct ExternalLayout {
+    name: String,
+}
+
+impl From<String> for ExternalLayout {
+    fn from(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl ExternalLayout {
+    fn request(&self, request: &ExternalLayoutRequest) -> Result<Vec<Rect>> {
+        let path = EXTERNAL_LAYOUTS
+            .lock()
+            .get(&self.name)
+            .cloned()
+            .ok_or_else(|| anyhow!("there is no external layout registered as {}", self.name))?;
+
+        // The layout stays registered when it can't be reached, as the process may just be
+        // restarting; it is only removed when it is explicitly unregistered
+        let mut stream = UnixStream::connect(&path)?;
+
+        let timeout = Duration::from_millis(EXTERNAL_LAYOUT_TIMEOUT.load(Ordering::SeqCst));
+        let deadline = Instant::now() + timeout;
+
+        stream.set_write_timeout(Option::from(timeout))?;
+        stream.write_all(serde_json::to_string(request)?.as_bytes())?;
+        stream.shutdown(Shutdown::Write)?;
+
+        let mut response = vec![];
+        let mut buffer = [0; 4096];
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                bail!("timed out after {}ms", timeout.as_millis());
+            }
+
+            stream.set_read_timeout(Option::from(remaining))?;
+
+            match stream.read(&mut buffer)? {
+                0 => break,
+                read => response.extend_from_slice(&buffer[..read]),
+            }
+        }
+
+        let layouts: Vec<Rect> = serde_json::from_slice(&response)?;
+
+        if layouts.len() != request.container_count {
+            bail!(
+                "expected {} rects but received {}",
+                request.container_count,
+                layouts.len()
+            );
+        }
+
+        Ok(layouts)
+    }
+}
+
+impl Arrangement for ExternalLayout {
+    #[allow(clippy::too_many_arguments)]
+    fn calculate(
+        &self,
+        area: &Rect,
+        len: NonZeroUsize,
+        container_padding: Option<i32>,
+        layout_flip: Option<Axis>,
+        resize_dimensions: &[Option<RelativeRect>],
+        focused_idx: usize,
+        layout_options: Option<LayoutOptions>,
+        latest_layout: &[Rect],
+    ) -> Vec<Rect> {
+        let request = ExternalLayoutRequest {
+            layout: self.name.clone(),
+            work_area: *area,
+            container_count: len.get(),
+            focused_idx,
+            resize_dimensions: resize_dimensions
+                .iter()
+                .map(|resize| resize.map(|resize| resize.to_rect(area)))
+                .collect(),
+            layout_flip,
+        };
+
+        let layouts = {
+            let mut responses = RESPONSES.lock();
+            let response = responses.entry((self.name.clone(), *area)).or_default();
+
+            if response.request.as_ref() != Some(&request) {
+                response.request = Option::from(request.clone());
+                response.failed = false;
+
+                if event_tx().try_send(Notification(request)).is_err() {
+                    tracing::warn!("channel is full; dropping notification");
+                    response.request = None;
+                }
+            }
+
+            if !response.failed && response.layouts.len() == len.get() {
+                Option::from(response.layouts.clone())
+            } else {
+                None
+            }
+        };
+
+        match layouts {
+            Some(mut layouts) => {
+                layouts
+                    .iter_mut()
+                    .for_each(|l| l.add_padding(container_padding.unwrap_or_default()));
+
+                layouts
+            }
+            None => DefaultLayout::BSP.calculate(
+                area,
+                len,
+                container_padding,
+                layout_flip,
+                resize_dimensions,
+                focused_idx,
+                layout_options,
+                latest_layout,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(windows))]
+    use std::os::unix::net::UnixListener;
+    #[cfg(windows)]
+    use uds_windows::UnixListener;
+
+    fn request(layout: &str) -> ExternalLayoutRequest {
+        ExternalLayoutRequest {
+            layout: layout.to_string(),
+            work_area: Rect {
+                left: 0,
+                top: 0,
+                right: 1500,
+                bottom: 1000,
+            },
+            container_count: 3,
+            focused_idx: 0,
+            resize_dimensions: vec![None, None, None],
+            layout_flip: None,
+        }
+    }
+
+    #[test]
+    fn request_is_answered_by_an_external_process() -> Result<()> {
+        let socket = std::env::temp_dir().join("komorebi-external-layout-test.sock");
+        let _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket)?;
+
+        EXTERNAL_LAYOUTS
+            .lock()
+            .insert(String::from("test-columns"), socket.clone());
+
+        // A stand-in for an external layout process, which arranges containers in equal columns
+        let process = std::thread::spawn(move || -> Result<()> {
+            let (mut stream, _) = listener.accept()?;
+            let mut buffer = String::new();
+            stream.read_to_string(&mut buffer)?;
+
+            let request: ExternalLayoutRequest = serde_json::from_str(&buffer)?;
+            let count = i32::try_from(request.container_count)?;
+            let width = request.work_area.right / count;
+
+            let layouts = (0..count)
+                .map(|i| Rect {
+                    left: request.work_area.left + width * i,
+                    top: request.work_area.top,
+                    right: width,
+                    bottom: request.work_area.bottom,
+                })
+                .collect::<Vec<_>>();
+
+            stream.write_all(serde_json::to_string(&layouts)?.as_bytes())?;
+
+            Ok(())
+        });
+
+        let layouts =
+            ExternalLayout::from(String::from("test-columns")).request(&request("test-columns"));
+
+        process
+            .join()
+            .map_err(|_| anyhow!("the layout process panicked"))??;
+
+        EXTERNAL_LAYOUTS.lock().remove("test-columns");
+        let _ = std::fs::remove_file(&socket);
+
+        let lefts = layouts?.iter().map(|rect| rect.left).collect::<Vec<_>>();
+        assert_eq!(lefts, vec![0, 500, 1000]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_layouts_stay_registered() {
+        let socket = std::env::temp_dir().join("komorebi-external-layout-missing.sock");
+        let _ = std::fs::remove_file(&socket);
+
+        EXTERNAL_LAYOUTS
+            .lock()
+            .insert(String::from("test-missing"), socket);
+
+        let layout = ExternalLayout::from(String::from("test-missing"));
+        assert!(layout.request(&request("test-missing")).is_err());
+        assert!(EXTERNAL_LAYOUTS.lock().contains_key("test-missing"));
+
+        EXTERNAL_LAYOUTS.lock().remove("test-missing");
+    }
+}
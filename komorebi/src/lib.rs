@@ -3,20 +3,28 @@
 pub mod animation;
 pub mod animation_manager;
 pub mod border_manager;
+#[cfg(windows)]
 pub mod com;
 #[macro_use]
 pub mod ring;
 pub mod colour;
 pub mod container;
 pub mod core;
+pub mod external_layout;
 pub mod focus_manager;
 pub mod monitor;
 pub mod monitor_reconciliator;
+pub mod platform;
 pub mod process_command;
 pub mod process_event;
+#[cfg(windows)]
 pub mod process_movement;
 pub mod reaper;
+pub mod rule_evaluator;
+pub mod rule_index;
+#[cfg(windows)]
 pub mod set_window_position;
+pub mod simulation;
 pub mod stackbar_manager;
 pub mod static_config;
 pub mod styles;
@@ -24,25 +32,32 @@ pub mod transparency_manager;
 pub mod window;
 pub mod window_manager;
 pub mod window_manager_event;
+#[cfg(windows)]
 pub mod windows_api;
+#[cfg(windows)]
 pub mod windows_callbacks;
 pub mod winevent;
 pub mod winevent_listener;
 pub mod workspace;
 pub mod workspace_reconciliator;
+pub mod workspace_transition;
 
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fs::File;
 use std::io::Write;
 use std::net::TcpStream;
+#[cfg(not(windows))]
+use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::AtomicU64;
+#[cfg(windows)]
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
@@ -56,13 +71,16 @@ pub use static_config::*;
 pub use window::*;
 pub use window_manager::*;
 pub use window_manager_event::*;
+#[cfg(windows)]
 pub use windows_api::WindowsApi;
+#[cfg(windows)]
 pub use windows_api::*;
 
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
 use crate::core::config_generation::WorkspaceMatchingRule;
+use crate::rule_index::RuleList;
 use color_eyre::Result;
 use os_info::Version;
 use parking_lot::Mutex;
@@ -70,22 +88,25 @@ use regex::Regex;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(windows)]
 use uds_windows::UnixStream;
 use which::which;
+#[cfg(windows)]
 use winreg::enums::HKEY_CURRENT_USER;
+#[cfg(windows)]
 use winreg::RegKey;
 
 lazy_static! {
     static ref HIDDEN_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
-    static ref LAYERED_WHITELIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+    static ref LAYERED_WHITELIST: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::from(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
             id: String::from("steam.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
-    ]));
-    static ref TRAY_AND_MULTI_WINDOW_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> =
-        Arc::new(Mutex::new(vec![
+    ])));
+    static ref TRAY_AND_MULTI_WINDOW_IDENTIFIERS: Arc<Mutex<RuleList>> =
+        Arc::new(Mutex::new(RuleList::from(vec![
             MatchingRule::Simple(IdWithIdentifier {
                 kind: ApplicationIdentifier::Exe,
                 id: String::from("explorer.exe"),
@@ -116,8 +137,8 @@ lazy_static! {
                 id: String::from("steam.exe"),
                 matching_strategy: Option::from(MatchingStrategy::Equals),
             })
-        ]));
-    static ref OBJECT_NAME_CHANGE_ON_LAUNCH: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+        ])));
+    static ref OBJECT_NAME_CHANGE_ON_LAUNCH: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::from(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
             id: String::from("firefox.exe"),
@@ -128,8 +149,8 @@ lazy_static! {
             id: String::from("idea64.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
-    ]));
-    static ref TRANSPARENCY_BLACKLIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    ])));
+    static ref TRANSPARENCY_BLACKLIST: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::default()));
     static ref MONITOR_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, Rect>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref DISPLAY_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, String>>> =
@@ -138,8 +159,8 @@ lazy_static! {
         Arc::new(Mutex::new(Vec::new()));
     static ref REGEX_IDENTIFIERS: Arc<Mutex<HashMap<String, Regex>>> =
         Arc::new(Mutex::new(HashMap::new()));
-    static ref MANAGE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
-    static ref IGNORE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+    static ref MANAGE_IDENTIFIERS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::default()));
+    static ref IGNORE_IDENTIFIERS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::from(vec![
         // mstsc.exe creates these on Windows 11 when a WSL process is launched
         // https://github.com/LGUG2Z/komorebi/issues/74
         MatchingRule::Simple(IdWithIdentifier {
@@ -157,8 +178,16 @@ lazy_static! {
             id: String::from("komorebi-bar.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         })
-    ]));
-    static ref FLOATING_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    ])));
+    static ref FLOATING_APPLICATIONS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::default()));
+    static ref WINDOW_SIZE_CONSTRAINTS: Arc<Mutex<Vec<WindowSizeConstraintRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref WINDOW_SIZE_CONSTRAINT_IDENTIFIERS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::default()));
+    static ref WINDOW_SIZE_CONSTRAINTS_BY_HWND: Arc<Mutex<HashMap<isize, Option<WindowSizeConstraints>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref FLOATING_PLACEMENT_RULES: Arc<Mutex<Vec<FloatingPlacementRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref FLOATING_PLACEMENT_IDENTIFIERS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::default()));
+    static ref MANAGE_ACTION_RULES: Arc<Mutex<Vec<ManageActionRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref MANAGE_ACTION_IDENTIFIERS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::default()));
     static ref PERMAIGNORE_CLASSES: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![
         "Chrome_RenderWidgetHostHWND".to_string(),
     ]));
@@ -166,13 +195,13 @@ lazy_static! {
         "X410.exe".to_string(),
         "vcxsrv.exe".to_string(),
     ]));
-    static ref SLOW_APPLICATION_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+    static ref SLOW_APPLICATION_IDENTIFIERS: Arc<Mutex<RuleList>> = Arc::new(Mutex::new(RuleList::from(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
             id: String::from("firefox.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
-    ]));
+    ])));
     static ref SUBSCRIPTION_PIPES: Arc<Mutex<HashMap<String, File>>> =
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref SUBSCRIPTION_SOCKETS: Arc<Mutex<HashMap<String, PathBuf>>> =
@@ -215,6 +244,10 @@ lazy_static! {
 
     static ref ANIMATION_STYLE: Arc<Mutex<AnimationStyle >> =
         Arc::new(Mutex::new(AnimationStyle::Linear));
+    static ref ANIMATION_OVERRIDES: Arc<Mutex<HashMap<AnimationOperation, AnimationOverride>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref ANIMATION_OPERATION: Arc<Mutex<AnimationOperation>> =
+        Arc::new(Mutex::new(AnimationOperation::default()));
 
     static ref ANIMATION_MANAGER: Arc<Mutex<AnimationManager>> =
         Arc::new(Mutex::new(AnimationManager::new()));
@@ -222,13 +255,21 @@ lazy_static! {
     // Use app-specific titlebar removal options where possible
     // eg. Windows Terminal, IntelliJ IDEA, Firefox
     static ref NO_TITLEBAR: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    // Windows which had their title bar removed by a manage action, which is kept removed even
+    // when title bars are not toggled off
+    static ref MANAGE_ACTION_NO_TITLEBAR: Arc<Mutex<HashSet<isize>>> =
+        Arc::new(Mutex::new(HashSet::new()));
 
     static ref WINDOWS_BY_BAR_HWNDS: Arc<Mutex<HashMap<isize, VecDeque<isize>>>> =
         Arc::new(Mutex::new(HashMap::new()));
+
+    static ref DEFAULT_INNER_GAP: Arc<Mutex<Option<i32>>> = Arc::new(Mutex::new(None));
+    static ref DEFAULT_OUTER_GAP: Arc<Mutex<Option<SidePadding>>> = Arc::new(Mutex::new(None));
 }
 
 pub static DEFAULT_WORKSPACE_PADDING: AtomicI32 = AtomicI32::new(10);
 pub static DEFAULT_CONTAINER_PADDING: AtomicI32 = AtomicI32::new(10);
+pub static DEFAULT_SMART_GAPS: AtomicBool = AtomicBool::new(false);
 
 pub static INITIAL_CONFIGURATION_LOADED: AtomicBool = AtomicBool::new(false);
 pub static CUSTOM_FFM: AtomicBool = AtomicBool::new(false);
@@ -240,6 +281,7 @@ pub static ANIMATION_DURATION: AtomicU64 = AtomicU64::new(250);
 
 pub static SLOW_APPLICATION_COMPENSATION_TIME: AtomicU64 = AtomicU64::new(20);
 
+#[cfg(windows)]
 #[must_use]
 pub fn current_virtual_desktop() -> Option<Vec<u8>> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
@@ -284,6 +326,14 @@ pub fn current_virtual_desktop() -> Option<Vec<u8>> {
     current
 }
 
+/// Virtual desktops are only tracked in the Windows registry, so without it the virtual desktop
+/// validation never runs
+#[cfg(not(windows))]
+#[must_use]
+pub fn current_virtual_desktop() -> Option<Vec<u8>> {
+    None
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum NotificationEvent {
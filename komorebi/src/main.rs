@@ -28,6 +28,7 @@ use tracing_subscriber::EnvFilter;
 use uds_windows::UnixStream;
 
 use komorebi::border_manager;
+use komorebi::external_layout;
 use komorebi::focus_manager;
 use komorebi::load_configuration;
 use komorebi::monitor_reconciliator;
@@ -36,6 +37,7 @@ use komorebi::process_command::listen_for_commands_tcp;
 use komorebi::process_event::listen_for_events;
 use komorebi::process_movement::listen_for_movements;
 use komorebi::reaper;
+use komorebi::simulation;
 use komorebi::stackbar_manager;
 use komorebi::static_config::StaticConfig;
 use komorebi::transparency_manager;
@@ -152,6 +154,9 @@ struct Opts {
     /// Path to a static configuration JSON file
     #[clap(short, long)]
     config: Option<PathBuf>,
+    /// Replay a simulation script against virtual monitors, print the resulting report and exit
+    #[clap(long)]
+    simulate: Option<PathBuf>,
 }
 
 #[tracing::instrument]
@@ -160,6 +165,21 @@ fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
     CUSTOM_FFM.store(opts.focus_follows_mouse, Ordering::SeqCst);
 
+    if let Some(script) = &opts.simulate {
+        let report = simulation::run_file(script)?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+
+        for error in &report.errors {
+            eprintln!("step {}: error: {}", error.step, error.message);
+        }
+
+        for failure in &report.failures {
+            eprintln!("step {}: {}", failure.step, failure.message);
+        }
+
+        std::process::exit(i32::from(!report.passed()));
+    }
+
     let process_id = WindowsApi::current_process_id();
     WindowsApi::allow_set_foreground_window(process_id)?;
     WindowsApi::set_process_dpi_awareness_context()?;
@@ -271,6 +291,7 @@ fn main() -> Result<()> {
     monitor_reconciliator::listen_for_notifications(wm.clone())?;
     reaper::watch_for_orphans(wm.clone());
     focus_manager::listen_for_notifications(wm.clone());
+    external_layout::listen_for_notifications(wm.clone());
 
     let (ctrlc_sender, ctrlc_receiver) = crossbeam_channel::bounded(1);
     ctrlc::set_handler(move || {
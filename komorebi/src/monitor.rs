@@ -13,14 +13,17 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::core::Rect;
+use crate::core::WorkspaceTransition;
 
+use crate::animation::AnimationSettings;
 use crate::container::Container;
+use crate::platform;
 use crate::ring::Ring;
 use crate::workspace::Workspace;
+use crate::workspace_transition::Transition;
 use crate::DefaultLayout;
 use crate::Layout;
 use crate::OperationDirection;
-use crate::WindowsApi;
 
 #[derive(
     Debug,
@@ -120,6 +123,41 @@ impl Monitor {
         Ok(())
     }
 
+    /// Load the focused workspace, transitioning to it from the workspace which was previously
+    /// focused on this monitor
+    pub fn load_focused_workspace_with_transition(
+        &mut self,
+        previous_idx: usize,
+        transition: WorkspaceTransition,
+        settings: AnimationSettings,
+        mouse_follows_focus: bool,
+    ) -> Result<()> {
+        let focused_idx = self.focused_workspace_idx();
+        let mut workspace_transition =
+            Transition::new(transition, self.size, focused_idx > previous_idx, settings);
+
+        let workspaces = self.workspaces();
+        if let (Some(previous), Some(focused)) =
+            (workspaces.get(previous_idx), workspaces.get(focused_idx))
+        {
+            let mut outgoing = previous.shown_tiled_windows();
+            outgoing.extend(previous.floating_windows());
+
+            workspace_transition.prepare(
+                &outgoing,
+                *previous.maximized_window(),
+                &focused.shown_tiled_windows(),
+                focused.floating_windows(),
+                *focused.maximized_window(),
+            );
+        }
+
+        self.load_focused_workspace(mouse_follows_focus)?;
+        workspace_transition.start();
+
+        Ok(())
+    }
+
     pub fn add_container(
         &mut self,
         container: Container,
@@ -179,7 +217,7 @@ impl Monitor {
             bail!("cannot move native maximized window to another monitor or workspace");
         }
 
-        let foreground_hwnd = WindowsApi::foreground_window()?;
+        let foreground_hwnd = platform::current().foreground_window()?;
         let floating_window_index = workspace
             .floating_windows()
             .iter()
@@ -232,18 +270,21 @@ impl Monitor {
                             target_workspace.add_container_to_back(container);
                         }
                     },
-                    Layout::Custom(_) => {
+                    Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                         target_workspace.add_container_to_back(container);
                     }
                 },
                 Some(OperationDirection::Right) => match target_workspace.layout() {
                     Layout::Default(layout) => {
-                        let target_index =
-                            layout.leftmost_index(target_workspace.containers().len());
+                        let target_index = layout.leftmost_index(
+                            target_workspace.containers().len(),
+                            target_workspace.layout_options(),
+                        );
 
                         match layout {
                             DefaultLayout::RightMainVerticalStack
-                            | DefaultLayout::UltrawideVerticalStack => {
+                            | DefaultLayout::UltrawideVerticalStack
+                            | DefaultLayout::CenteredMaster => {
                                 if target_workspace.containers().len() == 1 {
                                     target_workspace.add_container_to_back(container);
                                 } else {
@@ -256,7 +297,7 @@ impl Monitor {
                             }
                         }
                     }
-                    Layout::Custom(_) => {
+                    Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                         target_workspace.add_container_to_front(container);
                     }
                 },
@@ -305,6 +346,71 @@ impl Monitor {
         self.workspaces().len()
     }
 
+    /// Set the layout of every workspace with monitor layout rules to the layout of the first
+    /// rule which matches this monitor, or back to the layout it had before any rule was applied
+    /// if none of them match. Layouts are only changed when the matching rule changes, so that a
+    /// layout picked by hand is kept until the monitor changes
+    pub fn apply_monitor_layout_rules(&mut self) {
+        let name = self.name().clone();
+        let size = *self.size();
+        let work_area = *self.work_area_size();
+
+        for workspace in self.workspaces_mut() {
+            let matching_rule = workspace
+                .monitor_layout_rules()
+                .iter()
+                .find(|rule| rule.matches(&name, &size, &work_area))
+                .cloned();
+
+            if matching_rule == *workspace.applied_monitor_layout_rule() {
+                continue;
+            }
+
+            match &matching_rule {
+                Some(rule) => {
+                    if workspace.applied_monitor_layout_rule().is_none() {
+                        workspace.set_layout_before_monitor_layout_rule(Option::from(
+                            workspace.layout().clone(),
+                        ));
+                    }
+
+                    tracing::info!("applying monitor layout rule for {name}: {}", rule.layout);
+                    workspace.set_layout(Layout::Default(rule.layout));
+                }
+                None => {
+                    if let Some(layout) = workspace.layout_before_monitor_layout_rule().clone() {
+                        tracing::info!("no monitor layout rule matches {name}, restoring layout");
+                        workspace.set_layout(layout);
+                    }
+
+                    workspace.set_layout_before_monitor_layout_rule(None);
+                }
+            }
+
+            workspace.set_applied_monitor_layout_rule(matching_rule);
+        }
+    }
+
+    /// The area that the layout of the focused workspace is calculated in
+    pub fn focused_workspace_work_area(&self, offset: Option<Rect>) -> Result<Rect> {
+        let work_area = *self.work_area_size();
+        let window_based_work_area_offset = (
+            self.window_based_work_area_offset_limit(),
+            self.window_based_work_area_offset(),
+        );
+
+        let offset = if self.work_area_offset().is_some() {
+            self.work_area_offset()
+        } else {
+            offset
+        };
+
+        Ok(self
+            .focused_workspace()
+            .ok_or_else(|| anyhow!("there is no workspace"))?
+            .adjusted_work_area(&work_area, offset, window_based_work_area_offset))
+    }
+
     pub fn update_focused_workspace(&mut self, offset: Option<Rect>) -> Result<()> {
         let work_area = *self.work_area_size();
         let window_based_work_area_offset = (
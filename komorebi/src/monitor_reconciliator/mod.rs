@@ -1,13 +1,16 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
+use crate::animation::AnimationOperationScope;
 use crate::border_manager;
-use crate::core::Rect;
+use crate::core::AnimationOperation;
 use crate::monitor;
 use crate::monitor::Monitor;
+#[cfg(windows)]
 use crate::monitor_reconciliator::hidden::Hidden;
+use crate::platform;
+use crate::platform::MonitorInfo;
 use crate::MonitorConfig;
 use crate::WindowManager;
-use crate::WindowsApi;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicConsume;
@@ -18,6 +21,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
+#[cfg(windows)]
 pub mod hidden;
 
 pub enum Notification {
@@ -63,36 +67,32 @@ pub fn insert_in_monitor_cache(device_id: &str, config: MonitorConfig) {
 }
 
 pub fn attached_display_devices() -> color_eyre::Result<Vec<Monitor>> {
-    Ok(win32_display_data::connected_displays_all()
-        .flatten()
+    Ok(platform::current()
+        .monitors()?
+        .into_iter()
         .map(|display| {
-            let path = display.device_path;
-
-            let (device, device_id) = if path.is_empty() {
-                (String::from("UNKNOWN"), String::from("UNKNOWN"))
-            } else {
-                let mut split: Vec<_> = path.split('#').collect();
-                split.remove(0);
-                split.remove(split.len() - 1);
-                let device = split[0].to_string();
-                let device_id = split.join("-");
-                (device, device_id)
-            };
-
-            let name = display.device_name.trim_start_matches(r"\\.\").to_string();
-            let name = name.split('\\').collect::<Vec<_>>()[0].to_string();
-
             monitor::new(
-                display.hmonitor,
-                display.size.into(),
-                display.work_area_size.into(),
-                name,
-                device,
-                device_id,
+                display.id,
+                display.size,
+                display.work_area_size,
+                display.name,
+                display.device,
+                display.device_id,
             )
         })
         .collect::<Vec<_>>())
 }
+
+/// The current size and work area of a monitor as reported by the platform backend
+fn reference_monitor(id: isize) -> Option<MonitorInfo> {
+    platform::current()
+        .monitors()
+        .ok()?
+        .into_iter()
+        .find(|display| display.id == id)
+}
+
+#[cfg(windows)]
 pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
     #[allow(clippy::expect_used)]
     Hidden::create("komorebi-hidden")?;
@@ -138,6 +138,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         }
 
         let mut wm = wm.lock();
+        let _animation_scope = AnimationOperationScope::new(AnimationOperation::DisplayChange);
 
         match notification {
             Notification::EnteringSuspendedState | Notification::SessionLocked => {
@@ -156,14 +157,9 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     let mut should_update = false;
 
                     // Update work areas as necessary
-                    if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
-                        if reference.work_area_size() != monitor.work_area_size() {
-                            monitor.set_work_area_size(Rect {
-                                left: reference.work_area_size().left,
-                                top: reference.work_area_size().top,
-                                right: reference.work_area_size().right,
-                                bottom: reference.work_area_size().bottom,
-                            });
+                    if let Some(reference) = reference_monitor(monitor.id()) {
+                        if reference.work_area_size != *monitor.work_area_size() {
+                            monitor.set_work_area_size(reference.work_area_size);
 
                             should_update = true;
                         }
@@ -171,6 +167,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
 
                     if should_update {
                         tracing::info!("updated work area for {}", monitor.device_id());
+                        monitor.apply_monitor_layout_rules();
                         monitor.update_focused_workspace(offset)?;
                         border_manager::send_notification(None);
                     } else {
@@ -188,25 +185,15 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     let mut should_update = false;
 
                     // Update sizes and work areas as necessary
-                    if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
-                        if reference.work_area_size() != monitor.work_area_size() {
-                            monitor.set_work_area_size(Rect {
-                                left: reference.work_area_size().left,
-                                top: reference.work_area_size().top,
-                                right: reference.work_area_size().right,
-                                bottom: reference.work_area_size().bottom,
-                            });
+                    if let Some(reference) = reference_monitor(monitor.id()) {
+                        if reference.work_area_size != *monitor.work_area_size() {
+                            monitor.set_work_area_size(reference.work_area_size);
 
                             should_update = true;
                         }
 
-                        if reference.size() != monitor.size() {
-                            monitor.set_size(Rect {
-                                left: reference.size().left,
-                                top: reference.size().top,
-                                right: reference.size().right,
-                                bottom: reference.size().bottom,
-                            });
+                        if reference.size != *monitor.size() {
+                            monitor.set_size(reference.size);
 
                             should_update = true;
                         }
@@ -218,6 +205,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             monitor.device_id()
                         );
 
+                        monitor.apply_monitor_layout_rules();
                         monitor.update_focused_workspace(offset)?;
                         border_manager::send_notification(None);
                     } else {
@@ -352,7 +340,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
 
                 // Check for and add any new monitors that may have been plugged in
                 // Monitor and display index preferences get applied in this function
-                WindowsApi::load_monitor_information(&mut wm.monitors)?;
+                platform::load_monitor_information(&mut wm.monitors)?;
 
                 let post_addition_monitor_count = wm.monitors().len();
 
@@ -402,6 +390,10 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 let final_count = wm.monitors().len();
 
                 if post_removal_monitor_count != final_count {
+                    for monitor in wm.monitors_mut() {
+                        monitor.apply_monitor_layout_rules();
+                    }
+
                     wm.retile_all(true)?;
                     // Second retile to fix DPI/resolution related jank
                     wm.retile_all(true)?;
@@ -0,0 +1,893 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::Result;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+#[cfg(windows)]
+use windows::Win32::Foundation::HWND;
+#[cfg(windows)]
+use windows::Win32::Foundation::POINT;
+
+#[cfg(windows)]
+use crate::com::SetCloak;
+use crate::core::Rect;
+use crate::monitor;
+use crate::monitor::Monitor;
+use crate::ring::Ring;
+use crate::styles::ExtendedWindowStyle;
+use crate::styles::WindowStyle;
+#[cfg(windows)]
+use crate::windows_api::as_ptr;
+#[cfg(windows)]
+use crate::windows_api::WindowsApi;
+use crate::DISPLAY_INDEX_PREFERENCES;
+use crate::MONITOR_INDEX_PREFERENCES;
+
+#[cfg(windows)]
+lazy_static! {
+    static ref PLATFORM: RwLock<Arc<dyn Platform>> = RwLock::new(Arc::new(WindowsApi));
+}
+
+#[cfg(not(windows))]
+lazy_static! {
+    static ref PLATFORM: RwLock<Arc<dyn Platform>> = RwLock::new(Arc::new(FakePlatform::new()));
+}
+
+/// Returns the platform backend currently used by the window manager
+pub fn current() -> Arc<dyn Platform> {
+    PLATFORM.read().clone()
+}
+
+/// Replaces the platform backend used by the window manager and returns the previous one, this
+/// should be called before the `WindowManager` is initialized
+pub fn set_current(platform: Arc<dyn Platform>) -> Arc<dyn Platform> {
+    std::mem::replace(&mut *PLATFORM.write(), platform)
+}
+
+/// Adds any monitors reported by the current platform backend which are not already known
+pub fn load_monitor_information(monitors: &mut Ring<Monitor>) -> Result<()> {
+    'read: for display in current().monitors()? {
+        for monitor in monitors.elements() {
+            if display.device_id.eq(monitor.device_id()) {
+                continue 'read;
+            }
+        }
+
+        let m = monitor::new(
+            display.id,
+            display.size,
+            display.work_area_size,
+            display.name,
+            display.device,
+            display.device_id,
+        );
+
+        let mut index_preference = None;
+        let monitor_index_preferences = MONITOR_INDEX_PREFERENCES.lock();
+        for (index, monitor_size) in &*monitor_index_preferences {
+            if m.size() == monitor_size {
+                index_preference = Option::from(index);
+            }
+        }
+
+        let display_index_preferences = DISPLAY_INDEX_PREFERENCES.lock();
+        for (index, id) in &*display_index_preferences {
+            if id.eq(m.device_id()) {
+                index_preference = Option::from(index);
+            }
+        }
+
+        if monitors.elements().is_empty() {
+            monitors.elements_mut().push_back(m);
+        } else if let Some(preference) = index_preference {
+            while *preference > monitors.elements().len() {
+                monitors.elements_mut().push_back(Monitor::placeholder());
+            }
+
+            monitors.elements_mut().insert(*preference, m);
+        } else {
+            monitors.elements_mut().push_back(m);
+        }
+    }
+
+    monitors
+        .elements_mut()
+        .retain(|m| m.name().ne("PLACEHOLDER"));
+
+    Ok(())
+}
+
+/// A display as reported by the platform backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorInfo {
+    pub id: isize,
+    pub name: String,
+    pub device: String,
+    pub device_id: String,
+    pub size: Rect,
+    pub work_area_size: Rect,
+}
+
+/// The operations that the window manager needs from the underlying desktop
+///
+/// `WindowsApi` is the implementation used at runtime, `FakePlatform` is an in-memory
+/// implementation which allows the window manager to be driven without a desktop session
+pub trait Platform: Send + Sync {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>>;
+    fn window_rect(&self, hwnd: isize) -> Result<Rect>;
+    fn position_window(&self, hwnd: isize, layout: &Rect, top: bool) -> Result<()>;
+    fn hide_window(&self, hwnd: isize);
+    fn minimize_window(&self, hwnd: isize);
+    fn restore_window(&self, hwnd: isize);
+    fn cloak_window(&self, hwnd: isize, cloak: bool);
+    fn is_window_cloaked(&self, hwnd: isize) -> Result<bool>;
+    fn is_window(&self, hwnd: isize) -> bool;
+    fn is_window_visible(&self, hwnd: isize) -> bool;
+    fn is_iconic(&self, hwnd: isize) -> bool;
+    fn is_zoomed(&self, hwnd: isize) -> bool;
+    fn window_style(&self, hwnd: isize) -> Result<WindowStyle>;
+    fn window_ex_style(&self, hwnd: isize) -> Result<ExtendedWindowStyle>;
+    fn foreground_window(&self) -> Result<isize>;
+    fn raise_and_focus_window(&self, hwnd: isize) -> Result<()>;
+    fn center_cursor_in_rect(&self, rect: &Rect) -> Result<()>;
+    fn window_title(&self, hwnd: isize) -> Result<String>;
+    fn window_exe(&self, hwnd: isize) -> Result<String>;
+    fn window_path(&self, hwnd: isize) -> Result<String>;
+    fn window_class(&self, hwnd: isize) -> Result<String>;
+    /// Moves a window without raising it, this is used for the intermediate frames of animations
+    fn move_window(&self, hwnd: isize, layout: &Rect) -> Result<()>;
+    fn close_window(&self, hwnd: isize) -> Result<()>;
+    fn maximize_window(&self, hwnd: isize);
+    fn unmaximize_window(&self, hwnd: isize);
+    fn bring_window_to_top(&self, hwnd: isize) -> Result<()>;
+    fn set_window_style(&self, hwnd: isize, style: WindowStyle) -> Result<()>;
+    fn set_window_ex_style(&self, hwnd: isize, style: ExtendedWindowStyle) -> Result<()>;
+    fn set_transparent(&self, hwnd: isize, alpha: u8) -> Result<()>;
+    fn set_window_accent(&self, hwnd: isize, colour: Option<u32>) -> Result<()>;
+    fn process_id(&self, hwnd: isize) -> u32;
+    /// Windows which would be shown in the alt-tab menu, topmost first
+    fn alt_tab_windows(&self) -> Result<Vec<isize>>;
+    fn desktop_window(&self) -> Result<isize>;
+    fn cursor_pos(&self) -> Result<(i32, i32)>;
+    fn left_click(&self);
+    fn window_at_cursor_pos(&self) -> Result<isize>;
+    fn monitor_from_window(&self, hwnd: isize) -> isize;
+    fn monitor_from_point(&self, point: (i32, i32)) -> isize;
+    fn monitors_have_same_dpi(&self, a: isize, b: isize) -> Result<bool>;
+    /// Adds the windows which are already open on each monitor to its first workspace
+    fn load_workspace_information(&self, monitors: &mut Ring<Monitor>) -> Result<()>;
+    /// Whether the system-wide focus follows mouse setting is enabled
+    fn focus_follows_mouse(&self) -> Result<bool>;
+    fn enable_focus_follows_mouse(&self) -> Result<()>;
+    fn disable_focus_follows_mouse(&self) -> Result<()>;
+}
+
+#[cfg(windows)]
+impl Platform for WindowsApi {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>> {
+        let mut monitors = vec![];
+
+        for display in win32_display_data::connected_displays_all().flatten() {
+            let path = display.device_path.clone();
+
+            let (device, device_id) = if path.is_empty() {
+                (String::from("UNKNOWN"), String::from("UNKNOWN"))
+            } else {
+                let mut split: Vec<_> = path.split('#').collect();
+                split.remove(0);
+                split.remove(split.len() - 1);
+                let device = split[0].to_string();
+                let device_id = split.join("-");
+                (device, device_id)
+            };
+
+            let name = display.device_name.trim_start_matches(r"\\.\").to_string();
+            let name = name.split('\\').collect::<Vec<_>>()[0].to_string();
+
+            monitors.push(MonitorInfo {
+                id: display.hmonitor,
+                name,
+                device,
+                device_id,
+                size: display.size.into(),
+                work_area_size: display.work_area_size.into(),
+            });
+        }
+
+        Ok(monitors)
+    }
+
+    fn window_rect(&self, hwnd: isize) -> Result<Rect> {
+        Self::window_rect(hwnd)
+    }
+
+    fn position_window(&self, hwnd: isize, layout: &Rect, top: bool) -> Result<()> {
+        Self::position_window(hwnd, layout, top)
+    }
+
+    fn hide_window(&self, hwnd: isize) {
+        Self::hide_window(hwnd);
+    }
+
+    fn minimize_window(&self, hwnd: isize) {
+        Self::minimize_window(hwnd);
+    }
+
+    fn restore_window(&self, hwnd: isize) {
+        Self::restore_window(hwnd);
+    }
+
+    fn cloak_window(&self, hwnd: isize, cloak: bool) {
+        SetCloak(HWND(as_ptr!(hwnd)), 1, if cloak { 2 } else { 0 });
+    }
+
+    fn is_window_cloaked(&self, hwnd: isize) -> Result<bool> {
+        Self::is_window_cloaked(hwnd)
+    }
+
+    fn is_window(&self, hwnd: isize) -> bool {
+        Self::is_window(hwnd)
+    }
+
+    fn is_window_visible(&self, hwnd: isize) -> bool {
+        Self::is_window_visible(hwnd)
+    }
+
+    fn is_iconic(&self, hwnd: isize) -> bool {
+        Self::is_iconic(hwnd)
+    }
+
+    fn is_zoomed(&self, hwnd: isize) -> bool {
+        Self::is_zoomed(hwnd)
+    }
+
+    fn window_style(&self, hwnd: isize) -> Result<WindowStyle> {
+        let bits = u32::try_from(Self::gwl_style(hwnd)?)?;
+        Ok(WindowStyle::from_bits_truncate(bits))
+    }
+
+    fn window_ex_style(&self, hwnd: isize) -> Result<ExtendedWindowStyle> {
+        let bits = u32::try_from(Self::gwl_ex_style(hwnd)?)?;
+        Ok(ExtendedWindowStyle::from_bits_truncate(bits))
+    }
+
+    fn foreground_window(&self) -> Result<isize> {
+        Self::foreground_window()
+    }
+
+    fn raise_and_focus_window(&self, hwnd: isize) -> Result<()> {
+        Self::raise_and_focus_window(hwnd)
+    }
+
+    fn center_cursor_in_rect(&self, rect: &Rect) -> Result<()> {
+        Self::center_cursor_in_rect(rect)
+    }
+
+    fn window_title(&self, hwnd: isize) -> Result<String> {
+        Self::window_text_w(hwnd)
+    }
+
+    fn window_exe(&self, hwnd: isize) -> Result<String> {
+        let (process_id, _) = Self::window_thread_process_id(hwnd);
+        let handle = Self::process_handle(process_id)?;
+        let exe = Self::exe(handle);
+        Self::close_process(handle)?;
+        exe
+    }
+
+    fn window_path(&self, hwnd: isize) -> Result<String> {
+        let (process_id, _) = Self::window_thread_process_id(hwnd);
+        let handle = Self::process_handle(process_id)?;
+        let path = Self::exe_path(handle);
+        Self::close_process(handle)?;
+        path
+    }
+
+    fn window_class(&self, hwnd: isize) -> Result<String> {
+        Self::real_window_class_w(hwnd)
+    }
+
+    fn move_window(&self, hwnd: isize, layout: &Rect) -> Result<()> {
+        // using MoveWindow because it runs faster than SetWindowPos
+        Self::move_window(hwnd, layout, false)?;
+        Self::invalidate_rect(hwnd, None, false);
+        Ok(())
+    }
+
+    fn close_window(&self, hwnd: isize) -> Result<()> {
+        Self::close_window(hwnd)
+    }
+
+    fn maximize_window(&self, hwnd: isize) {
+        Self::maximize_window(hwnd);
+    }
+
+    fn unmaximize_window(&self, hwnd: isize) {
+        Self::unmaximize_window(hwnd);
+    }
+
+    fn bring_window_to_top(&self, hwnd: isize) -> Result<()> {
+        Self::bring_window_to_top(hwnd)
+    }
+
+    fn set_window_style(&self, hwnd: isize, style: WindowStyle) -> Result<()> {
+        Self::update_style(hwnd, style.bits().try_into()?)
+    }
+
+    fn set_window_ex_style(&self, hwnd: isize, style: ExtendedWindowStyle) -> Result<()> {
+        Self::update_ex_style(hwnd, style.bits().try_into()?)
+    }
+
+    fn set_transparent(&self, hwnd: isize, alpha: u8) -> Result<()> {
+        Self::set_transparent(hwnd, alpha)
+    }
+
+    fn set_window_accent(&self, hwnd: isize, colour: Option<u32>) -> Result<()> {
+        Self::set_window_accent(hwnd, colour)
+    }
+
+    fn process_id(&self, hwnd: isize) -> u32 {
+        let (process_id, _) = Self::window_thread_process_id(hwnd);
+        process_id
+    }
+
+    fn alt_tab_windows(&self) -> Result<Vec<isize>> {
+        Ok(Self::alt_tab_windows()?
+            .into_iter()
+            .map(|window| window.hwnd)
+            .collect())
+    }
+
+    fn desktop_window(&self) -> Result<isize> {
+        Self::desktop_window()
+    }
+
+    fn cursor_pos(&self) -> Result<(i32, i32)> {
+        let point = Self::cursor_pos()?;
+        Ok((point.x, point.y))
+    }
+
+    fn left_click(&self) {
+        Self::left_click();
+    }
+
+    fn window_at_cursor_pos(&self) -> Result<isize> {
+        Self::window_at_cursor_pos()
+    }
+
+    fn monitor_from_window(&self, hwnd: isize) -> isize {
+        Self::monitor_from_window(hwnd)
+    }
+
+    fn monitor_from_point(&self, point: (i32, i32)) -> isize {
+        Self::monitor_from_point(POINT {
+            x: point.0,
+            y: point.1,
+        })
+    }
+
+    fn monitors_have_same_dpi(&self, a: isize, b: isize) -> Result<bool> {
+        Self::monitors_have_same_dpi(a, b)
+    }
+
+    fn load_workspace_information(&self, monitors: &mut Ring<Monitor>) -> Result<()> {
+        Self::load_workspace_information(monitors)
+    }
+
+    fn focus_follows_mouse(&self) -> Result<bool> {
+        Self::focus_follows_mouse()
+    }
+
+    fn enable_focus_follows_mouse(&self) -> Result<()> {
+        Self::enable_focus_follows_mouse()
+    }
+
+    fn disable_focus_follows_mouse(&self) -> Result<()> {
+        Self::disable_focus_follows_mouse()
+    }
+}
+
+/// A window tracked by `FakePlatform`
+#[derive(Debug, Clone, Default)]
+pub struct FakeWindow {
+    pub title: String,
+    pub exe: String,
+    pub path: String,
+    pub class: String,
+    pub rect: Rect,
+    pub style: WindowStyle,
+    pub ex_style: ExtendedWindowStyle,
+    pub visible: bool,
+    pub minimized: bool,
+    pub maximized: bool,
+    pub cloaked: bool,
+    pub alpha: Option<u8>,
+    pub accent: Option<u32>,
+    pub process_id: u32,
+}
+
+impl FakeWindow {
+    pub fn new(title: &str, exe: &str, class: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            exe: exe.to_string(),
+            path: format!(r"C:\Program Files\{exe}"),
+            class: class.to_string(),
+            // the minimum set of styles for a window to be considered eligible for management
+            style: WindowStyle::CAPTION | WindowStyle::VISIBLE,
+            ex_style: ExtendedWindowStyle::WINDOWEDGE,
+            visible: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FakeDesktop {
+    monitors: Vec<MonitorInfo>,
+    windows: HashMap<isize, FakeWindow>,
+    foreground: isize,
+    cursor: (i32, i32),
+    focus_follows_mouse: bool,
+}
+
+/// An in-memory desktop which records every operation performed on it
+#[derive(Debug, Default)]
+pub struct FakePlatform {
+    desktop: Mutex<FakeDesktop>,
+}
+
+impl FakePlatform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_monitor(&self, monitor: MonitorInfo) {
+        self.desktop.lock().monitors.push(monitor);
+    }
+
+    pub fn remove_monitor(&self, id: isize) {
+        self.desktop.lock().monitors.retain(|m| m.id != id);
+    }
+
+    pub fn add_window(&self, hwnd: isize, window: FakeWindow) {
+        self.desktop.lock().windows.insert(hwnd, window);
+    }
+
+    pub fn remove_window(&self, hwnd: isize) -> Option<FakeWindow> {
+        let mut desktop = self.desktop.lock();
+        if desktop.foreground == hwnd {
+            desktop.foreground = 0;
+        }
+
+        desktop.windows.remove(&hwnd)
+    }
+
+    pub fn window(&self, hwnd: isize) -> Option<FakeWindow> {
+        self.desktop.lock().windows.get(&hwnd).cloned()
+    }
+
+    pub fn set_foreground_window(&self, hwnd: isize) {
+        self.desktop.lock().foreground = hwnd;
+    }
+
+    pub fn set_cursor_pos(&self, x: i32, y: i32) {
+        self.desktop.lock().cursor = (x, y);
+    }
+
+    fn with_window<T>(&self, hwnd: isize, f: impl FnOnce(&mut FakeWindow) -> T) -> Result<T> {
+        let mut desktop = self.desktop.lock();
+        let window = desktop
+            .windows
+            .get_mut(&hwnd)
+            .ok_or_else(|| anyhow!("there is no window with hwnd {hwnd}"))?;
+
+        Ok(f(window))
+    }
+}
+
+impl Platform for FakePlatform {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>> {
+        Ok(self.desktop.lock().monitors.clone())
+    }
+
+    fn window_rect(&self, hwnd: isize) -> Result<Rect> {
+        self.with_window(hwnd, |w| w.rect)
+    }
+
+    fn position_window(&self, hwnd: isize, layout: &Rect, _top: bool) -> Result<()> {
+        self.with_window(hwnd, |w| w.rect = *layout)
+    }
+
+    fn hide_window(&self, hwnd: isize) {
+        let _ = self.with_window(hwnd, |w| w.visible = false);
+    }
+
+    fn minimize_window(&self, hwnd: isize) {
+        let _ = self.with_window(hwnd, |w| w.minimized = true);
+    }
+
+    fn restore_window(&self, hwnd: isize) {
+        let _ = self.with_window(hwnd, |w| {
+            w.visible = true;
+            w.minimized = false;
+            w.maximized = false;
+        });
+    }
+
+    fn cloak_window(&self, hwnd: isize, cloak: bool) {
+        let _ = self.with_window(hwnd, |w| w.cloaked = cloak);
+    }
+
+    fn is_window_cloaked(&self, hwnd: isize) -> Result<bool> {
+        self.with_window(hwnd, |w| w.cloaked)
+    }
+
+    fn is_window(&self, hwnd: isize) -> bool {
+        self.desktop.lock().windows.contains_key(&hwnd)
+    }
+
+    fn is_window_visible(&self, hwnd: isize) -> bool {
+        self.with_window(hwnd, |w| w.visible).unwrap_or_default()
+    }
+
+    fn is_iconic(&self, hwnd: isize) -> bool {
+        self.with_window(hwnd, |w| w.minimized).unwrap_or_default()
+    }
+
+    fn is_zoomed(&self, hwnd: isize) -> bool {
+        self.with_window(hwnd, |w| w.maximized).unwrap_or_default()
+    }
+
+    fn window_style(&self, hwnd: isize) -> Result<WindowStyle> {
+        self.with_window(hwnd, |w| w.style)
+    }
+
+    fn window_ex_style(&self, hwnd: isize) -> Result<ExtendedWindowStyle> {
+        self.with_window(hwnd, |w| w.ex_style)
+    }
+
+    fn foreground_window(&self) -> Result<isize> {
+        Ok(self.desktop.lock().foreground)
+    }
+
+    fn raise_and_focus_window(&self, hwnd: isize) -> Result<()> {
+        let mut desktop = self.desktop.lock();
+        if !desktop.windows.contains_key(&hwnd) {
+            return Err(anyhow!("there is no window with hwnd {hwnd}"));
+        }
+
+        desktop.foreground = hwnd;
+        Ok(())
+    }
+
+    fn center_cursor_in_rect(&self, rect: &Rect) -> Result<()> {
+        self.desktop.lock().cursor = (rect.left + (rect.right / 2), rect.top + (rect.bottom / 2));
+        Ok(())
+    }
+
+    fn window_title(&self, hwnd: isize) -> Result<String> {
+        self.with_window(hwnd, |w| w.title.clone())
+    }
+
+    fn window_exe(&self, hwnd: isize) -> Result<String> {
+        self.with_window(hwnd, |w| w.exe.clone())
+    }
+
+    fn window_path(&self, hwnd: isize) -> Result<String> {
+        self.with_window(hwnd, |w| w.path.clone())
+    }
+
+    fn window_class(&self, hwnd: isize) -> Result<String> {
+        self.with_window(hwnd, |w| w.class.clone())
+    }
+
+    fn move_window(&self, hwnd: isize, layout: &Rect) -> Result<()> {
+        self.with_window(hwnd, |w| w.rect = *layout)
+    }
+
+    fn close_window(&self, hwnd: isize) -> Result<()> {
+        self.remove_window(hwnd)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("there is no window with hwnd {hwnd}"))
+    }
+
+    fn maximize_window(&self, hwnd: isize) {
+        let _ = self.with_window(hwnd, |w| {
+            w.visible = true;
+            w.minimized = false;
+            w.maximized = true;
+        });
+    }
+
+    fn unmaximize_window(&self, hwnd: isize) {
+        let _ = self.with_window(hwnd, |w| w.maximized = false);
+    }
+
+    fn bring_window_to_top(&self, hwnd: isize) -> Result<()> {
+        self.with_window(hwnd, |_| ())
+    }
+
+    fn set_window_style(&self, hwnd: isize, style: WindowStyle) -> Result<()> {
+        self.with_window(hwnd, |w| w.style = style)
+    }
+
+    fn set_window_ex_style(&self, hwnd: isize, style: ExtendedWindowStyle) -> Result<()> {
+        self.with_window(hwnd, |w| w.ex_style = style)
+    }
+
+    fn set_transparent(&self, hwnd: isize, alpha: u8) -> Result<()> {
+        self.with_window(hwnd, |w| w.alpha = Option::from(alpha))
+    }
+
+    fn set_window_accent(&self, hwnd: isize, colour: Option<u32>) -> Result<()> {
+        self.with_window(hwnd, |w| w.accent = colour)
+    }
+
+    fn process_id(&self, hwnd: isize) -> u32 {
+        self.with_window(hwnd, |w| w.process_id).unwrap_or_default()
+    }
+
+    fn alt_tab_windows(&self) -> Result<Vec<isize>> {
+        let desktop = self.desktop.lock();
+        let mut hwnds = desktop
+            .windows
+            .iter()
+            .filter(|(_, w)| w.visible && !w.cloaked)
+            .map(|(hwnd, _)| *hwnd)
+            .collect::<Vec<_>>();
+
+        hwnds.sort_unstable();
+        hwnds.sort_by_key(|hwnd| *hwnd != desktop.foreground);
+
+        Ok(hwnds)
+    }
+
+    fn desktop_window(&self) -> Result<isize> {
+        // the desktop is not tracked as a window, so focusing it clears the foreground window
+        Ok(0)
+    }
+
+    fn cursor_pos(&self) -> Result<(i32, i32)> {
+        Ok(self.desktop.lock().cursor)
+    }
+
+    fn left_click(&self) {
+        if let Ok(hwnd) = self.window_at_cursor_pos() {
+            self.set_foreground_window(hwnd);
+        }
+    }
+
+    fn window_at_cursor_pos(&self) -> Result<isize> {
+        let desktop = self.desktop.lock();
+        let cursor = desktop.cursor;
+        let under_cursor = |hwnd: &isize| {
+            desktop.windows.get(hwnd).is_some_and(|w| {
+                w.visible && !w.minimized && !w.cloaked && w.rect.contains_point(cursor)
+            })
+        };
+
+        if under_cursor(&desktop.foreground) {
+            return Ok(desktop.foreground);
+        }
+
+        desktop
+            .windows
+            .keys()
+            .copied()
+            .filter(under_cursor)
+            .min()
+            .ok_or_else(|| anyhow!("there is no window at the cursor position"))
+    }
+
+    fn monitor_from_window(&self, hwnd: isize) -> isize {
+        let Ok(rect) = self.window_rect(hwnd) else {
+            return self.monitor_from_point((0, 0));
+        };
+
+        self.monitor_from_point((rect.left + rect.right / 2, rect.top + rect.bottom / 2))
+    }
+
+    fn monitor_from_point(&self, point: (i32, i32)) -> isize {
+        let desktop = self.desktop.lock();
+        desktop
+            .monitors
+            .iter()
+            .find(|m| m.size.contains_point(point))
+            .or_else(|| desktop.monitors.first())
+            .map(|m| m.id)
+            .unwrap_or_default()
+    }
+
+    fn monitors_have_same_dpi(&self, _a: isize, _b: isize) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn load_workspace_information(&self, _monitors: &mut Ring<Monitor>) -> Result<()> {
+        // windows on the fake desktop are only managed once an event is received for them
+        Ok(())
+    }
+
+    fn focus_follows_mouse(&self) -> Result<bool> {
+        Ok(self.desktop.lock().focus_follows_mouse)
+    }
+
+    fn enable_focus_follows_mouse(&self) -> Result<()> {
+        self.desktop.lock().focus_follows_mouse = true;
+        Ok(())
+    }
+
+    fn disable_focus_follows_mouse(&self) -> Result<()> {
+        self.desktop.lock().focus_follows_mouse = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::Result;
+    use serde_json::json;
+
+    use super::*;
+    use crate::simulation::SimulationScript;
+
+    fn monitor(id: isize, left: i32) -> MonitorInfo {
+        let size = Rect {
+            left,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+
+        MonitorInfo {
+            id,
+            name: format!("VIRTUAL{id}"),
+            device: String::from("VIRTUAL"),
+            device_id: format!("VIRTUAL-{id}"),
+            size,
+            work_area_size: size,
+        }
+    }
+
+    #[test]
+    fn fake_platform_enumerates_monitors() -> Result<()> {
+        let desktop = FakePlatform::new();
+        desktop.add_monitor(monitor(1, 0));
+        desktop.add_monitor(monitor(2, 1920));
+
+        let ids = desktop.monitors()?.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(desktop.monitor_from_point((2000, 10)), 2);
+
+        // points outside of every monitor fall back to the first one
+        assert_eq!(desktop.monitor_from_point((-10, -10)), 1);
+
+        desktop.remove_monitor(1);
+        let ids = desktop.monitors()?.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fake_platform_positions_windows() -> Result<()> {
+        let desktop = FakePlatform::new();
+        desktop.add_monitor(monitor(1, 0));
+        desktop.add_monitor(monitor(2, 1920));
+        desktop.add_window(1, FakeWindow::new("First", "first.exe", "First"));
+
+        let rect = Rect {
+            left: 1920,
+            top: 0,
+            right: 800,
+            bottom: 600,
+        };
+
+        desktop.position_window(1, &rect, true)?;
+        assert_eq!(desktop.window_rect(1)?, rect);
+        assert_eq!(desktop.monitor_from_window(1), 2);
+
+        assert!(desktop.position_window(2, &rect, true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fake_platform_hides_and_cloaks_windows() -> Result<()> {
+        let desktop = FakePlatform::new();
+        desktop.add_window(1, FakeWindow::new("First", "first.exe", "First"));
+        desktop.add_window(2, FakeWindow::new("Second", "second.exe", "Second"));
+        desktop.add_window(3, FakeWindow::new("Third", "third.exe", "Third"));
+
+        desktop.hide_window(1);
+        desktop.cloak_window(2, true);
+        assert!(!desktop.is_window_visible(1));
+        assert!(desktop.is_window_cloaked(2)?);
+
+        // hidden and cloaked windows are not shown in the alt-tab menu
+        assert_eq!(desktop.alt_tab_windows()?, vec![3]);
+
+        desktop.restore_window(1);
+        desktop.cloak_window(2, false);
+        assert!(desktop.is_window_visible(1));
+        assert!(!desktop.is_window_cloaked(2)?);
+        assert_eq!(desktop.alt_tab_windows()?, vec![1, 2, 3]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fake_platform_focuses_windows() -> Result<()> {
+        let desktop = FakePlatform::new();
+        desktop.add_window(1, FakeWindow::new("First", "first.exe", "First"));
+        desktop.add_window(2, FakeWindow::new("Second", "second.exe", "Second"));
+
+        desktop.raise_and_focus_window(2)?;
+        assert_eq!(desktop.foreground_window()?, 2);
+
+        // the foreground window is listed first in the alt-tab menu
+        assert_eq!(desktop.alt_tab_windows()?, vec![2, 1]);
+
+        assert!(desktop.raise_and_focus_window(3).is_err());
+        assert_eq!(desktop.foreground_window()?, 2);
+
+        desktop.remove_window(2);
+        assert_eq!(desktop.foreground_window()?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn process_event_and_process_command_run_against_fake_platform() -> Result<()> {
+        let script: SimulationScript = serde_json::from_value(json!({
+            "monitors": [{
+                "name": "VIRTUAL1",
+                "size": { "left": 0, "top": 0, "right": 1920, "bottom": 1080 }
+            }],
+            "windows": [
+                { "hwnd": 1, "title": "First", "exe": "first.exe", "class": "First" },
+                { "hwnd": 2, "title": "Second", "exe": "second.exe", "class": "Second" }
+            ],
+            "steps": [
+                { "type": "SetForeground", "content": 1 },
+                { "type": "Event", "content": { "type": "Show", "content": ["ObjectShow", { "hwnd": 1 }] } },
+                { "type": "Event", "content": { "type": "Show", "content": ["ObjectShow", { "hwnd": 2 }] } },
+                {
+                    "type": "AssertState",
+                    "content": {
+                        "pointer": "/monitors/elements/0/workspaces/elements/0/containers/focused",
+                        "value": 1
+                    }
+                },
+                { "type": "Message", "content": { "type": "FocusWindow", "content": "Left" } },
+                {
+                    "type": "AssertState",
+                    "content": {
+                        "pointer": "/monitors/elements/0/workspaces/elements/0/containers/focused",
+                        "value": 0
+                    }
+                }
+            ]
+        }))?;
+
+        let report = script.run()?;
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+
+        let workspace = &report.state["monitors"]["elements"][0]["workspaces"]["elements"][0];
+        let containers = workspace["containers"]["elements"]
+            .as_array()
+            .map(Vec::len)
+            .unwrap_or_default();
+        assert_eq!(containers, 2);
+
+        // both windows have been positioned side by side on the fake desktop
+        let left = |idx: usize| workspace["latest_layout"][idx]["left"].as_i64();
+        assert!(matches!((left(0), left(1)), (Some(first), Some(second)) if first < second));
+
+        Ok(())
+    }
+}
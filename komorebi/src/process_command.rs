@@ -8,6 +8,8 @@ use std::net::Shutdown;
 use std::net::TcpListener;
 use std::net::TcpStream;
 use std::num::NonZeroUsize;
+#[cfg(not(windows))]
+use std::os::unix::net::UnixStream;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -15,17 +17,20 @@ use std::time::Duration;
 
 use color_eyre::eyre::anyhow;
 use color_eyre::Result;
+#[cfg(windows)]
 use miow::pipe::connect;
 use net2::TcpStreamExt;
 use parking_lot::Mutex;
 use schemars::gen::SchemaSettings;
 use schemars::schema_for;
+#[cfg(windows)]
 use uds_windows::UnixStream;
 
 use crate::core::config_generation::ApplicationConfiguration;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::AnimationOperation;
 use crate::core::ApplicationIdentifier;
 use crate::core::Axis;
 use crate::core::BorderImplementation;
@@ -34,19 +39,25 @@ use crate::core::Layout;
 use crate::core::MoveBehaviour;
 use crate::core::OperationDirection;
 use crate::core::Rect;
+use crate::core::SavedResizeDimensions;
 use crate::core::Sizing;
 use crate::core::SocketMessage;
 use crate::core::StateQuery;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowKind;
 
+use crate::animation::AnimationOperationScope;
 use crate::border_manager;
 use crate::border_manager::IMPLEMENTATION;
 use crate::border_manager::STYLE;
 use crate::colour::Rgb;
 use crate::config_generation::WorkspaceMatchingRule;
 use crate::current_virtual_desktop;
+use crate::external_layout;
+use crate::external_layout::EXTERNAL_LAYOUTS;
 use crate::notify_subscribers;
+use crate::platform;
+use crate::rule_evaluator::RuleSet;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
@@ -56,7 +67,6 @@ use crate::window::RuleDebug;
 use crate::window::Window;
 use crate::window_manager;
 use crate::window_manager::WindowManager;
-use crate::windows_api::WindowsApi;
 use crate::winevent_listener;
 use crate::GlobalState;
 use crate::Notification;
@@ -187,6 +197,8 @@ impl WindowManager {
             }
         }
 
+        let _animation_scope = AnimationOperationScope::new(AnimationOperation::from(&message));
+
         match message {
             SocketMessage::CycleFocusWorkspace(_) | SocketMessage::FocusWorkspaceNumber(_) => {
                 if let Some(monitor) = self.focused_monitor_mut() {
@@ -244,15 +256,16 @@ impl WindowManager {
             }
             SocketMessage::ForceFocus => {
                 let focused_window = self.focused_window()?;
-                let focused_window_rect = WindowsApi::window_rect(focused_window.hwnd)?;
-                WindowsApi::center_cursor_in_rect(&focused_window_rect)?;
-                WindowsApi::left_click();
+                let platform = platform::current();
+                let focused_window_rect = platform.window_rect(focused_window.hwnd)?;
+                platform.center_cursor_in_rect(&focused_window_rect)?;
+                platform.left_click();
             }
             SocketMessage::Close => {
-                Window::from(WindowsApi::foreground_window()?).close()?;
+                Window::from(platform::current().foreground_window()?).close()?;
             }
             SocketMessage::Minimize => {
-                Window::from(WindowsApi::foreground_window()?).minimize();
+                Window::from(platform::current().foreground_window()?).minimize();
             }
             SocketMessage::ToggleFloat => self.toggle_float()?,
             SocketMessage::ToggleMonocle => self.toggle_monocle()?,
@@ -277,6 +290,36 @@ impl WindowManager {
                     self.set_workspace_padding(monitor_idx, workspace_idx, size)?;
                 }
             }
+            SocketMessage::WorkspaceInnerGap(monitor_idx, workspace_idx, size) => {
+                self.set_workspace_inner_gap(monitor_idx, workspace_idx, size)?;
+            }
+            SocketMessage::NamedWorkspaceInnerGap(ref workspace, size) => {
+                if let Some((monitor_idx, workspace_idx)) =
+                    self.monitor_workspace_index_by_name(workspace)
+                {
+                    self.set_workspace_inner_gap(monitor_idx, workspace_idx, size)?;
+                }
+            }
+            SocketMessage::WorkspaceOuterGap(monitor_idx, workspace_idx, padding) => {
+                self.set_workspace_outer_gap(monitor_idx, workspace_idx, padding)?;
+            }
+            SocketMessage::NamedWorkspaceOuterGap(ref workspace, padding) => {
+                if let Some((monitor_idx, workspace_idx)) =
+                    self.monitor_workspace_index_by_name(workspace)
+                {
+                    self.set_workspace_outer_gap(monitor_idx, workspace_idx, padding)?;
+                }
+            }
+            SocketMessage::WorkspaceSmartGaps(monitor_idx, workspace_idx, enable) => {
+                self.set_workspace_smart_gaps(monitor_idx, workspace_idx, enable)?;
+            }
+            SocketMessage::NamedWorkspaceSmartGaps(ref workspace, enable) => {
+                if let Some((monitor_idx, workspace_idx)) =
+                    self.monitor_workspace_index_by_name(workspace)
+                {
+                    self.set_workspace_smart_gaps(monitor_idx, workspace_idx, enable)?;
+                }
+            }
             SocketMessage::InitialWorkspaceRule(identifier, ref id, monitor_idx, workspace_idx) => {
                 let mut workspace_rules = WORKSPACE_MATCHING_RULES.lock();
                 let workspace_matching_rule = WorkspaceMatchingRule {
@@ -492,6 +535,10 @@ impl WindowManager {
             SocketMessage::AdjustWorkspacePadding(sizing, adjustment) => {
                 self.adjust_workspace_padding(sizing, adjustment)?;
             }
+            SocketMessage::AdjustInnerGap(sizing, adjustment) => {
+                self.adjust_inner_gap(sizing, adjustment)?;
+            }
+            SocketMessage::ToggleSmartGaps => self.toggle_smart_gaps()?,
             SocketMessage::MoveContainerToWorkspaceNumber(workspace_idx) => {
                 self.move_container_to_workspace(workspace_idx, true, None)?;
             }
@@ -627,11 +674,19 @@ impl WindowManager {
                 self.retile_all(false)?
             }
             SocketMessage::FlipLayout(layout_flip) => self.flip_layout(layout_flip)?,
+            SocketMessage::ScrollingLayoutColumns(columns) => {
+                self.set_scrolling_layout_columns(columns)?;
+            }
+            SocketMessage::AdjustMasterCount(sizing) => self.adjust_master_count(sizing)?,
+            SocketMessage::AdjustMasterRatio(sizing) => self.adjust_master_ratio(sizing)?,
             SocketMessage::ChangeLayout(layout) => self.change_workspace_layout_default(layout)?,
             SocketMessage::CycleLayout(direction) => self.cycle_layout(direction)?,
             SocketMessage::ChangeLayoutCustom(ref path) => {
                 self.change_workspace_custom_layout(path)?;
             }
+            SocketMessage::ChangeLayoutExternal(ref name) => {
+                self.change_workspace_layout_external(name.clone())?;
+            }
             SocketMessage::WorkspaceLayoutCustom(monitor_idx, workspace_idx, ref path) => {
                 self.set_workspace_layout_custom(monitor_idx, workspace_idx, path)?;
             }
@@ -828,8 +883,8 @@ impl WindowManager {
                 );
                 self.restore_all_windows()?;
 
-                if WindowsApi::focus_follows_mouse()? {
-                    WindowsApi::disable_focus_follows_mouse()?;
+                if platform::current().focus_follows_mouse()? {
+                    platform::current().disable_focus_follows_mouse()?;
                 }
 
                 let sockets = SUBSCRIPTION_SOCKETS.lock();
@@ -1061,7 +1116,7 @@ impl WindowManager {
 
                 match implementation {
                     FocusFollowsMouseImplementation::Komorebi => {
-                        if WindowsApi::focus_follows_mouse()? {
+                        if platform::current().focus_follows_mouse()? {
                             tracing::warn!(
                                 "the komorebi implementation of focus follows mouse cannot be enabled while the windows implementation is enabled"
                             );
@@ -1081,11 +1136,11 @@ impl WindowManager {
                                 "the windows implementation of focus follows mouse cannot be enabled while the komorebi implementation is enabled"
                             );
                         } else if enable {
-                            WindowsApi::enable_focus_follows_mouse()?;
+                            platform::current().enable_focus_follows_mouse()?;
                             self.focus_follows_mouse =
                                 Option::from(FocusFollowsMouseImplementation::Windows);
                         } else {
-                            WindowsApi::disable_focus_follows_mouse()?;
+                            platform::current().disable_focus_follows_mouse()?;
                             self.focus_follows_mouse = None;
                         }
                     }
@@ -1101,7 +1156,7 @@ impl WindowManager {
 
                 match implementation {
                     FocusFollowsMouseImplementation::Komorebi => {
-                        if WindowsApi::focus_follows_mouse()? {
+                        if platform::current().focus_follows_mouse()? {
                             tracing::warn!(
                                 "the komorebi implementation of focus follows mouse cannot be toggled while the windows implementation is enabled"
                             );
@@ -1131,11 +1186,11 @@ impl WindowManager {
                         } else {
                             match self.focus_follows_mouse {
                                 None => {
-                                    WindowsApi::enable_focus_follows_mouse()?;
+                                    platform::current().enable_focus_follows_mouse()?;
                                     self.focus_follows_mouse = Option::from(implementation);
                                 }
                                 Some(FocusFollowsMouseImplementation::Windows) => {
-                                    WindowsApi::disable_focus_follows_mouse()?;
+                                    platform::current().disable_focus_follows_mouse()?;
                                     self.focus_follows_mouse = None;
                                 }
                                 Some(FocusFollowsMouseImplementation::Komorebi) => {
@@ -1279,6 +1334,7 @@ impl WindowManager {
                 serde_json::to_writer_pretty(&file, &resize)?;
             }
             SocketMessage::QuickLoad => {
+                let work_area = self.focused_workspace_work_area()?;
                 let workspace = self.focused_workspace_mut()?;
 
                 let quicksave_json = std::env::temp_dir().join("komorebi.quicksave.json");
@@ -1286,9 +1342,9 @@ impl WindowManager {
                 let file = File::open(&quicksave_json)
                     .map_err(|_| anyhow!("no quicksave found at {}", quicksave_json.display()))?;
 
-                let resize: Vec<Option<Rect>> = serde_json::from_reader(file)?;
+                let resize: SavedResizeDimensions = serde_json::from_reader(file)?;
 
-                workspace.set_resize_dimensions(resize);
+                workspace.set_resize_dimensions(resize.into_relative(&work_area));
                 self.update_focused_workspace(false, false)?;
             }
             SocketMessage::Save(ref path) => {
@@ -1304,14 +1360,16 @@ impl WindowManager {
                 serde_json::to_writer_pretty(&file, &resize)?;
             }
             SocketMessage::Load(ref path) => {
+                // Older save files hold pixel values, which are migrated using the current work area
+                let work_area = self.focused_workspace_work_area()?;
                 let workspace = self.focused_workspace_mut()?;
 
                 let file =
                     File::open(path).map_err(|_| anyhow!("no file found at {}", path.display()))?;
 
-                let resize: Vec<Option<Rect>> = serde_json::from_reader(file)?;
+                let resize: SavedResizeDimensions = serde_json::from_reader(file)?;
 
-                workspace.set_resize_dimensions(resize);
+                workspace.set_resize_dimensions(resize.into_relative(&work_area));
                 self.update_focused_workspace(false, false)?;
             }
             SocketMessage::AddSubscriberSocket(ref socket) => {
@@ -1323,6 +1381,21 @@ impl WindowManager {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
                 sockets.remove(socket);
             }
+            SocketMessage::RegisterExternalLayout(ref name) => {
+                let mut layouts = EXTERNAL_LAYOUTS.lock();
+                let socket_path = DATA_DIR.join(name);
+                layouts.insert(name.clone(), socket_path);
+                drop(layouts);
+                external_layout::clear_responses(name);
+
+                // Workspaces which were waiting on this layout can now use it
+                self.retile_all(true)?;
+            }
+            SocketMessage::UnregisterExternalLayout(ref name) => {
+                let mut layouts = EXTERNAL_LAYOUTS.lock();
+                layouts.remove(name);
+                external_layout::clear_responses(name);
+            }
             SocketMessage::AddSubscriberPipe(ref subscriber) => {
                 let mut pipes = SUBSCRIPTION_PIPES.lock();
                 let pipe_path = format!(r"\\.\pipe\{subscriber}");
@@ -1413,6 +1486,9 @@ impl WindowManager {
             SocketMessage::UnmanagedWindowOperationBehaviour(behaviour) => {
                 self.unmanaged_window_operation_behaviour = behaviour;
             }
+            SocketMessage::NavigationBehaviour(behaviour) => {
+                self.navigation_behaviour = behaviour;
+            }
             SocketMessage::Border(enable) => {
                 border_manager::BORDER_ENABLED.store(enable, Ordering::SeqCst);
             }
@@ -1471,6 +1547,7 @@ impl WindowManager {
                 ANIMATION_FPS.store(fps, Ordering::SeqCst);
             }
             SocketMessage::AnimationStyle(style) => {
+                style.validate()?;
                 *ANIMATION_STYLE.lock() = style;
             }
             SocketMessage::ToggleTransparency => {
@@ -1569,6 +1646,12 @@ impl WindowManager {
 
                 reply.write_all(schema.as_bytes())?;
             }
+            SocketMessage::EvaluateRules(ref title, ref exe, ref class, ref path) => {
+                let evaluation = RuleSet::current().evaluate(title, exe, class, path);
+                let report = serde_json::to_string_pretty(&evaluation)?;
+
+                reply.write_all(report.as_bytes())?;
+            }
             // Deprecated commands
             SocketMessage::AltFocusHack(_)
             | SocketMessage::IdentifyBorderOverflowApplication(_, _) => {}
@@ -1589,6 +1672,12 @@ impl WindowManager {
     }
 }
 
+/// Named pipes are only available on Windows
+#[cfg(not(windows))]
+fn connect(_path: &str) -> std::io::Result<File> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
 pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream) -> Result<()> {
     let reader = BufReader::new(stream.try_clone()?);
     // TODO(raggi): while this processes more than one command, if there are
@@ -9,33 +9,36 @@ use color_eyre::Result;
 use crossbeam_utils::atomic::AtomicConsume;
 use parking_lot::Mutex;
 
+use crate::core::AnimationOperation;
+use crate::core::DefaultLayout;
+use crate::core::Layout;
 use crate::core::OperationDirection;
 use crate::core::Rect;
 use crate::core::Sizing;
 use crate::core::WindowContainerBehaviour;
 
+use crate::animation::AnimationOperationScope;
 use crate::border_manager;
 use crate::border_manager::BORDER_OFFSET;
 use crate::border_manager::BORDER_WIDTH;
 use crate::current_virtual_desktop;
 use crate::notify_subscribers;
+use crate::platform;
 use crate::stackbar_manager;
 use crate::transparency_manager;
-use crate::window::should_act;
 use crate::window::RuleDebug;
+use crate::window::Window;
 use crate::window_manager::WindowManager;
 use crate::window_manager_event::WindowManagerEvent;
-use crate::windows_api::WindowsApi;
 use crate::winevent::WinEvent;
 use crate::workspace_reconciliator;
 use crate::workspace_reconciliator::ALT_TAB_HWND;
 use crate::workspace_reconciliator::ALT_TAB_HWND_INSTANT;
 use crate::Notification;
 use crate::NotificationEvent;
-use crate::DATA_DIR;
 use crate::FLOATING_APPLICATIONS;
 use crate::HIDDEN_HWNDS;
-use crate::REGEX_IDENTIFIERS;
+use crate::MANAGE_ACTION_NO_TITLEBAR;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 
 #[tracing::instrument]
@@ -122,6 +125,8 @@ impl WindowManager {
             }
         }
 
+        let _animation_scope = AnimationOperationScope::new(AnimationOperation::from(&event));
+
         // Make sure we have the most recently focused monitor from any event
         match event {
             WindowManagerEvent::FocusChange(_, window)
@@ -169,6 +174,10 @@ impl WindowManager {
                 self.has_pending_raise_op = false;
             }
             WindowManagerEvent::Destroy(_, window) | WindowManagerEvent::Unmanage(window) => {
+                transparency_manager::remove_window_alpha(window.hwnd);
+                window.remove_cached_size_constraints();
+                MANAGE_ACTION_NO_TITLEBAR.lock().remove(&window.hwnd);
+
                 if self.focused_workspace()?.contains_window(window.hwnd) {
                     self.focused_workspace_mut()?.remove_window(window.hwnd)?;
                     self.update_focused_workspace(false, false)?;
@@ -202,7 +211,6 @@ impl WindowManager {
                 {
                     let tray_and_multi_window_identifiers =
                         TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock();
-                    let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
                     let title = &window.title()?;
                     let exe_name = &window.exe()?;
@@ -212,15 +220,9 @@ impl WindowManager {
                     // We don't want to purge windows that have been deliberately hidden by us, eg. when
                     // they are not on the top of a container stack.
                     let programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
-                    let should_act = should_act(
-                        title,
-                        exe_name,
-                        class,
-                        path,
-                        &tray_and_multi_window_identifiers,
-                        &regex_identifiers,
-                    )
-                    .is_some();
+                    let should_act = tray_and_multi_window_identifiers
+                        .matching_rule(title, exe_name, class, path)
+                        .is_some();
 
                     if !window.is_window()
                         || should_act
@@ -262,6 +264,14 @@ impl WindowManager {
                             }
                         } else {
                             workspace.focus_container_by_window(window.hwnd)?;
+
+                            // scrolling layouts need to be retiled to bring the focused column into view
+                            if matches!(
+                                workspace.layout(),
+                                Layout::Default(DefaultLayout::Scrolling)
+                            ) {
+                                self.update_focused_workspace(false, false)?;
+                            }
                         }
                     }
                     Some(idx) => {
@@ -288,9 +298,11 @@ impl WindowManager {
                             // At this point we know we are going to send a notification to the workspace reconciliator
                             // So we get the topmost window returned by EnumWindows, which is almost always the window
                             // that has been selected by alt-tab
-                            if let Ok(alt_tab_windows) = WindowsApi::alt_tab_windows() {
-                                if let Some(first) =
-                                    alt_tab_windows.iter().find(|w| w.title().is_ok())
+                            if let Ok(alt_tab_windows) = platform::current().alt_tab_windows() {
+                                if let Some(first) = alt_tab_windows
+                                    .into_iter()
+                                    .map(Window::from)
+                                    .find(|w| w.title().is_ok())
                                 {
                                     // If our record of this HWND hasn't been updated in over a minute
                                     let mut instant = ALT_TAB_HWND_INSTANT.lock();
@@ -340,23 +352,18 @@ impl WindowManager {
                     let monocle_container = workspace.monocle_container().clone();
 
                     if !workspace_contains_window && !needs_reconciliation {
+                        window.cache_size_constraints()?;
+
                         let floating_applications = FLOATING_APPLICATIONS.lock();
-                        let regex_identifiers = REGEX_IDENTIFIERS.lock();
                         let mut should_float = false;
 
                         if !floating_applications.is_empty() {
                             if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
                                 (window.title(), window.exe(), window.class(), window.path())
                             {
-                                should_float = should_act(
-                                    &title,
-                                    &exe_name,
-                                    &class,
-                                    &path,
-                                    &floating_applications,
-                                    &regex_identifiers,
-                                )
-                                .is_some();
+                                should_float = floating_applications
+                                    .matching_rule(&title, &exe_name, &class, &path)
+                                    .is_some();
                             }
                         }
 
@@ -365,11 +372,14 @@ impl WindowManager {
 
                         if behaviour.float_override {
                             workspace.floating_windows_mut().push(window);
+                            self.apply_floating_placement(window)?;
+                            self.apply_manage_actions(window)?;
                             self.update_focused_workspace(false, false)?;
                         } else {
                             match behaviour.current_behaviour {
                                 WindowContainerBehaviour::Create => {
                                     workspace.new_container_for_window(window);
+                                    self.apply_manage_actions(window)?;
                                     self.update_focused_workspace(false, false)?;
                                 }
                                 WindowContainerBehaviour::Append => {
@@ -377,6 +387,7 @@ impl WindowManager {
                                         .focused_container_mut()
                                         .ok_or_else(|| anyhow!("there is no focused container"))?
                                         .add_window(window);
+                                    self.apply_manage_actions(window)?;
                                     self.update_focused_workspace(true, false)?;
                                     stackbar_manager::send_notification();
                                 }
@@ -414,7 +425,7 @@ impl WindowManager {
                         .ok_or_else(|| anyhow!("there is no workspace with this idx"))?
                         .focused_container_idx();
 
-                    WindowsApi::bring_window_to_top(window.hwnd)?;
+                    platform::current().bring_window_to_top(window.hwnd)?;
 
                     self.pending_move_op =
                         Option::from((monitor_idx, workspace_idx, container_idx));
@@ -438,7 +449,7 @@ impl WindowManager {
 
                 let workspace = self.focused_workspace_mut()?;
                 let focused_container_idx = workspace.focused_container_idx();
-                let new_position = WindowsApi::window_rect(window.hwnd)?;
+                let new_position = platform::current().window_rect(window.hwnd)?;
                 let old_position = *workspace
                     .latest_layout()
                     .get(focused_container_idx)
@@ -661,7 +672,7 @@ impl WindowManager {
             }
         }
 
-        let hwnd_json = DATA_DIR.join("komorebi.hwnd.json");
+        let hwnd_json = self.data_dir.join("komorebi.hwnd.json");
         let file = OpenOptions::new()
             .write(true)
             .truncate(true)
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::WorkspaceMatchingRule;
+use crate::core::ManageAction;
+use crate::core::WindowSizeConstraints;
+use crate::rule_index::RuleList;
+use crate::static_config::FloatingPlacementRule;
+use crate::static_config::ManageActionRule;
+use crate::static_config::WindowSizeConstraintRule;
+use crate::window::matching_floating_placement;
+use crate::window::matching_manage_actions;
+use crate::window::matching_size_constraints;
+use crate::FLOATING_APPLICATIONS;
+use crate::FLOATING_PLACEMENT_IDENTIFIERS;
+use crate::FLOATING_PLACEMENT_RULES;
+use crate::IGNORE_IDENTIFIERS;
+use crate::LAYERED_WHITELIST;
+use crate::MANAGE_ACTION_IDENTIFIERS;
+use crate::MANAGE_ACTION_RULES;
+use crate::MANAGE_IDENTIFIERS;
+use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::PERMAIGNORE_CLASSES;
+use crate::REGEX_IDENTIFIERS;
+use crate::SLOW_APPLICATION_IDENTIFIERS;
+use crate::TRANSPARENCY_BLACKLIST;
+use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
+use crate::WINDOW_SIZE_CONSTRAINTS;
+use crate::WINDOW_SIZE_CONSTRAINTS_BY_HWND;
+use crate::WINDOW_SIZE_CONSTRAINT_IDENTIFIERS;
+use crate::WORKSPACE_MATCHING_RULES;
+
+/// Every rule list which is consulted when deciding how to handle a window
+#[derive(Debug, Default)]
+pub struct RuleSet {
+    pub permaignore_classes: Vec<String>,
+    pub ignore_identifiers: RuleList,
+    pub manage_identifiers: RuleList,
+    pub floating_applications: RuleList,
+    pub layered_whitelist: RuleList,
+    pub tray_and_multi_window_identifiers: RuleList,
+    pub object_name_change_on_launch: RuleList,
+    pub slow_application_identifiers: RuleList,
+    pub transparency_blacklist: RuleList,
+    pub window_size_constraints: Vec<WindowSizeConstraintRule>,
+    pub floating_placement: Vec<FloatingPlacementRule>,
+    pub manage_actions: Vec<ManageActionRule>,
+    pub workspace_rules: Vec<WorkspaceMatchingRule>,
+    pub regex_identifiers: HashMap<String, Regex>,
+}
+
+impl RuleSet {
+    /// The rules currently loaded in this process. In a process which has not loaded a
+    /// configuration file, these are the built-in rules
+    pub fn current() -> Self {
+        Self {
+            permaignore_classes: PERMAIGNORE_CLASSES.lock().clone(),
+            ignore_identifiers: RuleList::from(IGNORE_IDENTIFIERS.lock().to_vec()),
+            manage_identifiers: RuleList::from(MANAGE_IDENTIFIERS.lock().to_vec()),
+            floating_applications: RuleList::from(FLOATING_APPLICATIONS.lock().to_vec()),
+            layered_whitelist: RuleList::from(LAYERED_WHITELIST.lock().to_vec()),
+            tray_and_multi_window_identifiers: RuleList::from(
+                TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().to_vec(),
+            ),
+            object_name_change_on_launch: RuleList::from(
+                OBJECT_NAME_CHANGE_ON_LAUNCH.lock().to_vec(),
+            ),
+            slow_application_identifiers: RuleList::from(
+                SLOW_APPLICATION_IDENTIFIERS.lock().to_vec(),
+            ),
+            transparency_blacklist: RuleList::from(TRANSPARENCY_BLACKLIST.lock().to_vec()),
+            window_size_constraints: WINDOW_SIZE_CONSTRAINTS.lock().clone(),
+            floating_placement: FLOATING_PLACEMENT_RULES.lock().clone(),
+            manage_actions: MANAGE_ACTION_RULES.lock().clone(),
+            workspace_rules: WORKSPACE_MATCHING_RULES.lock().clone(),
+            regex_identifiers: REGEX_IDENTIFIERS.lock().clone(),
+        }
+    }
+
+    /// Replace the rules loaded in this process with the rules which can be loaded from a
+    /// configuration file. Permanently ignored classes and workspace rules are left untouched
+    pub fn store(self) {
+        // the regexes are replaced first so that no rule is ever loaded without its regex
+        *REGEX_IDENTIFIERS.lock() = self.regex_identifiers;
+
+        *IGNORE_IDENTIFIERS.lock() = self.ignore_identifiers;
+        *MANAGE_IDENTIFIERS.lock() = self.manage_identifiers;
+        *FLOATING_APPLICATIONS.lock() = self.floating_applications;
+        *LAYERED_WHITELIST.lock() = self.layered_whitelist;
+        *TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock() = self.tray_and_multi_window_identifiers;
+        *OBJECT_NAME_CHANGE_ON_LAUNCH.lock() = self.object_name_change_on_launch;
+        *SLOW_APPLICATION_IDENTIFIERS.lock() = self.slow_application_identifiers;
+        *TRANSPARENCY_BLACKLIST.lock() = self.transparency_blacklist;
+
+        *WINDOW_SIZE_CONSTRAINT_IDENTIFIERS.lock() =
+            rule_identifiers(&self.window_size_constraints, |rule| &rule.matching_rule);
+        *WINDOW_SIZE_CONSTRAINTS.lock() = self.window_size_constraints;
+        WINDOW_SIZE_CONSTRAINTS_BY_HWND.lock().clear();
+
+        *FLOATING_PLACEMENT_IDENTIFIERS.lock() =
+            rule_identifiers(&self.floating_placement, |rule| &rule.matching_rule);
+        *FLOATING_PLACEMENT_RULES.lock() = self.floating_placement;
+
+        *MANAGE_ACTION_IDENTIFIERS.lock() =
+            rule_identifiers(&self.manage_actions, |rule| &rule.matching_rule);
+        *MANAGE_ACTION_RULES.lock() = self.manage_actions;
+    }
+
+    /// Run a hypothetical window through the same rules that decide whether a window is managed
+    /// and which workspace it belongs on, without needing the window to exist
+    pub fn evaluate(&self, title: &str, exe_name: &str, class: &str, path: &str) -> RuleEvaluation {
+        let matches = |rules: &RuleList| -> Vec<MatchingRule> {
+            rules.matching_rules(title, exe_name, class, path)
+        };
+
+        let workspace_rules = RuleList::from(
+            self.workspace_rules
+                .iter()
+                .map(|rule| rule.matching_rule.clone())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut evaluation = RuleEvaluation {
+            title: title.to_string(),
+            exe_name: exe_name.to_string(),
+            class: class.to_string(),
+            path: path.to_string(),
+            matches_permaignore_class: self
+                .permaignore_classes
+                .iter()
+                .find(|permaignore| permaignore.as_str() == class)
+                .cloned(),
+            matches_ignore_identifiers: matches(&self.ignore_identifiers),
+            matches_managed_overrides: matches(&self.manage_identifiers),
+            matches_floating_applications: matches(&self.floating_applications),
+            matches_layered_whitelist: matches(&self.layered_whitelist),
+            matches_tray_and_multi_window_identifiers: matches(
+                &self.tray_and_multi_window_identifiers,
+            ),
+            matches_object_name_change_on_launch: matches(&self.object_name_change_on_launch),
+            matches_slow_application_identifiers: matches(&self.slow_application_identifiers),
+            matches_transparency_blacklist: matches(&self.transparency_blacklist),
+            matches_workspace_rules: workspace_rules
+                .matching_indices(title, exe_name, class, path)
+                .into_iter()
+                .filter_map(|idx| self.workspace_rules.get(idx))
+                .cloned()
+                .collect(),
+            size_constraints: matching_size_constraints(
+                &self.window_size_constraints,
+                &rule_identifiers(&self.window_size_constraints, |rule| &rule.matching_rule),
+                title,
+                exe_name,
+                class,
+                path,
+            ),
+            floating_placement: matching_floating_placement(
+                &self.floating_placement,
+                &rule_identifiers(&self.floating_placement, |rule| &rule.matching_rule),
+                title,
+                exe_name,
+                class,
+                path,
+            ),
+            manage_actions: matching_manage_actions(
+                &self.manage_actions,
+                &rule_identifiers(&self.manage_actions, |rule| &rule.matching_rule),
+                title,
+                exe_name,
+                class,
+                path,
+            ),
+            decision: RuleDecision::ManagedIfEligible,
+            floating: false,
+        };
+
+        evaluation.decision = RuleDecision::from_matches(
+            evaluation.matches_permaignore_class.is_some(),
+            !evaluation.matches_ignore_identifiers.is_empty(),
+            !evaluation.matches_managed_overrides.is_empty(),
+        );
+
+        evaluation.floating = !matches!(evaluation.decision, RuleDecision::Ignored)
+            && !evaluation.matches_floating_applications.is_empty();
+
+        evaluation
+    }
+}
+
+/// Whether the rules allow a window to be managed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleDecision {
+    /// The window will never be managed
+    Ignored,
+    /// The window will always be managed, regardless of its window styles
+    Managed,
+    /// No rule decides, so the window will be managed if its window styles make it eligible
+    ManagedIfEligible,
+}
+
+impl RuleDecision {
+    /// The decision for a window from the kinds of rules which match it. A permanently ignored
+    /// class is never managed, and a manage rule takes precedence over an ignore rule
+    pub const fn from_matches(
+        permaignore_class: bool,
+        ignore_identifier: bool,
+        managed_override: bool,
+    ) -> Self {
+        if permaignore_class {
+            Self::Ignored
+        } else if managed_override {
+            Self::Managed
+        } else if ignore_identifier {
+            Self::Ignored
+        } else {
+            Self::ManagedIfEligible
+        }
+    }
+}
+
+/// The matching rules of a list of rules, in the same order, so that the index of a matching
+/// rule is also the index of the rule it belongs to
+fn rule_identifiers<T>(rules: &[T], matching_rule: impl Fn(&T) -> &MatchingRule) -> RuleList {
+    RuleList::from(
+        rules
+            .iter()
+            .map(|rule| matching_rule(rule).clone())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// The result of running a hypothetical window through the rules, with every rule that matched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleEvaluation {
+    pub title: String,
+    pub exe_name: String,
+    pub class: String,
+    pub path: String,
+    pub matches_permaignore_class: Option<String>,
+    pub matches_ignore_identifiers: Vec<MatchingRule>,
+    pub matches_managed_overrides: Vec<MatchingRule>,
+    pub matches_floating_applications: Vec<MatchingRule>,
+    pub matches_layered_whitelist: Vec<MatchingRule>,
+    pub matches_tray_and_multi_window_identifiers: Vec<MatchingRule>,
+    pub matches_object_name_change_on_launch: Vec<MatchingRule>,
+    pub matches_slow_application_identifiers: Vec<MatchingRule>,
+    pub matches_transparency_blacklist: Vec<MatchingRule>,
+    pub matches_workspace_rules: Vec<WorkspaceMatchingRule>,
+    /// The size constraints from the first window size constraint rule which matches
+    pub size_constraints: Option<WindowSizeConstraints>,
+    /// The first floating placement rule which matches
+    pub floating_placement: Option<FloatingPlacementRule>,
+    /// The actions of every manage action rule which matches, in the order that they are taken
+    pub manage_actions: Vec<ManageAction>,
+    pub decision: RuleDecision,
+    /// Whether the window will float when it is managed
+    pub floating: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::FloatingPlacement;
+    use crate::static_config::StaticConfig;
+    use color_eyre::Result;
+
+    #[test]
+    fn evaluation_reports_every_kind_of_rule() -> Result<()> {
+        let config: StaticConfig = serde_json::from_str(
+            r#"{
+                "ignore_rules": [
+                    { "kind": "Exe", "id": "app.exe", "matching_strategy": "Equals" }
+                ],
+                "manage_rules": [
+                    { "kind": "Title", "id": "Main", "matching_strategy": "StartsWith" }
+                ],
+                "window_size_constraints": [
+                    {
+                        "matching_rule": { "kind": "Exe", "id": "app.exe", "matching_strategy": "Equals" },
+                        "min_width": 800
+                    },
+                    {
+                        "matching_rule": { "kind": "Exe", "id": "app", "matching_strategy": "StartsWith" },
+                        "min_width": 400
+                    }
+                ],
+                "floating_placement": [
+                    {
+                        "matching_rule": { "kind": "Class", "id": "^App", "matching_strategy": "Regex" },
+                        "placement": "Center"
+                    },
+                    {
+                        "matching_rule": { "kind": "Title", "id": "Main", "matching_strategy": "StartsWith" },
+                        "placement": "TopLeft"
+                    }
+                ],
+                "manage_actions": [
+                    {
+                        "matching_rule": { "kind": "Exe", "id": "app.exe" },
+                        "actions": ["Monocle"]
+                    },
+                    {
+                        "matching_rule": { "kind": "Title", "id": "Window", "matching_strategy": "Contains" },
+                        "actions": [{ "Transparency": 200 }]
+                    }
+                ]
+            }"#,
+        )?;
+
+        let rule_set = config.rule_set()?;
+
+        let managed = rule_set.evaluate("Main Window", "app.exe", "AppWindow", "C:\\app.exe");
+        assert_eq!(managed.decision, RuleDecision::Managed);
+        assert_eq!(
+            managed
+                .size_constraints
+                .and_then(|constraints| constraints.min_width),
+            Some(800)
+        );
+        assert_eq!(
+            managed.floating_placement.map(|rule| rule.placement),
+            Some(FloatingPlacement::Center)
+        );
+        assert_eq!(
+            managed.manage_actions,
+            vec![ManageAction::Monocle, ManageAction::Transparency(200)]
+        );
+
+        let ignored = rule_set.evaluate("Settings", "app.exe", "Dialog", "C:\\app.exe");
+        assert_eq!(ignored.decision, RuleDecision::Ignored);
+        assert!(ignored.floating_placement.is_none());
+        assert_eq!(ignored.manage_actions, vec![ManageAction::Monocle]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decisions_follow_the_precedence_of_the_rules() {
+        assert_eq!(
+            RuleDecision::from_matches(true, false, true),
+            RuleDecision::Ignored
+        );
+        assert_eq!(
+            RuleDecision::from_matches(false, true, true),
+            RuleDecision::Managed
+        );
+        assert_eq!(
+            RuleDecision::from_matches(false, true, false),
+            RuleDecision::Ignored
+        );
+        assert_eq!(
+            RuleDecision::from_matches(false, false, false),
+            RuleDecision::ManagedIfEligible
+        );
+    }
+}
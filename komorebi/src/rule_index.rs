@@ -0,0 +1,693 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use regex::RegexSet;
+
+use crate::core::config_generation::IdWithIdentifier;
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::core::ApplicationIdentifier;
+use crate::window::contains_ignore_case;
+use crate::window::word_match;
+
+/// A list of matching rules which is compiled into a `RuleIndex` the first time that it is
+/// matched against a window after being changed
+#[derive(Debug, Default)]
+pub struct RuleList {
+    rules: Vec<MatchingRule>,
+    index: OnceLock<RuleIndex>,
+}
+
+impl From<Vec<MatchingRule>> for RuleList {
+    fn from(rules: Vec<MatchingRule>) -> Self {
+        Self {
+            rules,
+            index: OnceLock::new(),
+        }
+    }
+}
+
+impl Deref for RuleList {
+    type Target = Vec<MatchingRule>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rules
+    }
+}
+
+impl DerefMut for RuleList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // the rules may be about to change, so the index has to be compiled again
+        self.index = OnceLock::new();
+        &mut self.rules
+    }
+}
+
+impl<'a> IntoIterator for &'a RuleList {
+    type Item = &'a MatchingRule;
+    type IntoIter = std::slice::Iter<'a, MatchingRule>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.iter()
+    }
+}
+
+impl RuleList {
+    /// The last rule in the list which matches the window, which is the same rule that
+    /// `should_act` would return
+    pub fn matching_rule(
+        &self,
+        title: &str,
+        exe_name: &str,
+        class: &str,
+        path: &str,
+    ) -> Option<MatchingRule> {
+        self.index
+            .get_or_init(|| RuleIndex::new(&self.rules))
+            .find(title, exe_name, class, path)
+            .and_then(|idx| self.rules.get(idx))
+            .cloned()
+    }
+
+    /// The indices of every rule in the list which matches the window, in ascending order
+    pub fn matching_indices(
+        &self,
+        title: &str,
+        exe_name: &str,
+        class: &str,
+        path: &str,
+    ) -> Vec<usize> {
+        self.index
+            .get_or_init(|| RuleIndex::new(&self.rules))
+            .find_all(title, exe_name, class, path)
+    }
+
+    /// Every rule in the list which matches the window, in the order that they were added
+    pub fn matching_rules(
+        &self,
+        title: &str,
+        exe_name: &str,
+        class: &str,
+        path: &str,
+    ) -> Vec<MatchingRule> {
+        self.matching_indices(title, exe_name, class, path)
+            .into_iter()
+            .filter_map(|idx| self.rules.get(idx))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A matching rule compiled down to the identifiers (leaves) that it tests
+#[derive(Debug)]
+enum CompiledRule {
+    Leaf(usize),
+    All(Vec<CompiledRule>),
+    Any(Vec<CompiledRule>),
+    Not(Box<CompiledRule>),
+}
+
+impl CompiledRule {
+    fn evaluate(&self, leaves: &[bool]) -> bool {
+        match self {
+            Self::Leaf(leaf) => leaves[*leaf],
+            Self::All(rules) => rules.iter().all(|rule| rule.evaluate(leaves)),
+            Self::Any(rules) => rules.iter().any(|rule| rule.evaluate(leaves)),
+            Self::Not(rule) => !rule.evaluate(leaves),
+        }
+    }
+}
+
+/// A check of an identifier against the value of a window property which can't be expressed as a
+/// pattern
+type Check = fn(&str, &str) -> bool;
+
+/// The identifiers which test one property of a window, either by comparing the whole value or by
+/// matching it against a set of patterns in a single pass. Identifiers which can't be expressed
+/// either way are checked one by one
+#[derive(Debug, Default)]
+struct FieldIndex {
+    exact: HashMap<String, Vec<usize>>,
+    exact_ignore_case: HashMap<String, Vec<usize>>,
+    patterns: Vec<String>,
+    pattern_leaves: Vec<usize>,
+    set: Option<RegexSet>,
+    /// The patterns compiled one by one, when they could not be compiled into a single set
+    individual: Vec<(Regex, usize)>,
+    checked: Vec<(Check, String, usize)>,
+}
+
+impl FieldIndex {
+    fn add_exact(&mut self, value: &str, leaf: usize) {
+        self.exact.entry(value.to_string()).or_default().push(leaf);
+    }
+
+    fn add_exact_ignore_case(&mut self, value: &str, leaf: usize) {
+        self.exact_ignore_case
+            .entry(value.to_lowercase())
+            .or_default()
+            .push(leaf);
+    }
+
+    fn add_checked(&mut self, check: Check, id: &str, leaf: usize) {
+        self.checked.push((check, id.to_string(), leaf));
+    }
+
+    fn add_pattern(&mut self, pattern: String, leaf: usize) {
+        // an invalid pattern never matches, just like a regex rule which failed to compile
+        if Regex::new(&pattern).is_ok() {
+            self.patterns.push(pattern);
+            self.pattern_leaves.push(leaf);
+        }
+    }
+
+    fn compile(&mut self) {
+        if self.patterns.is_empty() {
+            return;
+        }
+
+        match RegexSet::new(&self.patterns) {
+            Ok(set) => self.set = Option::from(set),
+            Err(error) => {
+                tracing::warn!(
+                    "could not compile matching rule patterns into a set, matching them one by one: {error}"
+                );
+
+                self.compile_individually();
+            }
+        }
+    }
+
+    fn compile_individually(&mut self) {
+        self.set = None;
+        self.individual = self
+            .patterns
+            .iter()
+            .zip(&self.pattern_leaves)
+            .filter_map(|(pattern, leaf)| Regex::new(pattern).ok().map(|re| (re, *leaf)))
+            .collect();
+    }
+
+    fn matches(&self, value: &str, leaves: &mut [bool]) {
+        if let Some(matched) = self.exact.get(value) {
+            for leaf in matched {
+                leaves[*leaf] = true;
+            }
+        }
+
+        if !self.exact_ignore_case.is_empty() {
+            if let Some(matched) = self.exact_ignore_case.get(&value.to_lowercase()) {
+                for leaf in matched {
+                    leaves[*leaf] = true;
+                }
+            }
+        }
+
+        if let Some(set) = &self.set {
+            for idx in set.matches(value) {
+                leaves[self.pattern_leaves[idx]] = true;
+            }
+        }
+
+        for (re, leaf) in &self.individual {
+            if re.is_match(value) {
+                leaves[*leaf] = true;
+            }
+        }
+
+        for (check, id, leaf) in &self.checked {
+            if check(id, value) {
+                leaves[*leaf] = true;
+            }
+        }
+    }
+}
+
+/// An index over a list of matching rules which tests every identifier in the list with a hash
+/// lookup or a single regex set pass for each property of the window, instead of testing the rules
+/// one by one
+#[derive(Debug, Default)]
+pub struct RuleIndex {
+    title: FieldIndex,
+    exe: FieldIndex,
+    class: FieldIndex,
+    path: FieldIndex,
+    /// Whether the result of each leaf has to be inverted, eg. for `DoesNotContain`
+    negated: Vec<bool>,
+    /// The rule which each leaf belongs to when it is the only identifier of a non-negated rule
+    simple: Vec<Option<usize>>,
+    /// The rules which can't be resolved from a single leaf, in ascending order
+    complex: Vec<(usize, CompiledRule)>,
+}
+
+impl RuleIndex {
+    pub fn new(rules: &[MatchingRule]) -> Self {
+        let mut index = Self::default();
+
+        for (idx, rule) in rules.iter().enumerate() {
+            let compiled = index.compile_rule(rule);
+
+            match compiled {
+                CompiledRule::Leaf(leaf) if !index.negated[leaf] => {
+                    index.simple[leaf] = Option::from(idx);
+                }
+                compiled => index.complex.push((idx, compiled)),
+            }
+        }
+
+        for field in [
+            &mut index.title,
+            &mut index.exe,
+            &mut index.class,
+            &mut index.path,
+        ] {
+            field.compile();
+        }
+
+        index
+    }
+
+    fn compile_rule(&mut self, rule: &MatchingRule) -> CompiledRule {
+        match rule {
+            MatchingRule::Simple(identifier) => CompiledRule::Leaf(self.add_leaf(identifier)),
+            MatchingRule::Composite(identifiers) => CompiledRule::All(
+                identifiers
+                    .iter()
+                    .map(|identifier| CompiledRule::Leaf(self.add_leaf(identifier)))
+                    .collect(),
+            ),
+            MatchingRule::All { all } => {
+                CompiledRule::All(all.iter().map(|rule| self.compile_rule(rule)).collect())
+            }
+            MatchingRule::Any { any } => {
+                CompiledRule::Any(any.iter().map(|rule| self.compile_rule(rule)).collect())
+            }
+            MatchingRule::Not { not } => CompiledRule::Not(Box::new(self.compile_rule(not))),
+        }
+    }
+
+    fn add_leaf(&mut self, identifier: &IdWithIdentifier) -> usize {
+        let leaf = self.negated.len();
+        let id = &identifier.id;
+
+        let field = match identifier.kind {
+            ApplicationIdentifier::Title => &mut self.title,
+            ApplicationIdentifier::Exe => &mut self.exe,
+            ApplicationIdentifier::Class => &mut self.class,
+            ApplicationIdentifier::Path => &mut self.path,
+        };
+
+        let strategy = identifier
+            .matching_strategy
+            .clone()
+            .unwrap_or(MatchingStrategy::Legacy);
+
+        let negated = match strategy {
+            MatchingStrategy::Legacy => {
+                match identifier.kind {
+                    ApplicationIdentifier::Title | ApplicationIdentifier::Class => {
+                        let escaped = regex::escape(id);
+                        field.add_pattern(format!("^(?:{escaped})|(?:{escaped})$"), leaf);
+                    }
+                    ApplicationIdentifier::Exe | ApplicationIdentifier::Path => {
+                        field.add_exact(id, leaf);
+                    }
+                }
+
+                false
+            }
+            MatchingStrategy::Equals | MatchingStrategy::DoesNotEqual => {
+                field.add_exact(id, leaf);
+                matches!(strategy, MatchingStrategy::DoesNotEqual)
+            }
+            MatchingStrategy::StartsWith | MatchingStrategy::DoesNotStartWith => {
+                field.add_pattern(format!("^(?:{})", regex::escape(id)), leaf);
+                matches!(strategy, MatchingStrategy::DoesNotStartWith)
+            }
+            MatchingStrategy::EndsWith | MatchingStrategy::DoesNotEndWith => {
+                field.add_pattern(format!("(?:{})$", regex::escape(id)), leaf);
+                matches!(strategy, MatchingStrategy::DoesNotEndWith)
+            }
+            MatchingStrategy::Contains | MatchingStrategy::DoesNotContain => {
+                field.add_pattern(regex::escape(id), leaf);
+                matches!(strategy, MatchingStrategy::DoesNotContain)
+            }
+            MatchingStrategy::Regex => {
+                field.add_pattern(id.clone(), leaf);
+                false
+            }
+            MatchingStrategy::EqualsIgnoreCase => {
+                field.add_exact_ignore_case(id, leaf);
+                false
+            }
+            MatchingStrategy::ContainsIgnoreCase => {
+                field.add_checked(contains_ignore_case, id, leaf);
+                false
+            }
+            MatchingStrategy::Glob => {
+                field.add_pattern(glob_pattern(id), leaf);
+                false
+            }
+            MatchingStrategy::WordBoundary => {
+                field.add_checked(word_match, id, leaf);
+                false
+            }
+        };
+
+        self.negated.push(negated);
+        self.simple.push(None);
+
+        leaf
+    }
+
+    /// Whether each leaf matches the window, with negated leaves already inverted
+    fn leaves(&self, title: &str, exe_name: &str, class: &str, path: &str) -> Vec<bool> {
+        let mut leaves = vec![false; self.negated.len()];
+
+        self.title.matches(title, &mut leaves);
+        self.exe.matches(exe_name, &mut leaves);
+        self.class.matches(class, &mut leaves);
+        self.path.matches(path, &mut leaves);
+
+        for (leaf, negated) in leaves.iter_mut().zip(&self.negated) {
+            *leaf ^= *negated;
+        }
+
+        leaves
+    }
+
+    /// The index of the last rule which matches the window
+    pub fn find(&self, title: &str, exe_name: &str, class: &str, path: &str) -> Option<usize> {
+        let leaves = self.leaves(title, exe_name, class, path);
+
+        let simple = leaves
+            .iter()
+            .zip(&self.simple)
+            .filter_map(|(matched, rule)| if *matched { *rule } else { None })
+            .max();
+
+        // only rules after the last matching simple rule can change the result
+        let complex = self
+            .complex
+            .iter()
+            .rev()
+            .take_while(|(idx, _)| Some(*idx) > simple)
+            .find(|(_, rule)| rule.evaluate(&leaves))
+            .map(|(idx, _)| *idx);
+
+        complex.or(simple)
+    }
+
+    /// The indices of every rule which matches the window, in ascending order
+    pub fn find_all(&self, title: &str, exe_name: &str, class: &str, path: &str) -> Vec<usize> {
+        let leaves = self.leaves(title, exe_name, class, path);
+
+        let mut matched: Vec<usize> = leaves
+            .iter()
+            .zip(&self.simple)
+            .filter_map(|(matched, rule)| if *matched { *rule } else { None })
+            .chain(
+                self.complex
+                    .iter()
+                    .filter(|(_, rule)| rule.evaluate(&leaves))
+                    .map(|(idx, _)| *idx),
+            )
+            .collect();
+
+        matched.sort_unstable();
+        matched
+    }
+}
+
+/// Translate a wildcard pattern into a regex which matches exactly the same values as `glob_match`
+fn glob_pattern(glob: &str) -> String {
+    let mut pattern = String::from("(?s)^");
+    let mut literal = String::new();
+
+    for c in glob.chars() {
+        if c == '*' || c == '?' {
+            pattern.push_str(&regex::escape(&literal));
+            literal.clear();
+            pattern.push_str(if c == '*' { ".*" } else { "." });
+        } else {
+            literal.push(c);
+        }
+    }
+
+    pattern.push_str(&regex::escape(&literal));
+    pattern.push('$');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::should_act;
+    use crate::window::should_act_rule;
+
+    fn identifier(
+        kind: ApplicationIdentifier,
+        id: &str,
+        matching_strategy: MatchingStrategy,
+    ) -> IdWithIdentifier {
+        IdWithIdentifier {
+            kind,
+            id: id.to_string(),
+            matching_strategy: Option::from(matching_strategy),
+        }
+    }
+
+    fn simple(
+        kind: ApplicationIdentifier,
+        id: &str,
+        matching_strategy: MatchingStrategy,
+    ) -> MatchingRule {
+        MatchingRule::Simple(identifier(kind, id, matching_strategy))
+    }
+
+    fn rules() -> Vec<MatchingRule> {
+        use ApplicationIdentifier::*;
+        use MatchingStrategy::*;
+
+        vec![
+            simple(Title, "Mozilla", Legacy),
+            simple(Class, "Chrome_WidgetWin_1", Legacy),
+            simple(Exe, "code.exe", Legacy),
+            simple(Path, "C:\\Windows\\notepad.exe", Legacy),
+            simple(Title, "Settings", Equals),
+            simple(Exe, "slack.exe", DoesNotEqual),
+            simple(Title, "Picture", StartsWith),
+            simple(Class, "Tool", DoesNotStartWith),
+            simple(Path, "steam.exe", EndsWith),
+            simple(Exe, ".exe", DoesNotEndWith),
+            simple(Title, "(1)", Contains),
+            simple(Title, "Private", DoesNotContain),
+            simple(Title, "^Task .* [0-9]+$", Regex),
+            simple(Class, "([unclosed", Regex),
+            simple(Exe, "SPOTIFY.EXE", EqualsIgnoreCase),
+            simple(Title, "DISCORD", ContainsIgnoreCase),
+            simple(Path, "C:\\Program Files\\*\\*.exe", Glob),
+            simple(Title, "?ail", Glob),
+            simple(Title, "Meet", WordBoundary),
+            MatchingRule::Composite(vec![
+                identifier(Exe, "firefox.exe", Equals),
+                identifier(Title, "Picture-in-Picture", Equals),
+            ]),
+            MatchingRule::All {
+                all: vec![
+                    simple(Exe, "explorer.exe", Equals),
+                    MatchingRule::Not {
+                        not: Box::new(simple(Class, "CabinetWClass", Equals)),
+                    },
+                ],
+            },
+            MatchingRule::Any {
+                any: vec![
+                    simple(Title, "Zoom", WordBoundary),
+                    simple(Exe, "teams*", Glob),
+                ],
+            },
+            MatchingRule::Not {
+                not: Box::new(MatchingRule::Any {
+                    any: vec![simple(Path, "C:\\", StartsWith), simple(Title, "", Equals)],
+                }),
+            },
+        ]
+    }
+
+    fn windows() -> Vec<[&'static str; 4]> {
+        vec![
+            ["", "", "", ""],
+            [
+                "Mozilla Firefox",
+                "firefox.exe",
+                "MozillaWindowClass",
+                "C:\\Program Files\\Mozilla Firefox\\firefox.exe",
+            ],
+            [
+                "Picture-in-Picture",
+                "firefox.exe",
+                "MozillaDialogClass",
+                "C:\\Program Files\\Mozilla Firefox\\firefox.exe",
+            ],
+            [
+                "main.rs - Visual Studio Code",
+                "code.exe",
+                "Chrome_WidgetWin_1",
+                "C:\\Users\\user\\AppData\\Local\\Programs\\Microsoft VS Code\\Code.exe",
+            ],
+            [
+                "Untitled - Notepad",
+                "notepad.exe",
+                "Notepad",
+                "C:\\Windows\\notepad.exe",
+            ],
+            [
+                "Settings",
+                "ApplicationFrameHost.exe",
+                "ApplicationFrameWindow",
+                "C:\\Windows\\System32\\ApplicationFrameHost.exe",
+            ],
+            [
+                "Slack | general (1)",
+                "slack.exe",
+                "Chrome_WidgetWin_1",
+                "C:\\Users\\user\\AppData\\Local\\slack\\slack.exe",
+            ],
+            [
+                "Steam",
+                "steam.exe",
+                "SDL_app",
+                "D:\\Games\\Steam\\steam.exe",
+            ],
+            ["Task Manager 42", "Taskmgr", "TaskManagerWindow", ""],
+            [
+                "Spotify Premium",
+                "Spotify.exe",
+                "Chrome_WidgetWin_0",
+                "C:\\Users\\user\\AppData\\Roaming\\Spotify\\Spotify.exe",
+            ],
+            [
+                "#general - Discord",
+                "Discord.exe",
+                "Chrome_WidgetWin_1",
+                "C:\\Users\\user\\AppData\\Local\\Discord\\Discord.exe",
+            ],
+            [
+                "Mail",
+                "HxOutlook.exe",
+                "ToolWindow",
+                "C:\\Windows\\HxOutlook.exe",
+            ],
+            [
+                "Meeting notes",
+                "explorer.exe",
+                "Progman",
+                "C:\\Windows\\explorer.exe",
+            ],
+            [
+                "Documents",
+                "explorer.exe",
+                "CabinetWClass",
+                "C:\\Windows\\explorer.exe",
+            ],
+            [
+                "Google Meet - Private",
+                "teams-preview.exe",
+                "TeamsWebView",
+                "E:\\Teams\\teams-preview.exe",
+            ],
+            [
+                "Zoom Meeting",
+                "Zoom.exe",
+                "ConfMultiTabContentWndClass",
+                "C:\\Users\\user\\AppData\\Roaming\\Zoom\\bin\\Zoom.exe",
+            ],
+        ]
+    }
+
+    fn regex_identifiers(rules: &[MatchingRule]) -> HashMap<String, Regex> {
+        let mut regex_identifiers = HashMap::new();
+
+        for identifier in rules.iter().flat_map(MatchingRule::identifiers) {
+            if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+                if let Ok(re) = Regex::new(&identifier.id) {
+                    regex_identifiers.insert(identifier.id.clone(), re);
+                }
+            }
+        }
+
+        regex_identifiers
+    }
+
+    fn index(rules: &[MatchingRule], individually: bool) -> RuleIndex {
+        let mut index = RuleIndex::new(rules);
+
+        if individually {
+            for field in [
+                &mut index.title,
+                &mut index.exe,
+                &mut index.class,
+                &mut index.path,
+            ] {
+                field.compile_individually();
+            }
+        }
+
+        index
+    }
+
+    fn assert_matches_should_act(individually: bool) {
+        let rules = rules();
+        let regex_identifiers = regex_identifiers(&rules);
+
+        // check every prefix of the list, so that a rule which is shadowed by a later match is
+        // still compared
+        for end in 1..=rules.len() {
+            let rules = &rules[..end];
+            let index = index(rules, individually);
+
+            for [title, exe, class, path] in windows() {
+                assert_eq!(
+                    index
+                        .find(title, exe, class, path)
+                        .map(|idx| rules[idx].clone()),
+                    should_act(title, exe, class, path, rules, &regex_identifiers),
+                    "{title} | {exe} | {class} | {path}"
+                );
+            }
+        }
+
+        let index = index(&rules, individually);
+
+        for [title, exe, class, path] in windows() {
+            let expected: Vec<usize> = rules
+                .iter()
+                .enumerate()
+                .filter(|(_, rule)| {
+                    should_act_rule(title, exe, class, path, rule, &regex_identifiers)
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+
+            assert_eq!(
+                index.find_all(title, exe, class, path),
+                expected,
+                "{title} | {exe} | {class} | {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn index_matches_should_act() {
+        assert_matches_should_act(false);
+    }
+
+    #[test]
+    fn index_matches_should_act_without_a_regex_set() {
+        assert_matches_should_act(true);
+    }
+}
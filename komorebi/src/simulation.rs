@@ -0,0 +1,330 @@
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::Result;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::Rect;
+use crate::core::SocketMessage;
+
+use crate::platform;
+use crate::platform::FakePlatform;
+use crate::platform::FakeWindow;
+use crate::platform::MonitorInfo;
+use crate::platform::Platform;
+use crate::window_manager::State;
+use crate::window_manager::WindowManager;
+use crate::window_manager_event::WindowManagerEvent;
+use crate::ANIMATION_ENABLED;
+use crate::INITIAL_CONFIGURATION_LOADED;
+
+lazy_static! {
+    // the platform backend is global, so only one simulation can run at a time
+    static ref SIMULATION_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A scripted sequence of events and commands to be replayed against virtual monitors
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimulationScript {
+    /// Virtual monitors, in the order that they should be enumerated
+    pub monitors: Vec<VirtualMonitor>,
+    /// Windows which exist on the virtual desktop before the first step is run
+    #[serde(default)]
+    pub windows: Vec<VirtualWindow>,
+    /// Steps to replay in order
+    pub steps: Vec<SimulationStep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VirtualMonitor {
+    /// Display name of the monitor
+    pub name: String,
+    /// Full size of the monitor
+    pub size: Rect,
+    /// Work area of the monitor (default: the full size of the monitor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_area_size: Option<Rect>,
+    /// Number of workspaces to create on the monitor (default: 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspaces: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VirtualWindow {
+    pub hwnd: isize,
+    pub title: String,
+    pub exe: String,
+    pub class: String,
+    /// Initial position of the window (default: an empty Rect)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rect: Option<Rect>,
+}
+
+impl From<&VirtualWindow> for FakeWindow {
+    fn from(value: &VirtualWindow) -> Self {
+        let mut window = Self::new(&value.title, &value.exe, &value.class);
+        window.rect = value.rect.unwrap_or_default();
+        window
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "content")]
+pub enum SimulationStep {
+    /// Create a window on the virtual desktop
+    OpenWindow(VirtualWindow),
+    /// Remove a window from the virtual desktop
+    CloseWindow(isize),
+    /// Change the foreground window of the virtual desktop
+    SetForeground(isize),
+    /// Process a window manager event
+    Event(WindowManagerEvent),
+    /// Process a socket message
+    Message(SocketMessage),
+    /// Assert that the value at a JSON pointer in the current State matches
+    AssertState(StateAssertion),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateAssertion {
+    /// JSON pointer into the serialized State (ie. /monitors/elements/0/workspaces/focused)
+    pub pointer: String,
+    /// The expected value at the JSON pointer
+    #[schemars(schema_with = "any_value")]
+    pub value: serde_json::Value,
+}
+
+fn any_value(_: &mut SchemaGenerator) -> Schema {
+    Schema::Bool(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationFailure {
+    pub step: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Errors returned by the window manager while handling a step
+    pub errors: Vec<SimulationFailure>,
+    /// State assertions which did not hold
+    pub failures: Vec<SimulationFailure>,
+    /// The State after the last step was replayed
+    pub state: serde_json::Value,
+}
+
+impl SimulationReport {
+    /// Whether every step was handled without an error and every state assertion held
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// Restores the global state replaced for a simulation, even if replaying the script panics
+struct GlobalStateGuard {
+    platform: Option<Arc<dyn Platform>>,
+    animation_enabled: bool,
+    initial_configuration_loaded: bool,
+}
+
+impl Drop for GlobalStateGuard {
+    fn drop(&mut self) {
+        if let Some(platform) = self.platform.take() {
+            platform::set_current(platform);
+        }
+
+        ANIMATION_ENABLED.store(self.animation_enabled, Ordering::SeqCst);
+        INITIAL_CONFIGURATION_LOADED.store(self.initial_configuration_loaded, Ordering::SeqCst);
+    }
+}
+
+impl SimulationScript {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn run(&self) -> Result<SimulationReport> {
+        let _guard = SIMULATION_LOCK.lock();
+        self.run_exclusive()
+    }
+
+    /// Must only be called while holding `SIMULATION_LOCK`
+    fn run_exclusive(&self) -> Result<SimulationReport> {
+        let desktop = Arc::new(FakePlatform::new());
+
+        for (idx, monitor) in self.monitors.iter().enumerate() {
+            desktop.add_monitor(MonitorInfo {
+                id: isize::try_from(idx)? + 1,
+                name: monitor.name.clone(),
+                device: String::from("VIRTUAL"),
+                device_id: format!("VIRTUAL-{idx}"),
+                size: monitor.size,
+                work_area_size: monitor.work_area_size.unwrap_or(monitor.size),
+            });
+        }
+
+        for window in &self.windows {
+            desktop.add_window(window.hwnd, FakeWindow::from(window));
+        }
+
+        let _restore = GlobalStateGuard {
+            platform: Option::from(platform::set_current(desktop.clone())),
+            animation_enabled: ANIMATION_ENABLED.swap(false, Ordering::SeqCst),
+            // there is no configuration file to wait for, so workspaces are laid out immediately
+            initial_configuration_loaded: INITIAL_CONFIGURATION_LOADED.swap(true, Ordering::SeqCst),
+        };
+
+        self.replay(&desktop)
+    }
+
+    fn replay(&self, desktop: &FakePlatform) -> Result<SimulationReport> {
+        let (_, incoming) = crossbeam_channel::unbounded();
+        let data_dir =
+            std::env::temp_dir().join(format!("komorebi-simulation-{}", std::process::id()));
+        std::fs::create_dir_all(&data_dir)?;
+
+        let mut wm = WindowManager::with_data_dir(incoming, &data_dir)?;
+        wm.virtual_desktop_id = None;
+
+        platform::load_monitor_information(&mut wm.monitors)?;
+
+        for (monitor, config) in wm.monitors_mut().iter_mut().zip(&self.monitors) {
+            monitor.ensure_workspace_count(config.workspaces.unwrap_or(1));
+        }
+
+        let mut errors = vec![];
+        let mut failures = vec![];
+
+        for (step, action) in self.steps.iter().enumerate() {
+            let result = match action {
+                SimulationStep::OpenWindow(window) => {
+                    desktop.add_window(window.hwnd, FakeWindow::from(window));
+                    Ok(())
+                }
+                SimulationStep::CloseWindow(hwnd) => {
+                    desktop.remove_window(*hwnd);
+                    Ok(())
+                }
+                SimulationStep::SetForeground(hwnd) => {
+                    desktop.set_foreground_window(*hwnd);
+                    Ok(())
+                }
+                SimulationStep::Event(event) => wm.process_event(*event),
+                SimulationStep::Message(message) => {
+                    wm.process_command(message.clone(), std::io::sink())
+                }
+                SimulationStep::AssertState(assertion) => {
+                    let state = serde_json::to_value(State::from(&wm))?;
+                    match state.pointer(&assertion.pointer) {
+                        Some(value) if *value == assertion.value => {}
+                        Some(value) => failures.push(SimulationFailure {
+                            step,
+                            message: format!(
+                                "expected {} at {} but found {}",
+                                assertion.value, assertion.pointer, value
+                            ),
+                        }),
+                        None => failures.push(SimulationFailure {
+                            step,
+                            message: format!("there is no value at {}", assertion.pointer),
+                        }),
+                    }
+
+                    Ok(())
+                }
+            };
+
+            if let Err(error) = result {
+                tracing::warn!("step {step} returned an error: {error}");
+                errors.push(SimulationFailure {
+                    step,
+                    message: error.to_string(),
+                });
+            }
+        }
+
+        let state = serde_json::to_value(State::from(&wm))?;
+
+        drop(wm);
+        if let Err(error) = std::fs::remove_dir_all(&data_dir) {
+            tracing::debug!("could not remove simulation data directory: {error}");
+        }
+
+        Ok(SimulationReport {
+            errors,
+            failures,
+            state,
+        })
+    }
+}
+
+/// Replays the simulation script at the given path and returns a report of the run
+pub fn run_file(path: &Path) -> Result<SimulationReport> {
+    if !path.is_file() {
+        return Err(anyhow!("{} is not a file", path.display()));
+    }
+
+    SimulationScript::from_path(path)?.run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_script_passes() -> Result<()> {
+        let script: SimulationScript =
+            serde_json::from_str(include_str!("../../docs/simulation.example.json"))?;
+
+        let report = script.run()?;
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert!(report.passed());
+
+        Ok(())
+    }
+
+    #[test]
+    fn global_state_is_restored_after_a_run() -> Result<()> {
+        let script: SimulationScript =
+            serde_json::from_str(include_str!("../../docs/simulation.example.json"))?;
+
+        let _guard = SIMULATION_LOCK.lock();
+        let before = platform::current();
+        ANIMATION_ENABLED.store(true, Ordering::SeqCst);
+        INITIAL_CONFIGURATION_LOADED.store(false, Ordering::SeqCst);
+
+        script.run_exclusive()?;
+
+        assert!(ANIMATION_ENABLED.load(Ordering::SeqCst));
+        assert!(!INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst));
+        assert!(Arc::ptr_eq(&before, &platform::current()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors_fail_the_report() {
+        let report = SimulationReport {
+            errors: vec![SimulationFailure {
+                step: 0,
+                message: String::from("there is no window"),
+            }],
+            failures: vec![],
+            state: serde_json::Value::Null,
+        };
+
+        assert!(!report.passed());
+    }
+}
@@ -1,24 +1,31 @@
+#[cfg(windows)]
 mod stackbar;
 
+#[cfg(windows)]
 use crate::container::Container;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
+#[cfg(windows)]
 use crate::stackbar_manager::stackbar::Stackbar;
+#[cfg(windows)]
 use crate::WindowManager;
+#[cfg(windows)]
 use crate::WindowsApi;
-use crate::DEFAULT_CONTAINER_PADDING;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicCell;
-use crossbeam_utils::atomic::AtomicConsume;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
+#[cfg(windows)]
 use std::collections::hash_map::Entry;
+#[cfg(windows)]
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU32;
+#[cfg(windows)]
 use std::sync::atomic::Ordering;
+#[cfg(windows)]
 use std::sync::Arc;
 use std::sync::OnceLock;
 
@@ -34,8 +41,12 @@ pub static STACKBAR_MODE: AtomicCell<StackbarMode> = AtomicCell::new(StackbarMod
 pub static STACKBAR_TEMPORARILY_DISABLED: AtomicBool = AtomicBool::new(false);
 
 lazy_static! {
-    pub static ref STACKBAR_STATE: Mutex<HashMap<String, Stackbar>> = Mutex::new(HashMap::new());
     pub static ref STACKBAR_FONT_FAMILY: Mutex<Option<String>> = Mutex::new(None);
+}
+
+#[cfg(windows)]
+lazy_static! {
+    pub static ref STACKBAR_STATE: Mutex<HashMap<String, Stackbar>> = Mutex::new(HashMap::new());
     static ref STACKBARS_MONITORS: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
     static ref STACKBARS_CONTAINERS: Mutex<HashMap<isize, Container>> = Mutex::new(HashMap::new());
 }
@@ -52,6 +63,7 @@ fn event_tx() -> Sender<Notification> {
     channel().0.clone()
 }
 
+#[cfg(windows)]
 fn event_rx() -> Receiver<Notification> {
     channel().1.clone()
 }
@@ -70,6 +82,7 @@ pub fn should_have_stackbar(window_count: usize) -> bool {
     }
 }
 
+#[cfg(windows)]
 pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
         match handle_notifications(wm.clone()) {
@@ -83,6 +96,7 @@ pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     });
 }
 
+#[cfg(windows)]
 pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
     tracing::info!("listening");
 
@@ -169,9 +183,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     stackbars.remove(id);
                 }
 
-                let container_padding = ws
-                    .container_padding()
-                    .unwrap_or_else(|| DEFAULT_CONTAINER_PADDING.load_consume());
+                let (_, container_padding) = ws.gaps();
 
                 'containers: for container in ws.containers_mut() {
                     let should_add_stackbar = match STACKBAR_MODE.load() {
@@ -208,7 +220,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                         container.focused_window().copied().unwrap_or_default().hwnd,
                     )?;
 
-                    stackbar.update(container_padding, container, &rect)?;
+                    stackbar.update(container_padding.top, container, &rect)?;
                 }
             }
         }
@@ -8,9 +8,12 @@ use crate::core::BorderImplementation;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
+use crate::external_layout::EXTERNAL_LAYOUT_TIMEOUT;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator;
+use crate::platform;
 use crate::ring::Ring;
+use crate::rule_evaluator::RuleSet;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
@@ -24,30 +27,30 @@ use crate::transparency_manager;
 use crate::window;
 use crate::window_manager::WindowManager;
 use crate::window_manager_event::WindowManagerEvent;
-use crate::windows_api::WindowsApi;
 use crate::workspace::Workspace;
+use crate::workspace_transition::WORKSPACE_TRANSITION;
 use crate::CrossBoundaryBehaviour;
+use crate::NavigationBehaviour;
 use crate::ANIMATION_DURATION;
 use crate::ANIMATION_ENABLED;
 use crate::ANIMATION_FPS;
+use crate::ANIMATION_OVERRIDES;
 use crate::ANIMATION_STYLE;
 use crate::DATA_DIR;
 use crate::DEFAULT_CONTAINER_PADDING;
+use crate::DEFAULT_INNER_GAP;
+use crate::DEFAULT_OUTER_GAP;
+use crate::DEFAULT_SMART_GAPS;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::DISPLAY_INDEX_PREFERENCES;
-use crate::FLOATING_APPLICATIONS;
+use crate::FLOATING_PLACEMENT_RULES;
 use crate::HIDING_BEHAVIOUR;
-use crate::IGNORE_IDENTIFIERS;
-use crate::LAYERED_WHITELIST;
-use crate::MANAGE_IDENTIFIERS;
+use crate::MANAGE_ACTION_RULES;
 use crate::MONITOR_INDEX_PREFERENCES;
-use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
-use crate::REGEX_IDENTIFIERS;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
-use crate::TRANSPARENCY_BLACKLIST;
-use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WINDOWS_11;
+use crate::WINDOW_SIZE_CONSTRAINTS;
 use crate::WORKSPACE_MATCHING_RULES;
 
 use crate::config_generation::WorkspaceMatchingRule;
@@ -57,18 +60,26 @@ use crate::core::config_generation::ApplicationOptions;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
 use crate::core::resolve_home_path;
+use crate::core::AnimationOperation;
+use crate::core::AnimationOverride;
 use crate::core::AnimationStyle;
 use crate::core::BorderStyle;
 use crate::core::DefaultLayout;
+use crate::core::FloatingPlacement;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::LayoutOptions;
+use crate::core::ManageAction;
 use crate::core::MoveBehaviour;
 use crate::core::OperationBehaviour;
 use crate::core::Rect;
+use crate::core::SidePadding;
 use crate::core::SocketMessage;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowManagementBehaviour;
+use crate::core::WindowSizeConstraints;
+use crate::core::WorkspaceTransition;
 use color_eyre::Result;
 use crossbeam_channel::Receiver;
 use hotwatch::EventKind;
@@ -82,10 +93,16 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::io::Write;
+#[cfg(not(windows))]
+use std::os::unix::net::UnixListener;
+#[cfg(not(windows))]
+use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+#[cfg(windows)]
 use uds_windows::UnixListener;
+#[cfg(windows)]
 use uds_windows::UnixStream;
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -109,6 +126,9 @@ pub struct WorkspaceConfig {
     /// Layout (default: BSP)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout: Option<DefaultLayout>,
+    /// Layout-specific options (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_options: Option<LayoutOptions>,
     /// END OF LIFE FEATURE: Custom Layout (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_layout: Option<PathBuf>,
@@ -118,12 +138,30 @@ pub struct WorkspaceConfig {
     /// END OF LIFE FEATURE: Custom layout rules (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_layout_rules: Option<HashMap<usize, PathBuf>>,
+    /// The name of a registered external layout to use instead of the layout (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_layout: Option<String>,
+    /// Layout rules based on the monitor the workspace is on, the first matching rule sets the
+    /// layout and container count based layout rules take precedence over it once their
+    /// thresholds are met (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor_layout_rules: Option<Vec<MonitorLayoutRule>>,
     /// Container padding (default: global)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub container_padding: Option<i32>,
     /// Container padding (default: global)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace_padding: Option<i32>,
+    /// Gap between adjacent containers, replacing the container padding when set (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inner_gap: Option<i32>,
+    /// Gap between the containers and each edge of the work area, replacing the workspace padding
+    /// when set (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outer_gap: Option<SidePadding>,
+    /// Remove all padding and gaps when the workspace holds a single container (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_gaps: Option<bool>,
     /// Initial workspace application rules
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_workspace_rules: Option<Vec<MatchingRule>>,
@@ -150,7 +188,7 @@ impl From<&Workspace> for WorkspaceConfig {
                 Layout::Default(value) => {
                     layout_rules.insert(*threshold, *value);
                 }
-                Layout::Custom(_) => {}
+                Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {}
             }
         }
 
@@ -181,14 +219,32 @@ impl From<&Workspace> for WorkspaceConfig {
             layout: match value.layout() {
                 Layout::Default(layout) => Option::from(*layout),
                 // TODO: figure out how we might resolve file references in the future
-                Layout::Custom(_) => None,
+                Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => None,
             },
+            layout_options: value.layout_options(),
             custom_layout: None,
             layout_rules: Option::from(layout_rules),
             // TODO: figure out how we might resolve file references in the future
             custom_layout_rules: None,
+            external_layout: match value.layout() {
+                Layout::External(name) => Option::from(name.clone()),
+                Layout::Default(_) | Layout::Custom(_) | Layout::CustomTree(_) => None,
+            },
+            monitor_layout_rules: if value.monitor_layout_rules().is_empty() {
+                None
+            } else {
+                Option::from(value.monitor_layout_rules().clone())
+            },
             container_padding,
             workspace_padding,
+            inner_gap: value
+                .inner_gap()
+                .filter(|inner_gap| Some(*inner_gap) != *DEFAULT_INNER_GAP.lock()),
+            outer_gap: value
+                .outer_gap()
+                .filter(|outer_gap| Some(*outer_gap) != *DEFAULT_OUTER_GAP.lock()),
+            smart_gaps: (value.smart_gaps() != DEFAULT_SMART_GAPS.load(Ordering::SeqCst))
+                .then_some(value.smart_gaps()),
             initial_workspace_rules: None,
             workspace_rules: None,
             apply_window_based_work_area_offset: Some(value.apply_window_based_work_area_offset()),
@@ -198,6 +254,98 @@ impl From<&Workspace> for WorkspaceConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub enum MonitorOrientation {
+    /// The monitor is wider than it is tall
+    Landscape,
+    /// The monitor is taller than it is wide
+    Portrait,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct WindowSizeConstraintRule {
+    /// The windows which these constraints apply to
+    pub matching_rule: MatchingRule,
+    /// Size limits for the matching windows
+    #[serde(flatten)]
+    pub constraints: WindowSizeConstraints,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct FloatingPlacementRule {
+    /// The windows which this placement applies to
+    pub matching_rule: MatchingRule,
+    /// Where the matching windows are placed when they start floating
+    pub placement: FloatingPlacement,
+    /// Place the matching windows on the monitor with this name, device or device id instead of
+    /// the focused monitor (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ManageActionRule {
+    /// The windows which these actions apply to
+    pub matching_rule: MatchingRule,
+    /// Actions taken in order when a matching window is first managed
+    pub actions: Vec<ManageAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct MonitorLayoutRule {
+    /// Layout to use when every condition of this rule matches
+    pub layout: DefaultLayout,
+    /// Match monitors with this orientation (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<MonitorOrientation>,
+    /// Match monitors with a width to height ratio of at least this value (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_aspect_ratio: Option<f32>,
+    /// Match monitors with a width to height ratio of at most this value (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_aspect_ratio: Option<f32>,
+    /// Match monitors with a work area at least this many pixels wide (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_work_area_width: Option<i32>,
+    /// Match monitors with a work area at most this many pixels wide (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_work_area_width: Option<i32>,
+    /// Match the monitor with this name (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor_name: Option<String>,
+}
+
+impl MonitorLayoutRule {
+    /// Whether every condition of this rule matches a monitor with the given name, size and work
+    /// area; a rule without any conditions matches every monitor
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn matches(&self, name: &str, size: &Rect, work_area: &Rect) -> bool {
+        let orientation = if size.bottom > size.right {
+            MonitorOrientation::Portrait
+        } else {
+            MonitorOrientation::Landscape
+        };
+
+        let aspect_ratio = if size.bottom == 0 {
+            0.0
+        } else {
+            size.right as f32 / size.bottom as f32
+        };
+
+        self.orientation.is_none_or(|o| o == orientation)
+            && self.min_aspect_ratio.is_none_or(|min| aspect_ratio >= min)
+            && self.max_aspect_ratio.is_none_or(|max| aspect_ratio <= max)
+            && self
+                .min_work_area_width
+                .is_none_or(|min| work_area.right >= min)
+            && self
+                .max_work_area_width
+                .is_none_or(|max| work_area.right <= max)
+            && self.monitor_name.as_ref().is_none_or(|n| n == name)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MonitorConfig {
     /// Workspace configurations
@@ -257,6 +405,9 @@ pub struct StaticConfig {
     /// Determine what happens when an action is called on a window at a monitor boundary (default: Monitor)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cross_boundary_behaviour: Option<CrossBoundaryBehaviour>,
+    /// Determine how the container in a direction is found when focusing or moving containers (default: Layout)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navigation_behaviour: Option<NavigationBehaviour>,
     /// Determine what happens when commands are sent while an unmanaged window is in the foreground (default: Op)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unmanaged_window_operation_behaviour: Option<OperationBehaviour>,
@@ -310,6 +461,17 @@ pub struct StaticConfig {
     /// Global default container padding (default: 10)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_container_padding: Option<i32>,
+    /// Global default gap between adjacent containers, replacing the container padding when set
+    /// (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_inner_gap: Option<i32>,
+    /// Global default gap between the containers and each edge of the work area, replacing the
+    /// workspace padding when set (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_outer_gap: Option<SidePadding>,
+    /// Remove all padding and gaps on workspaces which hold a single container (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_gaps: Option<bool>,
     /// Monitor and workspace configurations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub monitors: Option<Vec<MonitorConfig>>,
@@ -341,6 +503,18 @@ pub struct StaticConfig {
     /// Identify applications that send EVENT_OBJECT_NAMECHANGE on launch (very rare)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object_name_change_applications: Option<Vec<MatchingRule>>,
+    /// Size constraints for the windows of individual applications, the first matching rule is
+    /// used (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_size_constraints: Option<Vec<WindowSizeConstraintRule>>,
+    /// Placements for the windows of individual applications when they start floating, the first
+    /// matching rule is used (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_placement: Option<Vec<FloatingPlacementRule>>,
+    /// Actions taken on the windows of individual applications when they are first managed, the
+    /// actions of every matching rule are taken (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_actions: Option<Vec<ManageActionRule>>,
     /// Set monitor index preferences
     #[serde(skip_serializing_if = "Option::is_none")]
     pub monitor_index_preferences: Option<HashMap<usize, Rect>>,
@@ -362,6 +536,10 @@ pub struct StaticConfig {
     /// How long to wait when compensating for slow applications, in milliseconds (default: 20)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slow_application_compensation_time: Option<u64>,
+    /// How long to wait for an external layout to respond before falling back to BSP, in
+    /// milliseconds (default: 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_layout_timeout: Option<u64>,
     /// Komorebi status bar configuration files for multiple instances on different monitors
     #[serde(skip_serializing_if = "Option::is_none")]
     // this option is a little special because it is only consumed by komorebic
@@ -378,7 +556,27 @@ pub struct AnimationsConfig {
     style: Option<AnimationStyle>,
     /// Set the animation FPS (default: 60)
     fps: Option<u64>,
+    /// Override the animation settings for tile reflows after windows are opened or closed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reflow: Option<AnimationOverride>,
+    /// Override the animation settings for windows moving between containers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    movement: Option<AnimationOverride>,
+    /// Override the animation settings for workspace switches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_switch: Option<AnimationOverride>,
+    /// Override the animation settings for windows and workspaces moving between monitors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    monitor_move: Option<AnimationOverride>,
+    /// Override the animation settings for retiling after the connected displays change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_change: Option<AnimationOverride>,
+    /// Transition between workspaces on the same monitor, using the workspace switch animation
+    /// settings (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_transition: Option<WorkspaceTransition>,
 }
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "palette")]
 pub enum KomorebiTheme {
@@ -565,6 +763,7 @@ impl From<&WindowManager> for StaticConfig {
             float_override: Option::from(value.window_management_behaviour.float_override),
             cross_monitor_move_behaviour: Option::from(value.cross_monitor_move_behaviour),
             cross_boundary_behaviour: Option::from(value.cross_boundary_behaviour),
+            navigation_behaviour: Option::from(value.navigation_behaviour),
             unmanaged_window_operation_behaviour: Option::from(
                 value.unmanaged_window_operation_behaviour,
             ),
@@ -593,6 +792,9 @@ impl From<&WindowManager> for StaticConfig {
             default_container_padding: Option::from(
                 DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst),
             ),
+            default_inner_gap: *DEFAULT_INNER_GAP.lock(),
+            default_outer_gap: *DEFAULT_OUTER_GAP.lock(),
+            smart_gaps: Option::from(DEFAULT_SMART_GAPS.load(Ordering::SeqCst)),
             monitors: Option::from(monitors),
             window_hiding_behaviour: Option::from(*HIDING_BEHAVIOUR.lock()),
             global_work_area_offset: value.work_area_offset,
@@ -603,6 +805,9 @@ impl From<&WindowManager> for StaticConfig {
             tray_and_multi_window_applications: None,
             layered_applications: None,
             object_name_change_applications: None,
+            window_size_constraints: Option::from(WINDOW_SIZE_CONSTRAINTS.lock().clone()),
+            floating_placement: Option::from(FLOATING_PLACEMENT_RULES.lock().clone()),
+            manage_actions: Option::from(MANAGE_ACTION_RULES.lock().clone()),
             monitor_index_preferences: Option::from(MONITOR_INDEX_PREFERENCES.lock().clone()),
             display_index_preferences: Option::from(DISPLAY_INDEX_PREFERENCES.lock().clone()),
             stackbar: None,
@@ -611,7 +816,10 @@ impl From<&WindowManager> for StaticConfig {
             slow_application_compensation_time: Option::from(
                 SLOW_APPLICATION_COMPENSATION_TIME.load(Ordering::SeqCst),
             ),
-            slow_application_identifiers: Option::from(SLOW_APPLICATION_IDENTIFIERS.lock().clone()),
+            slow_application_identifiers: Option::from(
+                SLOW_APPLICATION_IDENTIFIERS.lock().to_vec(),
+            ),
+            external_layout_timeout: Option::from(EXTERNAL_LAYOUT_TIMEOUT.load(Ordering::SeqCst)),
             bar_configurations: None,
         }
     }
@@ -644,11 +852,49 @@ impl StaticConfig {
         }
 
         if let Some(animations) = &self.animation {
+            let override_styles = [
+                animations.reflow,
+                animations.movement,
+                animations.workspace_switch,
+                animations.monitor_move,
+                animations.display_change,
+            ]
+            .into_iter()
+            .flatten()
+            .map(|animation_override| animation_override.style);
+
+            for style in std::iter::once(animations.style)
+                .chain(override_styles)
+                .flatten()
+            {
+                style.validate()?;
+            }
+
             ANIMATION_ENABLED.store(animations.enabled, Ordering::SeqCst);
             ANIMATION_DURATION.store(animations.duration.unwrap_or(250), Ordering::SeqCst);
             ANIMATION_FPS.store(animations.fps.unwrap_or(60), Ordering::SeqCst);
             let mut animation_style = ANIMATION_STYLE.lock();
             *animation_style = animations.style.unwrap_or(AnimationStyle::Linear);
+
+            *WORKSPACE_TRANSITION.lock() = animations.workspace_transition;
+
+            let mut animation_overrides = ANIMATION_OVERRIDES.lock();
+            animation_overrides.clear();
+
+            for (operation, animation_override) in [
+                (AnimationOperation::Reflow, animations.reflow),
+                (AnimationOperation::Movement, animations.movement),
+                (
+                    AnimationOperation::WorkspaceSwitch,
+                    animations.workspace_switch,
+                ),
+                (AnimationOperation::MonitorMove, animations.monitor_move),
+                (AnimationOperation::DisplayChange, animations.display_change),
+            ] {
+                if let Some(animation_override) = animation_override {
+                    animation_overrides.insert(operation, animation_override);
+                }
+            }
         }
 
         if let Some(container) = self.default_container_padding {
@@ -659,6 +905,15 @@ impl StaticConfig {
             DEFAULT_WORKSPACE_PADDING.store(workspace, Ordering::SeqCst);
         }
 
+        EXTERNAL_LAYOUT_TIMEOUT.store(
+            self.external_layout_timeout.unwrap_or(100),
+            Ordering::SeqCst,
+        );
+
+        *DEFAULT_INNER_GAP.lock() = self.default_inner_gap;
+        *DEFAULT_OUTER_GAP.lock() = self.default_outer_gap;
+        DEFAULT_SMART_GAPS.store(self.smart_gaps.unwrap_or_default(), Ordering::SeqCst);
+
         border_manager::BORDER_WIDTH.store(self.border_width.unwrap_or(8), Ordering::SeqCst);
         border_manager::BORDER_OFFSET.store(self.border_offset.unwrap_or(-1), Ordering::SeqCst);
 
@@ -718,60 +973,6 @@ impl StaticConfig {
         transparency_manager::TRANSPARENCY_ALPHA
             .store(self.transparency_alpha.unwrap_or(200), Ordering::SeqCst);
 
-        let mut ignore_identifiers = IGNORE_IDENTIFIERS.lock();
-        let mut regex_identifiers = REGEX_IDENTIFIERS.lock();
-        let mut manage_identifiers = MANAGE_IDENTIFIERS.lock();
-        let mut tray_and_multi_window_identifiers = TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock();
-        let mut object_name_change_identifiers = OBJECT_NAME_CHANGE_ON_LAUNCH.lock();
-        let mut layered_identifiers = LAYERED_WHITELIST.lock();
-        let mut transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
-        let mut slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
-        let mut floating_applications = FLOATING_APPLICATIONS.lock();
-
-        if let Some(rules) = &mut self.ignore_rules {
-            populate_rules(rules, &mut ignore_identifiers, &mut regex_identifiers)?;
-        }
-
-        if let Some(rules) = &mut self.floating_applications {
-            populate_rules(rules, &mut floating_applications, &mut regex_identifiers)?;
-        }
-
-        if let Some(rules) = &mut self.manage_rules {
-            populate_rules(rules, &mut manage_identifiers, &mut regex_identifiers)?;
-        }
-
-        if let Some(rules) = &mut self.object_name_change_applications {
-            populate_rules(
-                rules,
-                &mut object_name_change_identifiers,
-                &mut regex_identifiers,
-            )?;
-        }
-
-        if let Some(rules) = &mut self.layered_applications {
-            populate_rules(rules, &mut layered_identifiers, &mut regex_identifiers)?;
-        }
-
-        if let Some(rules) = &mut self.tray_and_multi_window_applications {
-            populate_rules(
-                rules,
-                &mut tray_and_multi_window_identifiers,
-                &mut regex_identifiers,
-            )?;
-        }
-
-        if let Some(rules) = &mut self.transparency_ignore_rules {
-            populate_rules(rules, &mut transparency_blacklist, &mut regex_identifiers)?;
-        }
-
-        if let Some(rules) = &mut self.slow_application_identifiers {
-            populate_rules(
-                rules,
-                &mut slow_application_identifiers,
-                &mut regex_identifiers,
-            )?;
-        }
-
         if let Some(stackbar) = &self.stackbar {
             if let Some(height) = &stackbar.height {
                 STACKBAR_TAB_HEIGHT.store(*height, Ordering::SeqCst);
@@ -956,54 +1157,9 @@ impl StaticConfig {
             );
         }
 
-        if let Some(path) = &self.app_specific_configuration_path {
-            let path = resolve_home_path(path)?;
-            let content = std::fs::read_to_string(path)?;
-            let asc = ApplicationConfigurationGenerator::load(&content)?;
-
-            for mut entry in asc {
-                if let Some(rules) = &mut entry.ignore_identifiers {
-                    populate_rules(rules, &mut ignore_identifiers, &mut regex_identifiers)?;
-                }
-
-                if let Some(ref options) = entry.options {
-                    let options = options.clone();
-                    for o in options {
-                        match o {
-                            ApplicationOptions::ObjectNameChange => {
-                                populate_option(
-                                    &mut entry,
-                                    &mut object_name_change_identifiers,
-                                    &mut regex_identifiers,
-                                )?;
-                            }
-                            ApplicationOptions::Layered => {
-                                populate_option(
-                                    &mut entry,
-                                    &mut layered_identifiers,
-                                    &mut regex_identifiers,
-                                )?;
-                            }
-                            ApplicationOptions::TrayAndMultiWindow => {
-                                populate_option(
-                                    &mut entry,
-                                    &mut tray_and_multi_window_identifiers,
-                                    &mut regex_identifiers,
-                                )?;
-                            }
-                            ApplicationOptions::Force => {
-                                populate_option(
-                                    &mut entry,
-                                    &mut manage_identifiers,
-                                    &mut regex_identifiers,
-                                )?;
-                            }
-                            ApplicationOptions::BorderOverflow => {} // deprecated
-                        }
-                    }
-                }
-            }
-        }
+        let mut rule_set = RuleSet::current();
+        self.populate_rule_set(&mut rule_set)?;
+        rule_set.store();
 
         Ok(())
     }
@@ -1041,6 +1197,131 @@ impl StaticConfig {
         Ok(value)
     }
 
+    /// Add the rules from this configuration and its application specific configuration file to
+    /// a set of rules. The window size constraint, floating placement and manage action rules are
+    /// replaced rather than extended
+    fn populate_rule_set(&self, rule_set: &mut RuleSet) -> Result<()> {
+        let regex_identifiers = &mut rule_set.regex_identifiers;
+
+        for (rules, identifiers) in [
+            (&self.ignore_rules, &mut rule_set.ignore_identifiers),
+            (
+                &self.floating_applications,
+                &mut rule_set.floating_applications,
+            ),
+            (&self.manage_rules, &mut rule_set.manage_identifiers),
+            (
+                &self.object_name_change_applications,
+                &mut rule_set.object_name_change_on_launch,
+            ),
+            (&self.layered_applications, &mut rule_set.layered_whitelist),
+            (
+                &self.tray_and_multi_window_applications,
+                &mut rule_set.tray_and_multi_window_identifiers,
+            ),
+            (
+                &self.transparency_ignore_rules,
+                &mut rule_set.transparency_blacklist,
+            ),
+            (
+                &self.slow_application_identifiers,
+                &mut rule_set.slow_application_identifiers,
+            ),
+        ] {
+            if let Some(rules) = rules {
+                populate_rules(&mut rules.clone(), identifiers, regex_identifiers)?;
+            }
+        }
+
+        rule_set.window_size_constraints.clear();
+
+        for rule in self.window_size_constraints.iter().flatten() {
+            rule_set
+                .window_size_constraints
+                .push(WindowSizeConstraintRule {
+                    matching_rule: populate_rule(&rule.matching_rule, regex_identifiers)?,
+                    constraints: rule.constraints,
+                });
+        }
+
+        rule_set.floating_placement.clear();
+
+        for rule in self.floating_placement.iter().flatten() {
+            rule_set.floating_placement.push(FloatingPlacementRule {
+                matching_rule: populate_rule(&rule.matching_rule, regex_identifiers)?,
+                placement: rule.placement,
+                monitor: rule.monitor.clone(),
+            });
+        }
+
+        rule_set.manage_actions.clear();
+
+        for rule in self.manage_actions.iter().flatten() {
+            rule_set.manage_actions.push(ManageActionRule {
+                matching_rule: populate_rule(&rule.matching_rule, regex_identifiers)?,
+                actions: rule.actions.clone(),
+            });
+        }
+
+        if let Some(path) = &self.app_specific_configuration_path {
+            let content = std::fs::read_to_string(resolve_home_path(path)?)?;
+            let asc = ApplicationConfigurationGenerator::load(&content)?;
+
+            for mut entry in asc {
+                if let Some(rules) = &mut entry.ignore_identifiers {
+                    populate_rules(rules, &mut rule_set.ignore_identifiers, regex_identifiers)?;
+                }
+
+                for option in entry.options.clone().unwrap_or_default() {
+                    let identifiers = match option {
+                        ApplicationOptions::ObjectNameChange => {
+                            &mut rule_set.object_name_change_on_launch
+                        }
+                        ApplicationOptions::Layered => &mut rule_set.layered_whitelist,
+                        ApplicationOptions::TrayAndMultiWindow => {
+                            &mut rule_set.tray_and_multi_window_identifiers
+                        }
+                        ApplicationOptions::Force => &mut rule_set.manage_identifiers,
+                        ApplicationOptions::BorderOverflow => continue, // deprecated
+                    };
+
+                    populate_option(&mut entry, identifiers, regex_identifiers)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The built-in rules extended with the rules from this configuration, as they would be loaded
+    /// by `komorebi` on startup, for evaluating windows without a running window manager
+    pub fn rule_set(&self) -> Result<RuleSet> {
+        let mut rule_set = RuleSet::current();
+        self.populate_rule_set(&mut rule_set)?;
+
+        rule_set.workspace_rules.clear();
+
+        for (i, monitor) in self.monitors.iter().flatten().enumerate() {
+            for (j, ws) in monitor.workspaces.iter().enumerate() {
+                for (rules, initial_only) in [
+                    (&ws.workspace_rules, false),
+                    (&ws.initial_workspace_rules, true),
+                ] {
+                    for r in rules.iter().flatten() {
+                        rule_set.workspace_rules.push(WorkspaceMatchingRule {
+                            monitor_index: i,
+                            workspace_index: j,
+                            matching_rule: populate_rule(r, &mut rule_set.regex_identifiers)?,
+                            initial_only,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(rule_set)
+    }
+
     #[allow(clippy::too_many_lines)]
     pub fn preload(
         path: &PathBuf,
@@ -1089,6 +1370,9 @@ impl StaticConfig {
             cross_boundary_behaviour: value
                 .cross_boundary_behaviour
                 .unwrap_or(CrossBoundaryBehaviour::Monitor),
+            navigation_behaviour: value
+                .navigation_behaviour
+                .unwrap_or(NavigationBehaviour::Layout),
             unmanaged_window_operation_behaviour: value
                 .unmanaged_window_operation_behaviour
                 .unwrap_or(OperationBehaviour::Op),
@@ -1099,12 +1383,13 @@ impl StaticConfig {
             has_pending_raise_op: false,
             pending_move_op: None,
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
+            data_dir: DATA_DIR.clone(),
         };
 
         match value.focus_follows_mouse {
-            None => WindowsApi::disable_focus_follows_mouse()?,
+            None => platform::current().disable_focus_follows_mouse()?,
             Some(FocusFollowsMouseImplementation::Windows) => {
-                WindowsApi::enable_focus_follows_mouse()?;
+                platform::current().enable_focus_follows_mouse()?;
             }
             Some(FocusFollowsMouseImplementation::Komorebi) => {}
         };
@@ -1157,6 +1442,8 @@ impl StaticConfig {
                                 .expect("no static workspace config"),
                         )?;
                     }
+
+                    m.apply_monitor_layout_rules();
                 }
 
                 let mut workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
@@ -1220,6 +1507,8 @@ impl StaticConfig {
                                 .expect("no static workspace config"),
                         )?;
                     }
+
+                    m.apply_monitor_layout_rules();
                 }
 
                 let mut workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
@@ -1272,6 +1561,10 @@ impl StaticConfig {
             wm.cross_boundary_behaviour = val;
         }
 
+        if let Some(val) = value.navigation_behaviour {
+            wm.navigation_behaviour = val;
+        }
+
         if let Some(val) = value.unmanaged_window_operation_behaviour {
             wm.unmanaged_window_operation_behaviour = val;
         }
@@ -1287,9 +1580,9 @@ impl StaticConfig {
         wm.work_area_offset = value.global_work_area_offset;
 
         match value.focus_follows_mouse {
-            None => WindowsApi::disable_focus_follows_mouse()?,
+            None => platform::current().disable_focus_follows_mouse()?,
             Some(FocusFollowsMouseImplementation::Windows) => {
-                WindowsApi::enable_focus_follows_mouse()?;
+                platform::current().enable_focus_follows_mouse()?;
             }
             Some(FocusFollowsMouseImplementation::Komorebi) => {}
         };
@@ -1332,6 +1625,18 @@ fn populate_option(
     Ok(())
 }
 
+/// A matching rule with a matching strategy for every identifier, populated on its own so that
+/// duplicate matching rules still get a matching strategy
+fn populate_rule(
+    matching_rule: &MatchingRule,
+    regex_identifiers: &mut HashMap<String, Regex>,
+) -> Result<MatchingRule> {
+    let mut matching_rules = vec![matching_rule.clone()];
+    populate_rules(&mut matching_rules, &mut vec![], regex_identifiers)?;
+
+    Ok(matching_rules.remove(0))
+}
+
 fn populate_rules(
     matching_rules: &mut Vec<MatchingRule>,
     identifiers: &mut Vec<MatchingRule>,
@@ -1339,30 +1644,17 @@ fn populate_rules(
 ) -> Result<()> {
     for matching_rule in matching_rules {
         if !identifiers.contains(matching_rule) {
-            match matching_rule {
-                MatchingRule::Simple(simple) => {
-                    if simple.matching_strategy.is_none() {
-                        simple.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                    }
-
-                    if matches!(simple.matching_strategy, Some(MatchingStrategy::Regex)) {
-                        let re = Regex::new(&simple.id)?;
-                        regex_identifiers.insert(simple.id.clone(), re);
-                    }
+            for identifier in matching_rule.identifiers_mut() {
+                if identifier.matching_strategy.is_none() {
+                    identifier.matching_strategy = Option::from(MatchingStrategy::Legacy);
                 }
-                MatchingRule::Composite(composite) => {
-                    for rule in composite {
-                        if rule.matching_strategy.is_none() {
-                            rule.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                        }
 
-                        if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
-                            let re = Regex::new(&rule.id)?;
-                            regex_identifiers.insert(rule.id.clone(), re);
-                        }
-                    }
+                if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+                    let re = Regex::new(&identifier.id)?;
+                    regex_identifiers.insert(identifier.id.clone(), re);
                 }
             }
+
             identifiers.push(matching_rule.clone());
         }
     }
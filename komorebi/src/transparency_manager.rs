@@ -4,22 +4,22 @@ use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicConsume;
 use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU8;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
-use crate::should_act;
+use crate::platform;
 use crate::Window;
 use crate::WindowManager;
-use crate::WindowsApi;
-use crate::REGEX_IDENTIFIERS;
 use crate::TRANSPARENCY_BLACKLIST;
 
 pub static TRANSPARENCY_ENABLED: AtomicBool = AtomicBool::new(false);
 pub static TRANSPARENCY_ALPHA: AtomicU8 = AtomicU8::new(200);
 
 static KNOWN_HWNDS: OnceLock<Mutex<Vec<isize>>> = OnceLock::new();
+static WINDOW_ALPHAS: OnceLock<Mutex<HashMap<isize, u8>>> = OnceLock::new();
 
 pub struct Notification;
 
@@ -27,7 +27,33 @@ static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceL
 
 pub fn known_hwnds() -> Vec<isize> {
     let known = KNOWN_HWNDS.get_or_init(|| Mutex::new(Vec::new())).lock();
-    known.iter().copied().collect()
+    let window_alphas = window_alphas().lock();
+    known
+        .iter()
+        .copied()
+        .chain(window_alphas.keys().copied())
+        .collect()
+}
+
+fn window_alphas() -> &'static Mutex<HashMap<isize, u8>> {
+    WINDOW_ALPHAS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The alpha value which a window returns to instead of being made fully opaque
+pub fn window_alpha(hwnd: isize) -> Option<u8> {
+    window_alphas().lock().get(&hwnd).copied()
+}
+
+pub fn set_window_alpha(hwnd: isize, alpha: u8) {
+    window_alphas().lock().insert(hwnd, alpha);
+}
+
+pub fn remove_window_alpha(hwnd: isize) {
+    window_alphas().lock().remove(&hwnd);
+}
+
+pub fn clear_window_alphas() {
+    window_alphas().lock().clear();
 }
 
 pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
@@ -124,8 +150,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     continue 'monitors;
                 }
 
-                let foreground_hwnd = WindowsApi::foreground_window().unwrap_or_default();
-                let is_maximized = WindowsApi::is_zoomed(foreground_hwnd);
+                let foreground_hwnd = platform::current().foreground_window().unwrap_or_default();
+                let is_maximized = platform::current().is_zoomed(foreground_hwnd);
 
                 if is_maximized {
                     if let Err(error) = Window::from(foreground_hwnd).opaque() {
@@ -137,7 +163,6 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 }
 
                 let transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
-                let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
                 for (idx, c) in ws.containers().iter().enumerate() {
                     // Update the transparency for all containers on this workspace
@@ -157,15 +182,9 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                         window.class(),
                                         window.path(),
                                     ) {
-                                        let is_blacklisted = should_act(
-                                            &title,
-                                            &exe_name,
-                                            &class,
-                                            &path,
-                                            &transparency_blacklist,
-                                            &regex_identifiers,
-                                        )
-                                        .is_some();
+                                        let is_blacklisted = transparency_blacklist
+                                            .matching_rule(&title, &exe_name, &class, &path)
+                                            .is_some();
 
                                         should_make_transparent = !is_blacklisted;
                                     }
@@ -1,11 +1,12 @@
 use crate::border_manager;
-use crate::com::SetCloak;
 use crate::focus_manager;
+use crate::platform;
 use crate::stackbar_manager;
+#[cfg(windows)]
 use crate::windows_api;
+use crate::workspace_transition;
 use crate::ANIMATIONS_IN_PROGRESS;
-use crate::ANIMATION_DURATION;
-use crate::ANIMATION_ENABLED;
+use crate::ANIMATION_MANAGER;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
 use std::collections::HashMap;
@@ -29,26 +30,42 @@ use serde::ser::SerializeStruct;
 use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
+#[cfg(windows)]
 use windows::Win32::Foundation::HWND;
 
 use crate::core::ApplicationIdentifier;
 use crate::core::HidingBehaviour;
+use crate::core::ManageAction;
 use crate::core::Rect;
+use crate::core::WindowSizeConstraints;
 
 use crate::animation::Animation;
+use crate::animation::AnimationFrame;
+use crate::animation::AnimationSettings;
+use crate::rule_evaluator::RuleDecision;
+use crate::rule_index::RuleList;
+use crate::static_config::FloatingPlacementRule;
+use crate::static_config::ManageActionRule;
+use crate::static_config::WindowSizeConstraintRule;
 use crate::styles::ExtendedWindowStyle;
 use crate::styles::WindowStyle;
 use crate::transparency_manager;
 use crate::window_manager_event::WindowManagerEvent;
-use crate::windows_api::WindowsApi;
+use crate::FLOATING_PLACEMENT_IDENTIFIERS;
+use crate::FLOATING_PLACEMENT_RULES;
 use crate::HIDDEN_HWNDS;
 use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
+use crate::MANAGE_ACTION_IDENTIFIERS;
+use crate::MANAGE_ACTION_NO_TITLEBAR;
+use crate::MANAGE_ACTION_RULES;
 use crate::MANAGE_IDENTIFIERS;
 use crate::NO_TITLEBAR;
 use crate::PERMAIGNORE_CLASSES;
-use crate::REGEX_IDENTIFIERS;
+use crate::WINDOW_SIZE_CONSTRAINTS;
+use crate::WINDOW_SIZE_CONSTRAINTS_BY_HWND;
+use crate::WINDOW_SIZE_CONSTRAINT_IDENTIFIERS;
 use crate::WSL2_UI_PROCESSES;
 
 pub static MINIMUM_WIDTH: AtomicI32 = AtomicI32::new(0);
@@ -70,6 +87,7 @@ impl From<isize> for Window {
     }
 }
 
+#[cfg(windows)]
 impl From<HWND> for Window {
     fn from(value: HWND) -> Self {
         Self {
@@ -148,19 +166,22 @@ impl Serialize for Window {
         )?;
         state.serialize_field(
             "rect",
-            &WindowsApi::window_rect(self.hwnd).unwrap_or_default(),
+            &platform::current()
+                .window_rect(self.hwnd)
+                .unwrap_or_default(),
         )?;
         state.end()
     }
 }
 
 impl Window {
+    #[cfg(windows)]
     pub const fn hwnd(self) -> HWND {
         HWND(windows_api::as_ptr!(self.hwnd))
     }
 
     pub fn move_to_area(&mut self, current_area: &Rect, target_area: &Rect) -> Result<()> {
-        let current_rect = WindowsApi::window_rect(self.hwnd)?;
+        let current_rect = platform::current().window_rect(self.hwnd)?;
         let x_diff = target_area.left - current_area.left;
         let y_diff = target_area.top - current_area.top;
         let x_ratio = f32::abs((target_area.right as f32) / (current_area.right as f32));
@@ -199,11 +220,15 @@ impl Window {
         )
     }
 
-    pub fn animate_position(&self, start_rect: &Rect, target_rect: &Rect, top: bool) -> Result<()> {
-        let start_rect = *start_rect;
-        let target_rect = *target_rect;
-        let duration = Duration::from_millis(ANIMATION_DURATION.load(Ordering::SeqCst));
+    pub fn animate_position(
+        &self,
+        start_rect: &Rect,
+        target_rect: &Rect,
+        top: bool,
+        settings: AnimationSettings,
+    ) -> Result<()> {
         let mut animation = self.animation;
+        let should_render = animation.start(start_rect, target_rect, top, settings);
 
         border_manager::BORDER_TEMPORARILY_DISABLED.store(true, Ordering::SeqCst);
         border_manager::send_notification(Some(self.hwnd));
@@ -211,32 +236,25 @@ impl Window {
         stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(true, Ordering::SeqCst);
         stackbar_manager::send_notification();
 
+        // an animation which is already in progress has been retargeted and is still rendering
+        if !should_render {
+            return Ok(());
+        }
+
         let hwnd = self.hwnd;
 
         std::thread::spawn(move || {
-            animation.animate(duration, |progress: f64| {
-                let new_rect = Animation::lerp_rect(&start_rect, &target_rect, progress);
-
-                if progress == 1.0 {
-                    WindowsApi::position_window(hwnd, &new_rect, top)?;
-                    if WindowsApi::foreground_window().unwrap_or_default() == hwnd {
+            animation.animate(|frame: AnimationFrame| {
+                let platform = platform::current();
+                if frame.finished {
+                    platform.position_window(hwnd, &frame.rect, frame.top)?;
+                    if platform.foreground_window().unwrap_or_default() == hwnd {
                         focus_manager::send_notification(hwnd)
                     }
 
-                    if ANIMATIONS_IN_PROGRESS.load(Ordering::Acquire) == 0 {
-                        border_manager::BORDER_TEMPORARILY_DISABLED.store(false, Ordering::SeqCst);
-                        stackbar_manager::STACKBAR_TEMPORARILY_DISABLED
-                            .store(false, Ordering::SeqCst);
-
-                        border_manager::send_notification(Some(hwnd));
-                        stackbar_manager::send_notification();
-                        transparency_manager::send_notification();
-                    }
+                    Self::end_animation_effects(hwnd);
                 } else {
-                    // using MoveWindow because it runs faster than SetWindowPos
-                    // so animation have more fps and feel smoother
-                    WindowsApi::move_window(hwnd, &new_rect, false)?;
-                    WindowsApi::invalidate_rect(hwnd, None, false);
+                    platform.move_window(hwnd, &frame.rect)?;
                 }
 
                 Ok(())
@@ -246,30 +264,61 @@ impl Window {
         Ok(())
     }
 
+    /// Borders and stackbars are hidden while windows are animating, and are only shown again
+    /// once the last animation in progress has finished
+    fn end_animation_effects(hwnd: isize) {
+        if ANIMATIONS_IN_PROGRESS.load(Ordering::Acquire) == 0 {
+            border_manager::BORDER_TEMPORARILY_DISABLED.store(false, Ordering::SeqCst);
+            stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(false, Ordering::SeqCst);
+
+            border_manager::send_notification(Some(hwnd));
+            stackbar_manager::send_notification();
+            transparency_manager::send_notification();
+        }
+    }
+
     pub fn set_position(&self, layout: &Rect, top: bool) -> Result<()> {
-        let window_rect = WindowsApi::window_rect(self.hwnd)?;
+        let platform = platform::current();
+        let window_rect = platform.window_rect(self.hwnd)?;
 
-        if window_rect.eq(layout) {
+        // a window which is still animating may pass through its new position on the way to an
+        // old one, so its animation still needs to be retargeted
+        if window_rect.eq(layout) && !ANIMATION_MANAGER.lock().in_progress(self.hwnd) {
             return Ok(());
         }
 
-        if ANIMATION_ENABLED.load(Ordering::SeqCst) {
-            self.animate_position(&window_rect, layout, top)
+        let animation_settings = AnimationSettings::current();
+
+        if animation_settings.enabled {
+            self.animate_position(&window_rect, layout, top, animation_settings)
         } else {
-            WindowsApi::position_window(self.hwnd, layout, top)
+            // an animation which is still in progress would otherwise move the window away from
+            // this position again on its next frame
+            let ended = {
+                let mut animation_manager = ANIMATION_MANAGER.lock();
+                let in_progress = animation_manager.in_progress(self.hwnd);
+                animation_manager.end(self.hwnd);
+                in_progress
+            };
+
+            if ended {
+                Self::end_animation_effects(self.hwnd);
+            }
+
+            platform.position_window(self.hwnd, layout, top)
         }
     }
 
     pub fn is_maximized(self) -> bool {
-        WindowsApi::is_zoomed(self.hwnd)
+        platform::current().is_zoomed(self.hwnd)
     }
 
     pub fn is_miminized(self) -> bool {
-        WindowsApi::is_iconic(self.hwnd)
+        platform::current().is_iconic(self.hwnd)
     }
 
     pub fn is_visible(self) -> bool {
-        WindowsApi::is_window_visible(self.hwnd)
+        platform::current().is_window_visible(self.hwnd)
     }
 
     pub fn hide(self) {
@@ -278,15 +327,27 @@ impl Window {
             programmatically_hidden_hwnds.push(self.hwnd);
         }
 
+        // windows leaving a workspace are only hidden once the transition away from it finishes
+        if workspace_transition::is_outgoing(self.hwnd) {
+            return;
+        }
+
+        self.apply_hiding_behaviour();
+    }
+
+    pub fn apply_hiding_behaviour(self) {
+        let platform = platform::current();
         let hiding_behaviour = HIDING_BEHAVIOUR.lock();
         match *hiding_behaviour {
-            HidingBehaviour::Hide => WindowsApi::hide_window(self.hwnd),
-            HidingBehaviour::Minimize => WindowsApi::minimize_window(self.hwnd),
-            HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 2),
+            HidingBehaviour::Hide => platform.hide_window(self.hwnd),
+            HidingBehaviour::Minimize => platform.minimize_window(self.hwnd),
+            HidingBehaviour::Cloak => platform.cloak_window(self.hwnd, true),
         }
     }
 
     pub fn restore(self) {
+        workspace_transition::cancel_outgoing(self.hwnd);
+
         let mut programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
         if let Some(idx) = programmatically_hidden_hwnds
             .iter()
@@ -295,24 +356,25 @@ impl Window {
             programmatically_hidden_hwnds.remove(idx);
         }
 
+        let platform = platform::current();
         let hiding_behaviour = HIDING_BEHAVIOUR.lock();
         match *hiding_behaviour {
             HidingBehaviour::Hide | HidingBehaviour::Minimize => {
-                WindowsApi::restore_window(self.hwnd);
+                platform.restore_window(self.hwnd);
             }
-            HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 0),
+            HidingBehaviour::Cloak => platform.cloak_window(self.hwnd, false),
         }
     }
 
     pub fn minimize(self) {
         let exe = self.exe().unwrap_or_default();
         if !exe.contains("komorebi-bar") {
-            WindowsApi::minimize_window(self.hwnd);
+            platform::current().minimize_window(self.hwnd);
         }
     }
 
     pub fn close(self) -> Result<()> {
-        WindowsApi::close_window(self.hwnd)
+        platform::current().close_window(self.hwnd)
     }
 
     pub fn maximize(self) {
@@ -324,7 +386,7 @@ impl Window {
             programmatically_hidden_hwnds.remove(idx);
         }
 
-        WindowsApi::maximize_window(self.hwnd);
+        platform::current().maximize_window(self.hwnd);
     }
 
     pub fn unmaximize(self) {
@@ -336,27 +398,29 @@ impl Window {
             programmatically_hidden_hwnds.remove(idx);
         }
 
-        WindowsApi::unmaximize_window(self.hwnd);
+        platform::current().unmaximize_window(self.hwnd);
     }
 
     pub fn focus(self, mouse_follows_focus: bool) -> Result<()> {
+        let platform = platform::current();
+
         // If the target window is already focused, do nothing.
-        if let Ok(ihwnd) = WindowsApi::foreground_window() {
+        if let Ok(ihwnd) = platform.foreground_window() {
             if ihwnd == self.hwnd {
                 // Center cursor in Window
                 if mouse_follows_focus {
-                    WindowsApi::center_cursor_in_rect(&WindowsApi::window_rect(self.hwnd)?)?;
+                    platform.center_cursor_in_rect(&platform.window_rect(self.hwnd)?)?;
                 }
 
                 return Ok(());
             }
         }
 
-        WindowsApi::raise_and_focus_window(self.hwnd)?;
+        platform.raise_and_focus_window(self.hwnd)?;
 
         // Center cursor in Window
         if mouse_follows_focus {
-            WindowsApi::center_cursor_in_rect(&WindowsApi::window_rect(self.hwnd)?)?;
+            platform.center_cursor_in_rect(&platform.window_rect(self.hwnd)?)?;
         }
 
         Ok(())
@@ -366,91 +430,82 @@ impl Window {
         let mut ex_style = self.ex_style()?;
         ex_style.insert(ExtendedWindowStyle::LAYERED);
         self.update_ex_style(&ex_style)?;
-        WindowsApi::set_transparent(
+        platform::current().set_transparent(
             self.hwnd,
             transparency_manager::TRANSPARENCY_ALPHA.load_consume(),
         )
     }
 
+    pub fn set_alpha(self, alpha: u8) -> Result<()> {
+        let mut ex_style = self.ex_style()?;
+        if !ex_style.contains(ExtendedWindowStyle::LAYERED) {
+            ex_style.insert(ExtendedWindowStyle::LAYERED);
+            self.update_ex_style(&ex_style)?;
+        }
+
+        platform::current().set_transparent(self.hwnd, alpha)
+    }
+
     pub fn opaque(self) -> Result<()> {
+        if let Some(alpha) = transparency_manager::window_alpha(self.hwnd) {
+            return self.set_alpha(alpha);
+        }
+
         let mut ex_style = self.ex_style()?;
         ex_style.remove(ExtendedWindowStyle::LAYERED);
         self.update_ex_style(&ex_style)
     }
 
     pub fn set_accent(self, colour: u32) -> Result<()> {
-        WindowsApi::set_window_accent(self.hwnd, Some(colour))
+        platform::current().set_window_accent(self.hwnd, Some(colour))
     }
 
     pub fn remove_accent(self) -> Result<()> {
-        WindowsApi::set_window_accent(self.hwnd, None)
-    }
-
-    #[cfg(target_pointer_width = "64")]
-    pub fn update_style(self, style: &WindowStyle) -> Result<()> {
-        WindowsApi::update_style(self.hwnd, isize::try_from(style.bits())?)
+        platform::current().set_window_accent(self.hwnd, None)
     }
 
-    #[cfg(target_pointer_width = "32")]
     pub fn update_style(self, style: &WindowStyle) -> Result<()> {
-        WindowsApi::update_style(self.hwnd, i32::try_from(style.bits())?)
-    }
-
-    #[cfg(target_pointer_width = "64")]
-    pub fn update_ex_style(self, style: &ExtendedWindowStyle) -> Result<()> {
-        WindowsApi::update_ex_style(self.hwnd, isize::try_from(style.bits())?)
+        platform::current().set_window_style(self.hwnd, *style)
     }
 
-    #[cfg(target_pointer_width = "32")]
     pub fn update_ex_style(self, style: &ExtendedWindowStyle) -> Result<()> {
-        WindowsApi::update_ex_style(self.hwnd, i32::try_from(style.bits())?)
+        platform::current().set_window_ex_style(self.hwnd, *style)
     }
 
     pub fn style(self) -> Result<WindowStyle> {
-        let bits = u32::try_from(WindowsApi::gwl_style(self.hwnd)?)?;
-        Ok(WindowStyle::from_bits_truncate(bits))
+        platform::current().window_style(self.hwnd)
     }
 
     pub fn ex_style(self) -> Result<ExtendedWindowStyle> {
-        let bits = u32::try_from(WindowsApi::gwl_ex_style(self.hwnd)?)?;
-        Ok(ExtendedWindowStyle::from_bits_truncate(bits))
+        platform::current().window_ex_style(self.hwnd)
     }
 
     pub fn title(self) -> Result<String> {
-        WindowsApi::window_text_w(self.hwnd)
+        platform::current().window_title(self.hwnd)
     }
 
     pub fn path(self) -> Result<String> {
-        let (process_id, _) = WindowsApi::window_thread_process_id(self.hwnd);
-        let handle = WindowsApi::process_handle(process_id)?;
-        let path = WindowsApi::exe_path(handle);
-        WindowsApi::close_process(handle)?;
-        path
+        platform::current().window_path(self.hwnd)
     }
 
     pub fn exe(self) -> Result<String> {
-        let (process_id, _) = WindowsApi::window_thread_process_id(self.hwnd);
-        let handle = WindowsApi::process_handle(process_id)?;
-        let exe = WindowsApi::exe(handle);
-        WindowsApi::close_process(handle)?;
-        exe
+        platform::current().window_exe(self.hwnd)
     }
 
     pub fn process_id(self) -> u32 {
-        let (process_id, _) = WindowsApi::window_thread_process_id(self.hwnd);
-        process_id
+        platform::current().process_id(self.hwnd)
     }
 
     pub fn class(self) -> Result<String> {
-        WindowsApi::real_window_class_w(self.hwnd)
+        platform::current().window_class(self.hwnd)
     }
 
     pub fn is_cloaked(self) -> Result<bool> {
-        WindowsApi::is_window_cloaked(self.hwnd)
+        platform::current().is_window_cloaked(self.hwnd)
     }
 
     pub fn is_window(self) -> bool {
-        WindowsApi::is_window(self.hwnd)
+        platform::current().is_window(self.hwnd)
     }
 
     pub fn remove_title_bar(self) -> Result<()> {
@@ -467,6 +522,90 @@ impl Window {
         self.update_style(&style)
     }
 
+    /// The size constraints of this window, which are looked up once when the window is first
+    /// managed and cached until the configuration is reloaded
+    pub fn size_constraints(self) -> Result<Option<WindowSizeConstraints>> {
+        if let Some(constraints) = WINDOW_SIZE_CONSTRAINTS_BY_HWND.lock().get(&self.hwnd) {
+            return Ok(*constraints);
+        }
+
+        self.cache_size_constraints()
+    }
+
+    /// Look up the size constraints from the first window size constraint rule which matches this
+    /// window and cache them for this hwnd
+    pub fn cache_size_constraints(self) -> Result<Option<WindowSizeConstraints>> {
+        let constraints = {
+            let window_size_constraints = WINDOW_SIZE_CONSTRAINTS.lock();
+            if window_size_constraints.is_empty() {
+                None
+            } else {
+                let (title, exe_name, class, path) =
+                    (self.title()?, self.exe()?, self.class()?, self.path()?);
+
+                matching_size_constraints(
+                    &window_size_constraints,
+                    &WINDOW_SIZE_CONSTRAINT_IDENTIFIERS.lock(),
+                    &title,
+                    &exe_name,
+                    &class,
+                    &path,
+                )
+            }
+        };
+
+        WINDOW_SIZE_CONSTRAINTS_BY_HWND
+            .lock()
+            .insert(self.hwnd, constraints);
+
+        Ok(constraints)
+    }
+
+    pub fn remove_cached_size_constraints(self) {
+        WINDOW_SIZE_CONSTRAINTS_BY_HWND.lock().remove(&self.hwnd);
+    }
+
+    /// The first floating placement rule which matches this window
+    pub fn floating_placement(self) -> Result<Option<FloatingPlacementRule>> {
+        let floating_placement_rules = FLOATING_PLACEMENT_RULES.lock();
+        if floating_placement_rules.is_empty() {
+            return Ok(None);
+        }
+
+        let (title, exe_name, class, path) =
+            (self.title()?, self.exe()?, self.class()?, self.path()?);
+
+        Ok(matching_floating_placement(
+            &floating_placement_rules,
+            &FLOATING_PLACEMENT_IDENTIFIERS.lock(),
+            &title,
+            &exe_name,
+            &class,
+            &path,
+        ))
+    }
+
+    /// The actions of every manage action rule which matches this window, in the order that the
+    /// rules were defined
+    pub fn manage_actions(self) -> Result<Vec<ManageAction>> {
+        let manage_action_rules = MANAGE_ACTION_RULES.lock();
+        if manage_action_rules.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (title, exe_name, class, path) =
+            (self.title()?, self.exe()?, self.class()?, self.path()?);
+
+        Ok(matching_manage_actions(
+            &manage_action_rules,
+            &MANAGE_ACTION_IDENTIFIERS.lock(),
+            &title,
+            &exe_name,
+            &class,
+            &path,
+        ))
+    }
+
     #[tracing::instrument(fields(exe, title), skip(debug))]
     pub fn should_manage(
         self,
@@ -479,7 +618,9 @@ impl Window {
 
         debug.is_window = true;
 
-        let rect = WindowsApi::window_rect(self.hwnd).unwrap_or_default();
+        let rect = platform::current()
+            .window_rect(self.hwnd)
+            .unwrap_or_default();
 
         if rect.right < MINIMUM_WIDTH.load(Ordering::SeqCst) {
             return Ok(false);
@@ -581,64 +722,37 @@ fn window_is_eligible(
     event: Option<WindowManagerEvent>,
     debug: &mut RuleDebug,
 ) -> bool {
-    {
-        let permaignore_classes = PERMAIGNORE_CLASSES.lock();
-        if permaignore_classes.contains(class) {
-            debug.matches_permaignore_class = Some(class.clone());
-            return false;
-        }
+    if PERMAIGNORE_CLASSES.lock().contains(class) {
+        debug.matches_permaignore_class = Some(class.clone());
+        return false;
     }
 
-    let regex_identifiers = REGEX_IDENTIFIERS.lock();
+    debug.matches_ignore_identifier = IGNORE_IDENTIFIERS
+        .lock()
+        .matching_rule(title, exe_name, class, path);
 
-    let ignore_identifiers = IGNORE_IDENTIFIERS.lock();
-    let should_float = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &ignore_identifiers,
-        &regex_identifiers,
-    ) {
-        debug.matches_ignore_identifier = Some(rule);
-        true
-    } else {
-        false
-    };
+    debug.matches_managed_override = MANAGE_IDENTIFIERS
+        .lock()
+        .matching_rule(title, exe_name, class, path);
 
-    let manage_identifiers = MANAGE_IDENTIFIERS.lock();
-    let managed_override = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &manage_identifiers,
-        &regex_identifiers,
+    let managed_override = match RuleDecision::from_matches(
+        debug.matches_permaignore_class.is_some(),
+        debug.matches_ignore_identifier.is_some(),
+        debug.matches_managed_override.is_some(),
     ) {
-        debug.matches_managed_override = Some(rule);
-        true
-    } else {
-        false
+        RuleDecision::Ignored => return false,
+        RuleDecision::Managed => true,
+        RuleDecision::ManagedIfEligible => false,
     };
 
-    if should_float && !managed_override {
-        return false;
-    }
-
     let layered_whitelist = LAYERED_WHITELIST.lock();
-    let mut allow_layered = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &layered_whitelist,
-        &regex_identifiers,
-    ) {
-        debug.matches_layered_whitelist = Some(rule);
-        true
-    } else {
-        false
-    };
+    let mut allow_layered =
+        if let Some(rule) = layered_whitelist.matching_rule(title, exe_name, class, path) {
+            debug.matches_layered_whitelist = Some(rule);
+            true
+        } else {
+            false
+        };
 
     let known_layered_hwnds = transparency_manager::known_hwnds();
 
@@ -661,20 +775,14 @@ fn window_is_eligible(
 
     let allow_titlebar_removed = {
         let titlebars_removed = NO_TITLEBAR.lock();
-        titlebars_removed.contains(exe_name)
+        titlebars_removed.contains(exe_name) || MANAGE_ACTION_NO_TITLEBAR.lock().contains(&hwnd)
     };
 
     {
         let slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
-        let should_sleep = should_act(
-            title,
-            exe_name,
-            class,
-            path,
-            &slow_application_identifiers,
-            &regex_identifiers,
-        )
-        .is_some();
+        let should_sleep = slow_application_identifiers
+            .matching_rule(title, exe_name, class, path)
+            .is_some();
 
         if should_sleep {
             std::thread::sleep(Duration::from_millis(
@@ -716,42 +824,92 @@ pub fn should_act(
 ) -> Option<MatchingRule> {
     let mut matching_rule = None;
     for rule in identifiers {
-        match rule {
-            MatchingRule::Simple(identifier) => {
-                if should_act_individual(
-                    title,
-                    exe_name,
-                    class,
-                    path,
-                    identifier,
-                    regex_identifiers,
-                ) {
-                    matching_rule = Some(rule.clone());
-                };
-            }
-            MatchingRule::Composite(identifiers) => {
-                let mut composite_results = vec![];
-                for identifier in identifiers {
-                    composite_results.push(should_act_individual(
-                        title,
-                        exe_name,
-                        class,
-                        path,
-                        identifier,
-                        regex_identifiers,
-                    ));
-                }
-
-                if composite_results.iter().all(|&x| x) {
-                    matching_rule = Some(rule.clone());
-                }
-            }
+        if should_act_rule(title, exe_name, class, path, rule, regex_identifiers) {
+            matching_rule = Some(rule.clone());
         }
     }
 
     matching_rule
 }
 
+/// The size constraints of the first window size constraint rule which matches a window, where
+/// `identifiers` holds the matching rule of each of the `rules`
+pub fn matching_size_constraints(
+    rules: &[WindowSizeConstraintRule],
+    identifiers: &RuleList,
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    path: &str,
+) -> Option<WindowSizeConstraints> {
+    identifiers
+        .matching_indices(title, exe_name, class, path)
+        .first()
+        .and_then(|idx| rules.get(*idx))
+        .map(|rule| rule.constraints)
+}
+
+/// The first floating placement rule which matches a window
+pub fn matching_floating_placement(
+    rules: &[FloatingPlacementRule],
+    identifiers: &RuleList,
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    path: &str,
+) -> Option<FloatingPlacementRule> {
+    identifiers
+        .matching_indices(title, exe_name, class, path)
+        .first()
+        .and_then(|idx| rules.get(*idx))
+        .cloned()
+}
+
+/// The actions of every manage action rule which matches a window, in the order that the rules
+/// were defined
+pub fn matching_manage_actions(
+    rules: &[ManageActionRule],
+    identifiers: &RuleList,
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    path: &str,
+) -> Vec<ManageAction> {
+    identifiers
+        .matching_indices(title, exe_name, class, path)
+        .into_iter()
+        .filter_map(|idx| rules.get(idx))
+        .flat_map(|rule| rule.actions.iter().copied())
+        .collect()
+}
+
+pub fn should_act_rule(
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    path: &str,
+    rule: &MatchingRule,
+    regex_identifiers: &HashMap<String, Regex>,
+) -> bool {
+    match rule {
+        MatchingRule::Simple(identifier) => {
+            should_act_individual(title, exe_name, class, path, identifier, regex_identifiers)
+        }
+        MatchingRule::Composite(identifiers) => identifiers.iter().all(|identifier| {
+            should_act_individual(title, exe_name, class, path, identifier, regex_identifiers)
+        }),
+        MatchingRule::All { all } => all
+            .iter()
+            .all(|rule| should_act_rule(title, exe_name, class, path, rule, regex_identifiers)),
+        MatchingRule::Any { any } => any
+            .iter()
+            .any(|rule| should_act_rule(title, exe_name, class, path, rule, regex_identifiers)),
+        MatchingRule::Not { not } => {
+            !should_act_rule(title, exe_name, class, path, not, regex_identifiers)
+        }
+    }
+}
+
 pub fn should_act_individual(
     title: &str,
     exe_name: &str,
@@ -994,7 +1152,158 @@ pub fn should_act_individual(
                 }
             }
         },
+        Some(MatchingStrategy::EqualsIgnoreCase) => match identifier.kind {
+            ApplicationIdentifier::Title => {
+                if equals_ignore_case(&identifier.id, title) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Class => {
+                if equals_ignore_case(&identifier.id, class) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Exe => {
+                if equals_ignore_case(&identifier.id, exe_name) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Path => {
+                if equals_ignore_case(&identifier.id, path) {
+                    should_act = true;
+                }
+            }
+        },
+        Some(MatchingStrategy::ContainsIgnoreCase) => match identifier.kind {
+            ApplicationIdentifier::Title => {
+                if contains_ignore_case(&identifier.id, title) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Class => {
+                if contains_ignore_case(&identifier.id, class) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Exe => {
+                if contains_ignore_case(&identifier.id, exe_name) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Path => {
+                if contains_ignore_case(&identifier.id, path) {
+                    should_act = true;
+                }
+            }
+        },
+        Some(MatchingStrategy::Glob) => match identifier.kind {
+            ApplicationIdentifier::Title => {
+                if glob_match(&identifier.id, title) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Class => {
+                if glob_match(&identifier.id, class) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Exe => {
+                if glob_match(&identifier.id, exe_name) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Path => {
+                if glob_match(&identifier.id, path) {
+                    should_act = true;
+                }
+            }
+        },
+        Some(MatchingStrategy::WordBoundary) => match identifier.kind {
+            ApplicationIdentifier::Title => {
+                if word_match(&identifier.id, title) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Class => {
+                if word_match(&identifier.id, class) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Exe => {
+                if word_match(&identifier.id, exe_name) {
+                    should_act = true;
+                }
+            }
+            ApplicationIdentifier::Path => {
+                if word_match(&identifier.id, path) {
+                    should_act = true;
+                }
+            }
+        },
     }
 
     should_act
 }
+
+pub fn equals_ignore_case(id: &str, value: &str) -> bool {
+    value.to_lowercase() == id.to_lowercase()
+}
+
+pub fn contains_ignore_case(id: &str, value: &str) -> bool {
+    value.to_lowercase().contains(&id.to_lowercase())
+}
+
+/// Match a value against a wildcard pattern, where `*` matches any number of characters
+/// (including none) and `?` matches exactly one character
+pub fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+
+    let (mut p, mut v) = (0, 0);
+    // the position of the last `*` in the pattern, and of the character in the value that it
+    // currently stops before
+    let mut backtrack = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Option::from((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` swallow one more character and try again
+                Some((star, swallowed)) => {
+                    backtrack = Option::from((star, swallowed + 1));
+                    p = star + 1;
+                    v = swallowed + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Whether a value contains a word which is not directly preceded or followed by a letter, digit
+/// or `_`
+pub fn word_match(word: &str, value: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    value.char_indices().any(|(idx, _)| {
+        value[idx..].starts_with(word)
+            && !value[..idx].chars().next_back().is_some_and(is_word)
+            && !value[idx + word.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_word)
+    })
+}
@@ -3,6 +3,8 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::io::ErrorKind;
 use std::num::NonZeroUsize;
+#[cfg(not(windows))]
+use std::os::unix::net::UnixListener;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
@@ -19,11 +21,11 @@ use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(windows)]
 use uds_windows::UnixListener;
 
 use crate::core::config_generation::MatchingRule;
-use crate::core::custom_layout::CustomLayout;
-use crate::core::Arrangement;
+use crate::core::default_layout::MASTER_RATIO_STEP;
 use crate::core::Axis;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
@@ -32,15 +34,20 @@ use crate::core::DefaultLayout;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::ManageAction;
 use crate::core::MoveBehaviour;
 use crate::core::OperationBehaviour;
 use crate::core::OperationDirection;
 use crate::core::Rect;
+use crate::core::RelativeRect;
+use crate::core::ScrollingLayoutOptions;
+use crate::core::SidePadding;
 use crate::core::Sizing;
 use crate::core::StackbarLabel;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowManagementBehaviour;
 
+use crate::animation::AnimationSettings;
 use crate::border_manager;
 use crate::border_manager::STYLE;
 use crate::config_generation::WorkspaceMatchingRule;
@@ -49,8 +56,9 @@ use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
 use crate::load_configuration;
 use crate::monitor::Monitor;
+use crate::platform;
 use crate::ring::Ring;
-use crate::should_act_individual;
+use crate::should_act_rule;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_LABEL;
 use crate::stackbar_manager::STACKBAR_MODE;
@@ -62,12 +70,13 @@ use crate::static_config::StaticConfig;
 use crate::transparency_manager;
 use crate::window::Window;
 use crate::window_manager_event::WindowManagerEvent;
-use crate::windows_api::WindowsApi;
 use crate::winevent_listener;
 use crate::workspace::Workspace;
+use crate::workspace_transition::WORKSPACE_TRANSITION;
 use crate::BorderColours;
 use crate::Colour;
 use crate::CrossBoundaryBehaviour;
+use crate::NavigationBehaviour;
 use crate::Rgb;
 use crate::CUSTOM_FFM;
 use crate::DATA_DIR;
@@ -76,6 +85,7 @@ use crate::HIDING_BEHAVIOUR;
 use crate::HOME_DIR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
+use crate::MANAGE_ACTION_NO_TITLEBAR;
 use crate::MANAGE_IDENTIFIERS;
 use crate::MONITOR_INDEX_PREFERENCES;
 use crate::NO_TITLEBAR;
@@ -96,6 +106,7 @@ pub struct WindowManager {
     pub window_management_behaviour: WindowManagementBehaviour,
     pub cross_monitor_move_behaviour: MoveBehaviour,
     pub cross_boundary_behaviour: CrossBoundaryBehaviour,
+    pub navigation_behaviour: NavigationBehaviour,
     pub unmanaged_window_operation_behaviour: OperationBehaviour,
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
@@ -104,6 +115,7 @@ pub struct WindowManager {
     pub has_pending_raise_op: bool,
     pub pending_move_op: Option<(usize, usize, usize)>,
     pub already_moved_window_handles: Arc<Mutex<HashSet<isize>>>,
+    pub data_dir: PathBuf,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -191,11 +203,11 @@ impl Default for GlobalState {
             stackbar_tab_width: STACKBAR_TAB_WIDTH.load(Ordering::SeqCst),
             stackbar_height: STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst),
             remove_titlebars: REMOVE_TITLEBARS.load(Ordering::SeqCst),
-            ignore_identifiers: IGNORE_IDENTIFIERS.lock().clone(),
-            manage_identifiers: MANAGE_IDENTIFIERS.lock().clone(),
-            layered_whitelist: LAYERED_WHITELIST.lock().clone(),
-            tray_and_multi_window_identifiers: TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().clone(),
-            name_change_on_launch_identifiers: OBJECT_NAME_CHANGE_ON_LAUNCH.lock().clone(),
+            ignore_identifiers: IGNORE_IDENTIFIERS.lock().to_vec(),
+            manage_identifiers: MANAGE_IDENTIFIERS.lock().to_vec(),
+            layered_whitelist: LAYERED_WHITELIST.lock().to_vec(),
+            tray_and_multi_window_identifiers: TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().to_vec(),
+            name_change_on_launch_identifiers: OBJECT_NAME_CHANGE_ON_LAUNCH.lock().to_vec(),
             monitor_index_preferences: MONITOR_INDEX_PREFERENCES.lock().clone(),
             display_index_preferences: DISPLAY_INDEX_PREFERENCES.lock().clone(),
             workspace_rules: WORKSPACE_MATCHING_RULES.lock().clone(),
@@ -259,7 +271,13 @@ impl EnforceWorkspaceRuleOp {
 impl WindowManager {
     #[tracing::instrument]
     pub fn new(incoming: Receiver<WindowManagerEvent>) -> Result<Self> {
-        let socket = DATA_DIR.join("komorebi.sock");
+        Self::with_data_dir(incoming, &DATA_DIR)
+    }
+
+    /// Creates a window manager which keeps its command socket and hwnd cache in `data_dir`
+    #[tracing::instrument]
+    pub fn with_data_dir(incoming: Receiver<WindowManagerEvent>, data_dir: &Path) -> Result<Self> {
+        let socket = data_dir.join("komorebi.sock");
 
         match std::fs::remove_file(&socket) {
             Ok(()) => {}
@@ -284,6 +302,7 @@ impl WindowManager {
             window_management_behaviour: WindowManagementBehaviour::default(),
             cross_monitor_move_behaviour: MoveBehaviour::Swap,
             cross_boundary_behaviour: CrossBoundaryBehaviour::Workspace,
+            navigation_behaviour: NavigationBehaviour::Layout,
             unmanaged_window_operation_behaviour: OperationBehaviour::Op,
             resize_delta: 50,
             focus_follows_mouse: None,
@@ -292,14 +311,15 @@ impl WindowManager {
             has_pending_raise_op: false,
             pending_move_op: None,
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
+            data_dir: data_dir.to_path_buf(),
         })
     }
 
     #[tracing::instrument(skip(self))]
     pub fn init(&mut self) -> Result<()> {
         tracing::info!("initialising");
-        WindowsApi::load_monitor_information(&mut self.monitors)?;
-        WindowsApi::load_workspace_information(&mut self.monitors)
+        platform::load_monitor_information(&mut self.monitors)?;
+        platform::current().load_workspace_information(&mut self.monitors)
     }
 
     #[tracing::instrument]
@@ -501,31 +521,14 @@ impl WindowManager {
                     let path = window.path()?;
 
                     for rule in &*workspace_matching_rules {
-                        let matched = match &rule.matching_rule {
-                            MatchingRule::Simple(r) => should_act_individual(
-                                &title,
-                                &exe_name,
-                                &class,
-                                &path,
-                                r,
-                                &regex_identifiers,
-                            ),
-                            MatchingRule::Composite(r) => {
-                                let mut composite_results = vec![];
-                                for identifier in r {
-                                    composite_results.push(should_act_individual(
-                                        &title,
-                                        &exe_name,
-                                        &class,
-                                        &path,
-                                        identifier,
-                                        &regex_identifiers,
-                                    ));
-                                }
-
-                                composite_results.iter().all(|&x| x)
-                            }
-                        };
+                        let matched = should_act_rule(
+                            &title,
+                            &exe_name,
+                            &class,
+                            &path,
+                            &rule.matching_rule,
+                            &regex_identifiers,
+                        );
 
                         if matched {
                             if rule.initial_only {
@@ -655,14 +658,14 @@ impl WindowManager {
 
     #[tracing::instrument(skip(self))]
     pub fn manage_focused_window(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = platform::current().foreground_window()?;
         let event = WindowManagerEvent::Manage(Window::from(hwnd));
         Ok(winevent_listener::event_tx().send(event)?)
     }
 
     #[tracing::instrument(skip(self))]
     pub fn unmanage_focused_window(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = platform::current().foreground_window()?;
         let event = WindowManagerEvent::Unmanage(Window::from(hwnd));
         Ok(winevent_listener::event_tx().send(event)?)
     }
@@ -698,7 +701,7 @@ impl WindowManager {
 
         // finally try matching the other way using a hwnd returned from the cursor pos
         if hwnd.is_none() {
-            let cursor_pos_hwnd = WindowsApi::window_at_cursor_pos()?;
+            let cursor_pos_hwnd = platform::current().window_at_cursor_pos()?;
 
             for monitor in self.monitors() {
                 for ws in monitor.workspaces() {
@@ -715,7 +718,7 @@ impl WindowManager {
                     // Sometimes we need this check, because the focus may have been given by a click
                     // to a non-window such as the taskbar or system tray, and komorebi doesn't know that
                     // the focused window of the workspace is not actually focused by the OS at that point
-                    || WindowsApi::foreground_window()? == hwnd
+                    || platform::current().foreground_window()? == hwnd
             {
                 return Ok(());
             }
@@ -726,7 +729,7 @@ impl WindowManager {
         } else {
             tracing::debug!(
                 "not raising unknown window: {}",
-                Window::from(WindowsApi::window_at_cursor_pos()?)
+                Window::from(platform::current().window_at_cursor_pos()?)
             );
         }
 
@@ -850,12 +853,12 @@ impl WindowManager {
                     window.focus(self.mouse_follows_focus)?;
                 }
             } else {
-                let desktop_window = Window::from(WindowsApi::desktop_window()?);
+                let desktop_window = Window::from(platform::current().desktop_window()?);
 
                 let rect = self.focused_monitor_size()?;
-                WindowsApi::center_cursor_in_rect(&rect)?;
+                platform::current().center_cursor_in_rect(&rect)?;
 
-                match WindowsApi::raise_and_focus_window(desktop_window.hwnd) {
+                match platform::current().raise_and_focus_window(desktop_window.hwnd) {
                     Ok(()) => {}
                     Err(error) => {
                         tracing::warn!("{} {}:{}", error, file!(), line!());
@@ -864,9 +867,9 @@ impl WindowManager {
             }
         } else {
             if self.focused_workspace()?.is_empty() {
-                let desktop_window = Window::from(WindowsApi::desktop_window()?);
+                let desktop_window = Window::from(platform::current().desktop_window()?);
 
-                match WindowsApi::raise_and_focus_window(desktop_window.hwnd) {
+                match platform::current().raise_and_focus_window(desktop_window.hwnd) {
                     Ok(()) => {}
                     Err(error) => {
                         tracing::warn!("{} {}:{}", error, file!(), line!());
@@ -914,19 +917,23 @@ impl WindowManager {
         delta: i32,
         update: bool,
     ) -> Result<()> {
-        let work_area = self.focused_monitor_work_area()?;
+        let work_area = self.focused_workspace_work_area()?;
         let workspace = self.focused_workspace_mut()?;
 
         match workspace.layout() {
-            Layout::Default(layout) => {
+            Layout::Default(_) | Layout::Custom(_) => {
                 tracing::info!("resizing window");
                 let len = NonZeroUsize::new(workspace.containers().len())
                     .ok_or_else(|| anyhow!("there must be at least one container"))?;
                 let focused_idx = workspace.focused_container_idx();
+
+                // Resize dimensions are stored relative to the work area, but the resizing itself
+                // is done in pixels
                 let focused_idx_resize = workspace
                     .resize_dimensions()
                     .get(focused_idx)
-                    .ok_or_else(|| anyhow!("there is no resize adjustment for this container"))?;
+                    .ok_or_else(|| anyhow!("there is no resize adjustment for this container"))?
+                    .map(|resize| resize.to_rect(&work_area));
 
                 if direction
                     .destination(
@@ -934,22 +941,32 @@ impl WindowManager {
                         workspace.layout_flip(),
                         focused_idx,
                         len,
+                        workspace.layout_options(),
                     )
                     .is_some()
                 {
-                    let unaltered = layout.calculate(
+                    let unaltered = workspace.layout().as_boxed_arrangement().calculate(
                         &work_area,
                         len,
                         workspace.container_padding(),
                         workspace.layout_flip(),
                         &[],
+                        workspace.focused_container_idx(),
+                        workspace.layout_options(),
+                        workspace.latest_layout(),
                     );
 
                     let mut direction = direction;
 
                     // We only ever want to operate on the unflipped Rect positions when resizing, then we
-                    // can flip them however they need to be flipped once the resizing has been done
-                    if let Some(flip) = workspace.layout_flip() {
+                    // can flip them however they need to be flipped once the resizing has been done.
+                    // Custom layouts are never flipped
+                    let layout_flip = match workspace.layout() {
+                        Layout::Default(_) => workspace.layout_flip(),
+                        Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => None,
+                    };
+
+                    if let Some(flip) = layout_flip {
                         match flip {
                             Axis::Horizontal => {
                                 if matches!(direction, OperationDirection::Left)
@@ -969,17 +986,18 @@ impl WindowManager {
                         }
                     }
 
-                    let resize = layout.resize(
+                    let resize = workspace.layout().resize(
                         unaltered
                             .get(focused_idx)
                             .ok_or_else(|| anyhow!("there is no last layout"))?,
-                        focused_idx_resize,
+                        &focused_idx_resize,
                         direction,
                         sizing,
                         delta,
                     );
 
-                    workspace.resize_dimensions_mut()[focused_idx] = resize;
+                    workspace.resize_dimensions_mut()[focused_idx] =
+                        resize.map(|resize| RelativeRect::from_rect(&resize, &work_area));
 
                     return if update {
                         self.update_focused_workspace(false, false)
@@ -990,8 +1008,11 @@ impl WindowManager {
 
                 tracing::warn!("cannot resize container in this direction");
             }
-            Layout::Custom(_) => {
-                tracing::warn!("containers cannot be resized when using custom layouts");
+            Layout::CustomTree(_) => {
+                tracing::warn!("containers cannot be resized when using tree layouts");
+            }
+            Layout::External(_) => {
+                tracing::warn!("containers cannot be resized when using external layouts");
             }
         }
         Ok(())
@@ -1003,6 +1024,7 @@ impl WindowManager {
 
         let no_titlebar = NO_TITLEBAR.lock();
         let known_transparent_hwnds = transparency_manager::known_hwnds();
+        transparency_manager::clear_window_alphas();
         let border_implementation = border_manager::IMPLEMENTATION.load();
 
         for monitor in self.monitors_mut() {
@@ -1035,6 +1057,10 @@ impl WindowManager {
             }
         }
 
+        for hwnd in MANAGE_ACTION_NO_TITLEBAR.lock().drain() {
+            Window::from(hwnd).add_title_bar()?;
+        }
+
         Ok(())
     }
 
@@ -1068,7 +1094,7 @@ impl WindowManager {
             OperationBehaviour::NoOp
         ) {
             let workspace = self.focused_workspace()?;
-            let focused_hwnd = WindowsApi::foreground_window()?;
+            let focused_hwnd = platform::current().foreground_window()?;
             if !workspace.contains_managed_window(focused_hwnd) {
                 bail!("ignoring commands while active window is not managed by komorebi");
             }
@@ -1198,7 +1224,7 @@ impl WindowManager {
             bail!("cannot move native maximized window to another monitor or workspace");
         }
 
-        let foreground_hwnd = WindowsApi::foreground_window()?;
+        let foreground_hwnd = platform::current().foreground_window()?;
         let floating_window_index = workspace
             .floating_windows()
             .iter()
@@ -1336,7 +1362,10 @@ impl WindowManager {
         let new_idx = if workspace.monocle_container().is_some() {
             None
         } else {
-            workspace.new_idx_for_direction(direction)
+            match self.navigation_behaviour {
+                NavigationBehaviour::Layout => workspace.new_idx_for_direction(direction),
+                NavigationBehaviour::Geometric => workspace.geometric_idx_for_direction(direction),
+            }
         };
 
         let mut cross_monitor_monocle = false;
@@ -1377,11 +1406,13 @@ impl WindowManager {
                     match direction {
                         OperationDirection::Left => match focused_workspace.layout() {
                             Layout::Default(layout) => {
-                                let target_index =
-                                    layout.rightmost_index(focused_workspace.containers().len());
+                                let target_index = layout.rightmost_index(
+                                    focused_workspace.containers().len(),
+                                    focused_workspace.layout_options(),
+                                );
                                 focused_workspace.focus_container(target_index);
                             }
-                            Layout::Custom(_) => {
+                            Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                                 focused_workspace.focus_container(
                                     focused_workspace.containers().len().saturating_sub(1),
                                 );
@@ -1389,11 +1420,13 @@ impl WindowManager {
                         },
                         OperationDirection::Right => match focused_workspace.layout() {
                             Layout::Default(layout) => {
-                                let target_index =
-                                    layout.leftmost_index(focused_workspace.containers().len());
+                                let target_index = layout.leftmost_index(
+                                    focused_workspace.containers().len(),
+                                    focused_workspace.layout_options(),
+                                );
                                 focused_workspace.focus_container(target_index);
                             }
-                            Layout::Custom(_) => {
+                            Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                                 focused_workspace.focus_container(0);
                             }
                         },
@@ -1419,9 +1452,9 @@ impl WindowManager {
                     if let Some(monocle) = focused_workspace.monocle_container() {
                         if let Some(window) = monocle.focused_window() {
                             window.focus(mouse_follows_focus)?;
-                            WindowsApi::center_cursor_in_rect(&WindowsApi::window_rect(
-                                window.hwnd,
-                            )?)?;
+                            platform::current().center_cursor_in_rect(
+                                &platform::current().window_rect(window.hwnd)?,
+                            )?;
 
                             cross_monitor_monocle = true;
                         }
@@ -1429,11 +1462,13 @@ impl WindowManager {
                         match direction {
                             OperationDirection::Left => match focused_workspace.layout() {
                                 Layout::Default(layout) => {
-                                    let target_index = layout
-                                        .rightmost_index(focused_workspace.containers().len());
+                                    let target_index = layout.rightmost_index(
+                                        focused_workspace.containers().len(),
+                                        focused_workspace.layout_options(),
+                                    );
                                     focused_workspace.focus_container(target_index);
                                 }
-                                Layout::Custom(_) => {
+                                Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                                     focused_workspace.focus_container(
                                         focused_workspace.containers().len().saturating_sub(1),
                                     );
@@ -1441,11 +1476,13 @@ impl WindowManager {
                             },
                             OperationDirection::Right => match focused_workspace.layout() {
                                 Layout::Default(layout) => {
-                                    let target_index =
-                                        layout.leftmost_index(focused_workspace.containers().len());
+                                    let target_index = layout.leftmost_index(
+                                        focused_workspace.containers().len(),
+                                        focused_workspace.layout_options(),
+                                    );
                                     focused_workspace.focus_container(target_index);
                                 }
-                                Layout::Custom(_) => {
+                                Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                                     focused_workspace.focus_container(0);
                                 }
                             },
@@ -1457,6 +1494,14 @@ impl WindowManager {
             Some(idx) => {
                 let workspace = self.focused_workspace_mut()?;
                 workspace.focus_container(idx);
+
+                // scrolling layouts need to be retiled to bring the focused column into view
+                if matches!(
+                    workspace.layout(),
+                    Layout::Default(DefaultLayout::Scrolling)
+                ) {
+                    self.update_focused_workspace(false, false)?;
+                }
             }
         }
 
@@ -1486,7 +1531,10 @@ impl WindowManager {
 
         let origin_container_idx = workspace.focused_container_idx();
         let origin_monitor_idx = self.focused_monitor_idx();
-        let target_container_idx = workspace.new_idx_for_direction(direction);
+        let target_container_idx = match self.navigation_behaviour {
+            NavigationBehaviour::Layout => workspace.new_idx_for_direction(direction),
+            NavigationBehaviour::Geometric => workspace.geometric_idx_for_direction(direction),
+        };
 
         // this is for when we are scrolling across workspaces like PaperWM
         if target_container_idx.is_none()
@@ -1590,7 +1638,7 @@ impl WindowManager {
                                         target_workspace.add_container_to_back(origin_container);
                                     }
                                 },
-                                Layout::Custom(_) => {
+                                Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                                     target_workspace.add_container_to_back(origin_container);
                                 }
                             }
@@ -1601,12 +1649,15 @@ impl WindowManager {
                             // right (front = left side of the target)
                             match target_workspace.layout() {
                                 Layout::Default(layout) => {
-                                    let target_index =
-                                        layout.leftmost_index(target_workspace.containers().len());
+                                    let target_index = layout.leftmost_index(
+                                        target_workspace.containers().len(),
+                                        target_workspace.layout_options(),
+                                    );
 
                                     match layout {
                                         DefaultLayout::RightMainVerticalStack
-                                        | DefaultLayout::UltrawideVerticalStack => {
+                                        | DefaultLayout::UltrawideVerticalStack
+                                        | DefaultLayout::CenteredMaster => {
                                             if target_workspace.containers().len() == 1 {
                                                 target_workspace
                                                     .add_container_to_back(origin_container);
@@ -1625,7 +1676,7 @@ impl WindowManager {
                                         }
                                     }
                                 }
-                                Layout::Custom(_) => {
+                                Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {
                                     target_workspace.add_container_to_front(origin_container);
                                 }
                             }
@@ -1704,7 +1755,7 @@ impl WindowManager {
                     .ok_or_else(|| anyhow!("there is no monitor at this index"))?
                     .id();
 
-                if !WindowsApi::monitors_have_same_dpi(a, b)? {
+                if !platform::current().monitors_have_same_dpi(a, b)? {
                     self.update_focused_workspace(self.mouse_follows_focus, true)?;
                 }
             }
@@ -1905,6 +1956,7 @@ impl WindowManager {
                 workspace.layout_flip(),
                 workspace.focused_container_idx(),
                 len,
+                workspace.layout_options(),
             )
             .is_some();
 
@@ -1970,7 +2022,7 @@ impl WindowManager {
         tracing::info!("promoting focus");
 
         let target_idx = match workspace.layout() {
-            Layout::Default(_) => 0,
+            Layout::Default(_) | Layout::CustomTree(_) | Layout::External(_) => 0,
             Layout::Custom(custom) => custom
                 .first_container_idx(custom.primary_idx().map_or(0, |primary_idx| primary_idx)),
         };
@@ -2004,7 +2056,7 @@ impl WindowManager {
 
     #[tracing::instrument(skip(self))]
     pub fn toggle_float(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = platform::current().foreground_window()?;
         let workspace = self.focused_workspace_mut()?;
 
         let mut is_floating_window = false;
@@ -2033,17 +2085,186 @@ impl WindowManager {
         let workspace = self.focused_workspace_mut()?;
         workspace.new_floating_window()?;
 
-        let window = workspace
-            .floating_windows_mut()
-            .last_mut()
+        let mut window = *workspace
+            .floating_windows()
+            .last()
             .ok_or_else(|| anyhow!("there is no floating window"))?;
 
-        window.center(&work_area)?;
+        if !self.apply_floating_placement(window)? {
+            window.center(&work_area)?;
+        }
+
         window.focus(self.mouse_follows_focus)?;
 
         Ok(())
     }
 
+    /// Place a window which has just been added to the floating windows of the focused workspace
+    /// according to the first floating placement rule which matches it, moving it to the focused
+    /// workspace of another monitor if the rule names one. Returns false if no rule matches
+    #[tracing::instrument(skip(self))]
+    pub fn apply_floating_placement(&mut self, window: Window) -> Result<bool> {
+        let Some(rule) = window.floating_placement()? else {
+            return Ok(false);
+        };
+
+        tracing::info!("applying floating placement");
+
+        let focused_monitor_idx = self.focused_monitor_idx();
+        let monitor_idx = rule
+            .monitor
+            .as_ref()
+            .and_then(|name| {
+                self.monitors().iter().position(|monitor| {
+                    monitor.name() == name
+                        || monitor.device() == name
+                        || monitor.device_id() == name
+                })
+            })
+            .unwrap_or(focused_monitor_idx);
+
+        if monitor_idx != focused_monitor_idx {
+            self.focused_workspace_mut()?
+                .floating_windows_mut()
+                .retain(|w| w.hwnd != window.hwnd);
+
+            self.monitors_mut()
+                .get_mut(monitor_idx)
+                .ok_or_else(|| anyhow!("there is no monitor with that index"))?
+                .focused_workspace_mut()
+                .ok_or_else(|| anyhow!("there is no workspace"))?
+                .floating_windows_mut()
+                .push(window);
+        }
+
+        let work_area = *self
+            .monitors()
+            .get(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor with that index"))?
+            .work_area_size();
+
+        let rect = platform::current().window_rect(window.hwnd)?;
+        window.set_position(&rule.placement.rect(&rect, &work_area), true)?;
+
+        Ok(true)
+    }
+
+    /// Take the actions of every manage action rule which matches a window that has just been
+    /// added to the focused workspace. This should be called before the workspace is updated so
+    /// that the window is only laid out once
+    #[tracing::instrument(skip(self))]
+    pub fn apply_manage_actions(&mut self, window: Window) -> Result<()> {
+        let actions = window.manage_actions()?;
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        tracing::info!("applying manage actions");
+
+        for action in actions {
+            let workspace = self.focused_workspace_mut()?;
+
+            // Floating windows, and windows which have already been moved into a monocle
+            // container or maximized by an earlier action, are not in the layout
+            let container_idx = workspace
+                .containers()
+                .iter()
+                .position(|container| container.contains_window(window.hwnd));
+
+            match action {
+                ManageAction::Transparency(alpha) => {
+                    transparency_manager::set_window_alpha(window.hwnd, alpha);
+                    window.set_alpha(alpha)?;
+                }
+                ManageAction::RemoveTitleBar => {
+                    // only this window is tracked, other windows of the same application keep
+                    // their title bars
+                    MANAGE_ACTION_NO_TITLEBAR.lock().insert(window.hwnd);
+                    window.remove_title_bar()?;
+                }
+                ManageAction::StackWithSameExe => {
+                    let Some(idx) = container_idx else {
+                        continue;
+                    };
+
+                    let exe_name = window.exe()?;
+                    let target_idx = workspace
+                        .containers()
+                        .iter()
+                        .enumerate()
+                        .find(|(i, container)| {
+                            *i != idx && container.hwnd_from_exe(&exe_name).is_some()
+                        })
+                        .map(|(i, _)| i);
+
+                    if let Some(target_idx) = target_idx {
+                        workspace.focus_container_by_window(window.hwnd)?;
+                        workspace.move_window_to_container(target_idx)?;
+                    }
+                }
+                ManageAction::LayoutIndex(target_idx) => {
+                    let Some(idx) = container_idx else {
+                        continue;
+                    };
+
+                    let target_idx = target_idx.min(workspace.containers().len() - 1);
+                    if idx == target_idx {
+                        continue;
+                    }
+
+                    let resize = if idx < workspace.resize_dimensions().len() {
+                        Option::from(workspace.resize_dimensions_mut().remove(idx))
+                    } else {
+                        None
+                    };
+
+                    let container = workspace
+                        .containers_mut()
+                        .remove(idx)
+                        .ok_or_else(|| anyhow!("there is no container"))?;
+
+                    workspace.insert_container_at_idx(target_idx, container);
+
+                    if let Some(resize) = resize {
+                        if target_idx <= workspace.resize_dimensions().len() {
+                            workspace.resize_dimensions_mut().insert(target_idx, resize);
+                        }
+                    }
+                }
+                ManageAction::Monocle => {
+                    if container_idx.is_none()
+                        || workspace.monocle_container().is_some()
+                        || workspace.maximized_window().is_some()
+                    {
+                        continue;
+                    }
+
+                    workspace.focus_container_by_window(window.hwnd)?;
+                    self.monocle_on()?;
+                }
+                ManageAction::Maximize => {
+                    // new_maximized_window prefers a floating window if one has focus
+                    let foreground_hwnd = platform::current().foreground_window()?;
+                    if container_idx.is_none()
+                        || workspace.monocle_container().is_some()
+                        || workspace.maximized_window().is_some()
+                        || workspace
+                            .floating_windows()
+                            .iter()
+                            .any(|w| w.hwnd == foreground_hwnd)
+                    {
+                        continue;
+                    }
+
+                    workspace.focus_container_by_window(window.hwnd)?;
+                    self.maximize_window()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn unfloat_window(&mut self) -> Result<()> {
         tracing::info!("unfloating window");
@@ -2128,6 +2349,11 @@ impl WindowManager {
     pub fn flip_layout(&mut self, layout_flip: Axis) -> Result<()> {
         let workspace = self.focused_workspace_mut()?;
 
+        // Custom layouts are never flipped when they are calculated or resized
+        if matches!(workspace.layout(), Layout::Custom(_)) {
+            bail!("custom layouts cannot be flipped");
+        }
+
         tracing::info!("flipping layout");
 
         #[allow(clippy::match_same_arms)]
@@ -2174,7 +2400,7 @@ impl WindowManager {
         let workspace = self.focused_workspace_mut()?;
 
         match workspace.layout() {
-            Layout::Default(_) => {}
+            Layout::Default(_) | Layout::CustomTree(_) | Layout::External(_) => {}
             Layout::Custom(layout) => {
                 let primary_idx =
                     layout.first_container_idx(layout.primary_idx().ok_or_else(|| {
@@ -2192,6 +2418,87 @@ impl WindowManager {
         self.update_focused_workspace(self.mouse_follows_focus, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn change_workspace_layout_external(&mut self, name: String) -> Result<()> {
+        tracing::info!("changing layout");
+
+        let workspace = self.focused_workspace_mut()?;
+
+        match workspace.layout() {
+            Layout::Default(_) | Layout::CustomTree(_) | Layout::External(_) => {}
+            Layout::Custom(layout) => {
+                let primary_idx =
+                    layout.first_container_idx(layout.primary_idx().ok_or_else(|| {
+                        anyhow!("this custom layout does not have a primary column")
+                    })?);
+
+                if !workspace.containers().is_empty() && primary_idx < workspace.containers().len()
+                {
+                    workspace.swap_containers(0, primary_idx);
+                }
+            }
+        }
+
+        workspace.set_layout(Layout::External(name));
+        workspace.set_layout_flip(None);
+        self.update_focused_workspace(self.mouse_follows_focus, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn set_scrolling_layout_columns(&mut self, columns: NonZeroUsize) -> Result<()> {
+        tracing::info!("setting scrolling layout columns");
+
+        let workspace = self.focused_workspace_mut()?;
+
+        let mut layout_options = workspace.layout_options().unwrap_or_default();
+        layout_options.scrolling = Option::from(ScrollingLayoutOptions {
+            columns: columns.get(),
+        });
+
+        workspace.set_layout_options(Option::from(layout_options));
+        self.update_focused_workspace(self.mouse_follows_focus, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn adjust_master_count(&mut self, sizing: Sizing) -> Result<()> {
+        tracing::info!("adjusting master count");
+
+        let workspace = self.focused_workspace_mut()?;
+
+        // There is no point in growing the master area beyond the number of containers
+        let container_count = workspace.containers().len().max(1);
+
+        let mut layout_options = workspace.layout_options().unwrap_or_default();
+        let master_count = layout_options.master_count();
+        layout_options.master_count = Option::from(match sizing {
+            Sizing::Increase => (master_count + 1).min(container_count),
+            Sizing::Decrease => master_count.saturating_sub(1).max(1),
+        });
+
+        workspace.set_layout_options(Option::from(layout_options));
+        self.update_focused_workspace(self.mouse_follows_focus, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn adjust_master_ratio(&mut self, sizing: Sizing) -> Result<()> {
+        tracing::info!("adjusting master ratio");
+
+        let workspace = self.focused_workspace_mut()?;
+
+        let mut layout_options = workspace.layout_options().unwrap_or_default();
+        let master_ratio = layout_options.master_ratio();
+        layout_options.master_ratio = Option::from(match sizing {
+            Sizing::Increase => master_ratio + MASTER_RATIO_STEP,
+            Sizing::Decrease => master_ratio - MASTER_RATIO_STEP,
+        });
+
+        // Store the clamped value so that repeated adjustments past the limits are not accumulated
+        layout_options.master_ratio = Option::from(layout_options.master_ratio());
+
+        workspace.set_layout_options(Option::from(layout_options));
+        self.update_focused_workspace(self.mouse_follows_focus, false)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn cycle_layout(&mut self, direction: CycleDirection) -> Result<()> {
         tracing::info!("cycling layout");
@@ -2209,7 +2516,7 @@ impl WindowManager {
                 tracing::info!("next layout: {new_layout}");
                 workspace.set_layout(Layout::Default(new_layout));
             }
-            Layout::Custom(_) => {}
+            Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {}
         }
 
         self.update_focused_workspace(self.mouse_follows_focus, false)
@@ -2222,25 +2529,28 @@ impl WindowManager {
     {
         tracing::info!("changing layout");
 
-        let layout = CustomLayout::from_path(path)?;
+        let layout = Layout::from_custom_layout_path(path)?;
         let workspace = self.focused_workspace_mut()?;
 
         match workspace.layout() {
             Layout::Default(_) => {
-                let primary_idx =
-                    layout.first_container_idx(layout.primary_idx().ok_or_else(|| {
-                        anyhow!("this custom layout does not have a primary column")
-                    })?);
-
-                if !workspace.containers().is_empty() && primary_idx < workspace.containers().len()
-                {
-                    workspace.swap_containers(0, primary_idx);
+                if let Layout::Custom(custom) = &layout {
+                    let primary_idx =
+                        custom.first_container_idx(custom.primary_idx().ok_or_else(|| {
+                            anyhow!("this custom layout does not have a primary column")
+                        })?);
+
+                    if !workspace.containers().is_empty()
+                        && primary_idx < workspace.containers().len()
+                    {
+                        workspace.swap_containers(0, primary_idx);
+                    }
                 }
             }
-            Layout::Custom(_) => {}
+            Layout::Custom(_) | Layout::CustomTree(_) | Layout::External(_) => {}
         }
 
-        workspace.set_layout(Layout::Custom(layout));
+        workspace.set_layout(layout);
         workspace.set_layout_flip(None);
         self.update_focused_workspace(self.mouse_follows_focus, false)
     }
@@ -2275,6 +2585,32 @@ impl WindowManager {
         self.update_focused_workspace(false, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn adjust_inner_gap(&mut self, sizing: Sizing, adjustment: i32) -> Result<()> {
+        tracing::info!("adjusting inner gap");
+
+        let workspace = self.focused_workspace_mut()?;
+
+        // Without an inner gap, adjustments start from the gap given by the container padding
+        let gap = workspace
+            .inner_gap()
+            .unwrap_or_else(|| workspace.container_padding().unwrap_or_default() * 2);
+
+        workspace.set_inner_gap(Option::from(sizing.adjust_by(gap, adjustment)));
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_smart_gaps(&mut self) -> Result<()> {
+        tracing::info!("toggling smart gaps");
+
+        let workspace = self.focused_workspace_mut()?;
+        workspace.set_smart_gaps(!workspace.smart_gaps());
+
+        self.update_focused_workspace(false, false)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn set_workspace_tiling(
         &mut self,
@@ -2386,11 +2722,11 @@ impl WindowManager {
             .get_mut(workspace_idx)
             .ok_or_else(|| anyhow!("there is no monitor"))?;
 
-        let layout = CustomLayout::from_path(path)?;
+        let layout = Layout::from_custom_layout_path(path)?;
 
         let rules: &mut Vec<(usize, Layout)> = workspace.layout_rules_mut();
         rules.retain(|pair| pair.0 != at_container_count);
-        rules.push((at_container_count, Layout::Custom(layout)));
+        rules.push((at_container_count, layout));
         rules.sort_by(|a, b| a.0.cmp(&b.0));
 
         // If this is the focused workspace on a non-focused screen, let's update it
@@ -2505,7 +2841,7 @@ impl WindowManager {
         P: AsRef<Path> + std::fmt::Debug,
     {
         tracing::info!("setting workspace layout");
-        let layout = CustomLayout::from_path(path)?;
+        let layout = Layout::from_custom_layout_path(path)?;
         let offset = self.work_area_offset;
         let focused_monitor_idx = self.focused_monitor_idx();
 
@@ -2532,7 +2868,7 @@ impl WindowManager {
             .get_mut(workspace_idx)
             .ok_or_else(|| anyhow!("there is no monitor"))?;
 
-        workspace.set_layout(Layout::Custom(layout));
+        workspace.set_layout(layout);
         workspace.set_layout_flip(None);
 
         // If this is the focused workspace on a non-focused screen, let's update it
@@ -2659,6 +2995,78 @@ impl WindowManager {
         self.update_focused_workspace(false, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn set_workspace_inner_gap(
+        &mut self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        size: i32,
+    ) -> Result<()> {
+        tracing::info!("setting workspace inner gap");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        workspace.set_inner_gap(Option::from(size));
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn set_workspace_outer_gap(
+        &mut self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        padding: SidePadding,
+    ) -> Result<()> {
+        tracing::info!("setting workspace outer gap");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        workspace.set_outer_gap(Option::from(padding));
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn set_workspace_smart_gaps(
+        &mut self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        enable: bool,
+    ) -> Result<()> {
+        tracing::info!("setting workspace smart gaps");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        workspace.set_smart_gaps(enable);
+
+        self.update_focused_workspace(false, false)
+    }
+
     pub fn focused_monitor_size(&self) -> Result<Rect> {
         Ok(*self
             .focused_monitor()
@@ -2673,6 +3081,14 @@ impl WindowManager {
             .work_area_size())
     }
 
+    /// The work area of the focused monitor with the offsets and padding of the focused workspace
+    /// applied, which is what resize dimensions are relative to
+    pub fn focused_workspace_work_area(&self) -> Result<Rect> {
+        self.focused_monitor()
+            .ok_or_else(|| anyhow!("there is no monitor"))?
+            .focused_workspace_work_area(self.work_area_offset)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn focus_monitor(&mut self, idx: usize) -> Result<()> {
         tracing::info!("focusing monitor");
@@ -2687,7 +3103,7 @@ impl WindowManager {
     }
 
     pub fn monitor_idx_from_window(&mut self, window: Window) -> Option<usize> {
-        let hmonitor = WindowsApi::monitor_from_window(window.hwnd);
+        let hmonitor = platform::current().monitor_from_window(window.hwnd);
 
         for (i, monitor) in self.monitors().iter().enumerate() {
             if monitor.id() == hmonitor {
@@ -2696,21 +3112,13 @@ impl WindowManager {
         }
 
         // our hmonitor might be stale, so if we didn't return above, try querying via the latest
-        // info taken from win32_display_data and update our hmonitor while we're at it
-        if let Ok(latest) = WindowsApi::monitor(hmonitor) {
-            for (i, monitor) in self.monitors_mut().iter_mut().enumerate() {
-                if monitor.device_id() == latest.device_id() {
-                    monitor.set_id(latest.id());
-                    return Option::from(i);
-                }
-            }
-        }
-
-        None
+        // info taken from the platform and update our hmonitor while we're at it
+        self.update_stale_monitor_id(hmonitor)
     }
 
     pub fn monitor_idx_from_current_pos(&mut self) -> Option<usize> {
-        let hmonitor = WindowsApi::monitor_from_point(WindowsApi::cursor_pos().ok()?);
+        let platform = platform::current();
+        let hmonitor = platform.monitor_from_point(platform.cursor_pos().ok()?);
 
         for (i, monitor) in self.monitors().iter().enumerate() {
             if monitor.id() == hmonitor {
@@ -2719,13 +3127,21 @@ impl WindowManager {
         }
 
         // our hmonitor might be stale, so if we didn't return above, try querying via the latest
-        // info taken from win32_display_data and update our hmonitor while we're at it
-        if let Ok(latest) = WindowsApi::monitor(hmonitor) {
-            for (i, monitor) in self.monitors_mut().iter_mut().enumerate() {
-                if monitor.device_id() == latest.device_id() {
-                    monitor.set_id(latest.id());
-                    return Option::from(i);
-                }
+        // info taken from the platform and update our hmonitor while we're at it
+        self.update_stale_monitor_id(hmonitor)
+    }
+
+    fn update_stale_monitor_id(&mut self, hmonitor: isize) -> Option<usize> {
+        let latest = platform::current()
+            .monitors()
+            .ok()?
+            .into_iter()
+            .find(|monitor| monitor.id == hmonitor)?;
+
+        for (i, monitor) in self.monitors_mut().iter_mut().enumerate() {
+            if *monitor.device_id() == latest.device_id {
+                monitor.set_id(latest.id);
+                return Option::from(i);
             }
         }
 
@@ -2786,8 +3202,23 @@ impl WindowManager {
             .focused_monitor_mut()
             .ok_or_else(|| anyhow!("there is no workspace"))?;
 
+        let previous_idx = monitor.focused_workspace_idx();
+        let transition = *WORKSPACE_TRANSITION.lock();
+        let animation_settings = AnimationSettings::current();
+
         monitor.focus_workspace(idx)?;
-        monitor.load_focused_workspace(mouse_follows_focus)?;
+
+        match transition {
+            Some(transition) if animation_settings.enabled && previous_idx != idx => {
+                monitor.load_focused_workspace_with_transition(
+                    previous_idx,
+                    transition,
+                    animation_settings,
+                    mouse_follows_focus,
+                )?;
+            }
+            _ => monitor.load_focused_workspace(mouse_follows_focus)?,
+        }
 
         self.update_focused_workspace(false, true)
     }
@@ -5,11 +5,9 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::window::should_act;
 use crate::window::Window;
 use crate::winevent::WinEvent;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
-use crate::REGEX_IDENTIFIERS;
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type", content = "content")]
@@ -176,22 +174,15 @@ impl WindowManagerEvent {
                 // [yatta\src\windows_event.rs:110] event = 32779 ObjectLocationChange
 
                 let object_name_change_on_launch = OBJECT_NAME_CHANGE_ON_LAUNCH.lock();
-                let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
                 let title = &window.title().ok()?;
                 let exe_name = &window.exe().ok()?;
                 let class = &window.class().ok()?;
                 let path = &window.path().ok()?;
 
-                let should_trigger_show = should_act(
-                    title,
-                    exe_name,
-                    class,
-                    path,
-                    &object_name_change_on_launch,
-                    &regex_identifiers,
-                )
-                .is_some();
+                let should_trigger_show = object_name_change_on_launch
+                    .matching_rule(title, exe_name, class, path)
+                    .is_some();
 
                 // should not trigger show on minimized windows, for example when firefox sends
                 // this message due to youtube autoplay changing the window title
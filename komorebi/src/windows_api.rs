@@ -144,8 +144,6 @@ use crate::ring::Ring;
 use crate::set_window_position::SetWindowPosition;
 use crate::windows_callbacks;
 use crate::Window;
-use crate::DISPLAY_INDEX_PREFERENCES;
-use crate::MONITOR_INDEX_PREFERENCES;
 
 macro_rules! as_ptr {
     ($value:expr) => {
@@ -252,74 +250,6 @@ impl WindowsApi {
             .collect::<Vec<_>>())
     }
 
-    pub fn load_monitor_information(monitors: &mut Ring<Monitor>) -> Result<()> {
-        'read: for display in win32_display_data::connected_displays_all().flatten() {
-            let path = display.device_path.clone();
-
-            let (device, device_id) = if path.is_empty() {
-                (String::from("UNKNOWN"), String::from("UNKNOWN"))
-            } else {
-                let mut split: Vec<_> = path.split('#').collect();
-                split.remove(0);
-                split.remove(split.len() - 1);
-                let device = split[0].to_string();
-                let device_id = split.join("-");
-                (device, device_id)
-            };
-
-            let name = display.device_name.trim_start_matches(r"\\.\").to_string();
-            let name = name.split('\\').collect::<Vec<_>>()[0].to_string();
-
-            for monitor in monitors.elements() {
-                if device_id.eq(monitor.device_id()) {
-                    continue 'read;
-                }
-            }
-
-            let m = monitor::new(
-                display.hmonitor,
-                display.size.into(),
-                display.work_area_size.into(),
-                name,
-                device,
-                device_id,
-            );
-
-            let mut index_preference = None;
-            let monitor_index_preferences = MONITOR_INDEX_PREFERENCES.lock();
-            for (index, monitor_size) in &*monitor_index_preferences {
-                if m.size() == monitor_size {
-                    index_preference = Option::from(index);
-                }
-            }
-
-            let display_index_preferences = DISPLAY_INDEX_PREFERENCES.lock();
-            for (index, id) in &*display_index_preferences {
-                if id.eq(m.device_id()) {
-                    index_preference = Option::from(index);
-                }
-            }
-
-            if monitors.elements().is_empty() {
-                monitors.elements_mut().push_back(m);
-            } else if let Some(preference) = index_preference {
-                while *preference > monitors.elements().len() {
-                    monitors.elements_mut().push_back(Monitor::placeholder());
-                }
-
-                monitors.elements_mut().insert(*preference, m);
-            } else {
-                monitors.elements_mut().push_back(m);
-            }
-        }
-
-        monitors
-            .elements_mut()
-            .retain(|m| m.name().ne("PLACEHOLDER"));
-
-        Ok(())
-    }
-
     pub fn enum_windows(callback: WNDENUMPROC, callback_data_address: isize) -> Result<()> {
         unsafe { EnumWindows(callback, LPARAM(callback_data_address)) }.process()
     }
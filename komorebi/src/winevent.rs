@@ -4,90 +4,92 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::Display;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_AIA_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_AIA_START;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_CARET;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_END_APPLICATION;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_LAYOUT;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_START_APPLICATION;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_UPDATE_REGION;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_UPDATE_SCROLL;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_CONSOLE_UPDATE_SIMPLE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_ACCELERATORCHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_CLOAKED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_CONTENTSCROLLED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_CREATE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DEFACTIONCHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DESCRIPTIONCHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DESTROY;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DRAGCANCEL;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DRAGCOMPLETE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DRAGDROPPED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DRAGENTER;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DRAGLEAVE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_DRAGSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_FOCUS;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_HELPCHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_HIDE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_HOSTEDOBJECTSINVALIDATED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_IME_CHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_IME_HIDE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_IME_SHOW;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_INVOKED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_LIVEREGIONCHANGED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_LOCATIONCHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_NAMECHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_PARENTCHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_REORDER;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_SELECTION;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_SELECTIONADD;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_SELECTIONREMOVE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_SELECTIONWITHIN;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_SHOW;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_STATECHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_TEXTEDIT_CONVERSIONTARGETCHANGED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_TEXTSELECTIONCHANGED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_UNCLOAKED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OBJECT_VALUECHANGE;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OEM_DEFINED_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_OEM_DEFINED_START;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_ALERT;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_ARRANGMENTPREVIEW;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_CAPTUREEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_CAPTURESTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_CONTEXTHELPEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_CONTEXTHELPSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_DESKTOPSWITCH;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_DIALOGEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_DIALOGSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_DRAGDROPEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_DRAGDROPSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_FOREGROUND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_IME_KEY_NOTIFICATION;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MENUEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MENUPOPUPEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MENUPOPUPSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MENUSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MINIMIZEEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MINIMIZESTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MOVESIZEEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_MOVESIZESTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SCROLLINGEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SCROLLINGSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SOUND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SWITCHEND;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SWITCHER_APPDROPPED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SWITCHER_APPGRABBED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SWITCHER_APPOVERTARGET;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SWITCHER_CANCELLED;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_SYSTEM_SWITCHSTART;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_UIA_EVENTID_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_UIA_EVENTID_START;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_UIA_PROPID_END;
-use windows::Win32::UI::WindowsAndMessaging::EVENT_UIA_PROPID_START;
+
+// https://learn.microsoft.com/en-us/windows/win32/winauto/event-constants
+const EVENT_AIA_END: u32 = 0xAFFF;
+const EVENT_AIA_START: u32 = 0xA000;
+const EVENT_CONSOLE_CARET: u32 = 0x4001;
+const EVENT_CONSOLE_END: u32 = 0x40FF;
+const EVENT_CONSOLE_END_APPLICATION: u32 = 0x4007;
+const EVENT_CONSOLE_LAYOUT: u32 = 0x4005;
+const EVENT_CONSOLE_START_APPLICATION: u32 = 0x4006;
+const EVENT_CONSOLE_UPDATE_REGION: u32 = 0x4002;
+const EVENT_CONSOLE_UPDATE_SCROLL: u32 = 0x4004;
+const EVENT_CONSOLE_UPDATE_SIMPLE: u32 = 0x4003;
+const EVENT_OBJECT_ACCELERATORCHANGE: u32 = 0x8012;
+const EVENT_OBJECT_CLOAKED: u32 = 0x8017;
+const EVENT_OBJECT_CONTENTSCROLLED: u32 = 0x8015;
+const EVENT_OBJECT_CREATE: u32 = 0x8000;
+const EVENT_OBJECT_DEFACTIONCHANGE: u32 = 0x8011;
+const EVENT_OBJECT_DESCRIPTIONCHANGE: u32 = 0x800D;
+const EVENT_OBJECT_DESTROY: u32 = 0x8001;
+const EVENT_OBJECT_DRAGCANCEL: u32 = 0x8022;
+const EVENT_OBJECT_DRAGCOMPLETE: u32 = 0x8023;
+const EVENT_OBJECT_DRAGDROPPED: u32 = 0x8026;
+const EVENT_OBJECT_DRAGENTER: u32 = 0x8024;
+const EVENT_OBJECT_DRAGLEAVE: u32 = 0x8025;
+const EVENT_OBJECT_DRAGSTART: u32 = 0x8021;
+const EVENT_OBJECT_END: u32 = 0x80FF;
+const EVENT_OBJECT_FOCUS: u32 = 0x8005;
+const EVENT_OBJECT_HELPCHANGE: u32 = 0x8010;
+const EVENT_OBJECT_HIDE: u32 = 0x8003;
+const EVENT_OBJECT_HOSTEDOBJECTSINVALIDATED: u32 = 0x8020;
+const EVENT_OBJECT_IME_CHANGE: u32 = 0x8029;
+const EVENT_OBJECT_IME_HIDE: u32 = 0x8028;
+const EVENT_OBJECT_IME_SHOW: u32 = 0x8027;
+const EVENT_OBJECT_INVOKED: u32 = 0x8013;
+const EVENT_OBJECT_LIVEREGIONCHANGED: u32 = 0x8019;
+const EVENT_OBJECT_LOCATIONCHANGE: u32 = 0x800B;
+const EVENT_OBJECT_NAMECHANGE: u32 = 0x800C;
+const EVENT_OBJECT_PARENTCHANGE: u32 = 0x800F;
+const EVENT_OBJECT_REORDER: u32 = 0x8004;
+const EVENT_OBJECT_SELECTION: u32 = 0x8006;
+const EVENT_OBJECT_SELECTIONADD: u32 = 0x8007;
+const EVENT_OBJECT_SELECTIONREMOVE: u32 = 0x8008;
+const EVENT_OBJECT_SELECTIONWITHIN: u32 = 0x8009;
+const EVENT_OBJECT_SHOW: u32 = 0x8002;
+const EVENT_OBJECT_STATECHANGE: u32 = 0x800A;
+const EVENT_OBJECT_TEXTEDIT_CONVERSIONTARGETCHANGED: u32 = 0x8030;
+const EVENT_OBJECT_TEXTSELECTIONCHANGED: u32 = 0x8014;
+const EVENT_OBJECT_UNCLOAKED: u32 = 0x8018;
+const EVENT_OBJECT_VALUECHANGE: u32 = 0x800E;
+const EVENT_OEM_DEFINED_END: u32 = 0x01FF;
+const EVENT_OEM_DEFINED_START: u32 = 0x0101;
+const EVENT_SYSTEM_ALERT: u32 = 0x0002;
+const EVENT_SYSTEM_ARRANGMENTPREVIEW: u32 = 0x8016;
+const EVENT_SYSTEM_CAPTUREEND: u32 = 0x0009;
+const EVENT_SYSTEM_CAPTURESTART: u32 = 0x0008;
+const EVENT_SYSTEM_CONTEXTHELPEND: u32 = 0x000D;
+const EVENT_SYSTEM_CONTEXTHELPSTART: u32 = 0x000C;
+const EVENT_SYSTEM_DESKTOPSWITCH: u32 = 0x0020;
+const EVENT_SYSTEM_DIALOGEND: u32 = 0x0011;
+const EVENT_SYSTEM_DIALOGSTART: u32 = 0x0010;
+const EVENT_SYSTEM_DRAGDROPEND: u32 = 0x000F;
+const EVENT_SYSTEM_DRAGDROPSTART: u32 = 0x000E;
+const EVENT_SYSTEM_END: u32 = 0x00FF;
+const EVENT_SYSTEM_FOREGROUND: u32 = 0x0003;
+const EVENT_SYSTEM_IME_KEY_NOTIFICATION: u32 = 0x0029;
+const EVENT_SYSTEM_MENUEND: u32 = 0x0005;
+const EVENT_SYSTEM_MENUPOPUPEND: u32 = 0x0007;
+const EVENT_SYSTEM_MENUPOPUPSTART: u32 = 0x0006;
+const EVENT_SYSTEM_MENUSTART: u32 = 0x0004;
+const EVENT_SYSTEM_MINIMIZEEND: u32 = 0x0017;
+const EVENT_SYSTEM_MINIMIZESTART: u32 = 0x0016;
+const EVENT_SYSTEM_MOVESIZEEND: u32 = 0x000B;
+const EVENT_SYSTEM_MOVESIZESTART: u32 = 0x000A;
+const EVENT_SYSTEM_SCROLLINGEND: u32 = 0x0013;
+const EVENT_SYSTEM_SCROLLINGSTART: u32 = 0x0012;
+const EVENT_SYSTEM_SOUND: u32 = 0x0001;
+const EVENT_SYSTEM_SWITCHEND: u32 = 0x0015;
+const EVENT_SYSTEM_SWITCHER_APPDROPPED: u32 = 0x0026;
+const EVENT_SYSTEM_SWITCHER_APPGRABBED: u32 = 0x0024;
+const EVENT_SYSTEM_SWITCHER_APPOVERTARGET: u32 = 0x0025;
+const EVENT_SYSTEM_SWITCHER_CANCELLED: u32 = 0x0027;
+const EVENT_SYSTEM_SWITCHSTART: u32 = 0x0014;
+const EVENT_UIA_EVENTID_END: u32 = 0x4EFF;
+const EVENT_UIA_EVENTID_START: u32 = 0x4E00;
+const EVENT_UIA_PROPID_END: u32 = 0x75FF;
+const EVENT_UIA_PROPID_START: u32 = 0x7500;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, Display, JsonSchema)]
 #[repr(u32)]
@@ -1,25 +1,37 @@
 use std::sync::OnceLock;
+#[cfg(windows)]
 use std::time::Duration;
 
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
+#[cfg(windows)]
 use windows::Win32::Foundation::HWND;
+#[cfg(windows)]
 use windows::Win32::UI::Accessibility::SetWinEventHook;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::EVENT_MAX;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::EVENT_MIN;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::MSG;
 
 use crate::window_manager_event::WindowManagerEvent;
+#[cfg(windows)]
 use crate::windows_callbacks;
 
 static CHANNEL: OnceLock<(Sender<WindowManagerEvent>, Receiver<WindowManagerEvent>)> =
     OnceLock::new();
 
+#[cfg(windows)]
 static EVENT_PUMP: OnceLock<std::thread::JoinHandle<()>> = OnceLock::new();
 
+#[cfg(windows)]
 pub fn start() {
     EVENT_PUMP.get_or_init(|| {
         std::thread::spawn(move || {
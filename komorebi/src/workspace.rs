@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::num::NonZeroUsize;
+use std::ops::Range;
 use std::sync::atomic::Ordering;
 
 use color_eyre::eyre::anyhow;
@@ -12,28 +14,38 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::size_constraints::apply_size_constraints;
+use crate::core::Arrangement;
 use crate::core::Axis;
-use crate::core::CustomLayout;
 use crate::core::CycleDirection;
 use crate::core::DefaultLayout;
 use crate::core::Layout;
+use crate::core::LayoutOptions;
 use crate::core::OperationDirection;
 use crate::core::Rect;
+use crate::core::RelativeRect;
+use crate::core::SidePadding;
 
 use crate::border_manager::BORDER_OFFSET;
 use crate::border_manager::BORDER_WIDTH;
 use crate::container::Container;
+use crate::external_layout::ExternalLayout;
+use crate::platform;
 use crate::ring::Ring;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
+use crate::static_config::MonitorLayoutRule;
 use crate::static_config::WorkspaceConfig;
 use crate::window::Window;
 use crate::window::WindowDetails;
-use crate::windows_api::WindowsApi;
 use crate::WindowContainerBehaviour;
 use crate::DEFAULT_CONTAINER_PADDING;
+use crate::DEFAULT_INNER_GAP;
+use crate::DEFAULT_OUTER_GAP;
+use crate::DEFAULT_SMART_GAPS;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::INITIAL_CONFIGURATION_LOADED;
+use crate::MANAGE_ACTION_NO_TITLEBAR;
 use crate::NO_TITLEBAR;
 use crate::REMOVE_TITLEBARS;
 
@@ -68,18 +80,30 @@ pub struct Workspace {
     floating_windows: Vec<Window>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     layout: Layout,
+    #[getset(get_copy = "pub", set = "pub")]
+    layout_options: Option<LayoutOptions>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     layout_rules: Vec<(usize, Layout)>,
+    #[getset(get = "pub", set = "pub")]
+    monitor_layout_rules: Vec<MonitorLayoutRule>,
     #[getset(get_copy = "pub", set = "pub")]
     layout_flip: Option<Axis>,
     #[getset(get_copy = "pub", set = "pub")]
     workspace_padding: Option<i32>,
     #[getset(get_copy = "pub", set = "pub")]
     container_padding: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    inner_gap: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    outer_gap: Option<SidePadding>,
+    #[getset(get_copy = "pub", set = "pub")]
+    smart_gaps: bool,
     #[getset(get = "pub", set = "pub")]
     latest_layout: Vec<Rect>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
-    resize_dimensions: Vec<Option<Rect>>,
+    resize_dimensions: Vec<Option<RelativeRect>>,
     #[getset(get = "pub", set = "pub")]
     tile: bool,
     #[getset(get_copy = "pub", set = "pub")]
@@ -88,6 +112,19 @@ pub struct Workspace {
     window_container_behaviour: Option<WindowContainerBehaviour>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     float_override: Option<bool>,
+    #[serde(skip)]
+    scrolled_out_hwnds: HashSet<isize>,
+    /// Windows which did not get as much space as their size constraints ask for the last time
+    /// that the layout was calculated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub")]
+    unsatisfied_size_constraints: Vec<isize>,
+    #[serde(skip)]
+    #[getset(get = "pub", set = "pub")]
+    applied_monitor_layout_rule: Option<MonitorLayoutRule>,
+    #[serde(skip)]
+    #[getset(get = "pub", set = "pub")]
+    layout_before_monitor_layout_rule: Option<Layout>,
 }
 
 impl_ring_elements!(Workspace, Container);
@@ -103,16 +140,25 @@ impl Default for Workspace {
             monocle_container_restore_idx: None,
             floating_windows: Vec::default(),
             layout: Layout::Default(DefaultLayout::BSP),
+            layout_options: None,
             layout_rules: vec![],
+            monitor_layout_rules: vec![],
             layout_flip: None,
             workspace_padding: Option::from(DEFAULT_WORKSPACE_PADDING.load(Ordering::SeqCst)),
             container_padding: Option::from(DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst)),
+            inner_gap: *DEFAULT_INNER_GAP.lock(),
+            outer_gap: *DEFAULT_OUTER_GAP.lock(),
+            smart_gaps: DEFAULT_SMART_GAPS.load(Ordering::SeqCst),
             latest_layout: vec![],
             resize_dimensions: vec![],
             tile: true,
             apply_window_based_work_area_offset: true,
             window_container_behaviour: None,
             float_override: None,
+            scrolled_out_hwnds: HashSet::new(),
+            unsatisfied_size_constraints: vec![],
+            applied_monitor_layout_rule: None,
+            layout_before_monitor_layout_rule: None,
         }
     }
 }
@@ -129,21 +175,44 @@ impl Workspace {
             self.set_workspace_padding(config.workspace_padding);
         }
 
+        if config.inner_gap.is_some() {
+            self.set_inner_gap(config.inner_gap);
+        }
+
+        if config.outer_gap.is_some() {
+            self.set_outer_gap(config.outer_gap);
+        }
+
+        if let Some(smart_gaps) = config.smart_gaps {
+            self.set_smart_gaps(smart_gaps);
+        }
+
         if let Some(layout) = &config.layout {
             self.layout = Layout::Default(*layout);
             self.tile = true;
         }
 
         if let Some(pathbuf) = &config.custom_layout {
-            let layout = CustomLayout::from_path(pathbuf)?;
-            self.layout = Layout::Custom(layout);
+            self.layout = Layout::from_custom_layout_path(pathbuf)?;
+            self.tile = true;
+        }
+
+        if let Some(name) = &config.external_layout {
+            self.layout = Layout::External(name.clone());
             self.tile = true;
         }
 
-        if config.custom_layout.is_none() && config.layout.is_none() {
+        if config.custom_layout.is_none()
+            && config.layout.is_none()
+            && config.external_layout.is_none()
+        {
             self.tile = false;
         }
 
+        if config.layout_options.is_some() {
+            self.set_layout_options(config.layout_options);
+        }
+
         if let Some(layout_rules) = &config.layout_rules {
             let mut all_rules = vec![];
             for (count, rule) in layout_rules {
@@ -158,13 +227,18 @@ impl Workspace {
         if let Some(layout_rules) = &config.custom_layout_rules {
             let rules = self.layout_rules_mut();
             for (count, pathbuf) in layout_rules {
-                let rule = CustomLayout::from_path(pathbuf)?;
-                rules.push((*count, Layout::Custom(rule)));
+                let rule = Layout::from_custom_layout_path(pathbuf)?;
+                rules.push((*count, rule));
             }
 
             self.tile = true;
         }
 
+        self.set_monitor_layout_rules(config.monitor_layout_rules.clone().unwrap_or_default());
+        // The layout from the configuration is the one restored when no monitor layout rule matches
+        self.set_applied_monitor_layout_rule(None);
+        self.set_layout_before_monitor_layout_rule(None);
+
         self.set_apply_window_based_work_area_offset(
             config.apply_window_based_work_area_offset.unwrap_or(true),
         );
@@ -252,20 +326,18 @@ impl Workspace {
         Ok(())
     }
 
-    pub fn update(
-        &mut self,
+    /// The area that the layout of this workspace is calculated in, after the work area offsets
+    /// and the workspace padding have been applied
+    pub fn adjusted_work_area(
+        &self,
         work_area: &Rect,
         work_area_offset: Option<Rect>,
         window_based_work_area_offset: (isize, Option<Rect>),
-    ) -> Result<()> {
-        if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
-            return Ok(());
-        }
-
+    ) -> Rect {
         let (window_based_work_area_offset_limit, window_based_work_area_offset) =
             window_based_work_area_offset;
 
-        let container_padding = self.container_padding();
+        let (work_area_padding, _) = self.gaps();
         let mut adjusted_work_area = work_area_offset.map_or_else(
             || *work_area,
             |offset| {
@@ -297,10 +369,26 @@ impl Workspace {
             );
         }
 
-        adjusted_work_area.add_padding(self.workspace_padding().unwrap_or_default());
+        adjusted_work_area.add_side_padding(work_area_padding);
 
-        self.enforce_resize_constraints();
+        adjusted_work_area
+    }
+
+    pub fn update(
+        &mut self,
+        work_area: &Rect,
+        work_area_offset: Option<Rect>,
+        window_based_work_area_offset: (isize, Option<Rect>),
+    ) -> Result<()> {
+        if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
+            return Ok(());
+        }
 
+        let (_, container_padding) = self.gaps();
+        let mut adjusted_work_area =
+            self.adjusted_work_area(work_area, work_area_offset, window_based_work_area_offset);
+
+        self.enforce_resize_constraints();
         if !self.layout_rules().is_empty() {
             let mut updated_layout = None;
 
@@ -320,11 +408,12 @@ impl Workspace {
         }
 
         let managed_maximized_window = self.maximized_window().is_some();
+        self.unsatisfied_size_constraints.clear();
 
         if *self.tile() {
             if let Some(container) = self.monocle_container_mut() {
                 if let Some(window) = container.focused_window_mut() {
-                    adjusted_work_area.add_padding(container_padding.unwrap_or_default());
+                    adjusted_work_area.add_side_padding(container_padding);
                     {
                         let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
                         adjusted_work_area.add_padding(border_offset);
@@ -336,25 +425,119 @@ impl Workspace {
             } else if let Some(window) = self.maximized_window_mut() {
                 window.maximize();
             } else if !self.containers().is_empty() {
-                let mut layouts = self.layout().as_boxed_arrangement().calculate(
+                let arrangement: Box<dyn Arrangement> = match self.layout() {
+                    Layout::External(name) => Box::new(ExternalLayout::from(name.clone())),
+                    layout => layout.as_boxed_arrangement(),
+                };
+
+                let mut layouts = arrangement.calculate(
                     &adjusted_work_area,
                     NonZeroUsize::new(self.containers().len()).ok_or_else(|| {
                         anyhow!(
                             "there must be at least one container to calculate a workspace layout"
                         )
                     })?,
-                    self.container_padding(),
+                    // Container padding is applied after the size constraints
+                    None,
                     self.layout_flip(),
                     self.resize_dimensions(),
+                    self.focused_container_idx(),
+                    self.layout_options(),
+                    self.latest_layout(),
                 );
 
+                let border_inset =
+                    BORDER_OFFSET.load(Ordering::SeqCst) + BORDER_WIDTH.load(Ordering::SeqCst);
+                let (size_constraints, insets): (Vec<_>, Vec<_>) = self
+                    .containers()
+                    .iter()
+                    .map(|container| {
+                        let size_constraints = container
+                            .focused_window()
+                            .and_then(|window| window.size_constraints().ok().flatten());
+
+                        let horizontal_inset =
+                            container_padding.left + container_padding.right + border_inset * 2;
+                        let vertical_inset =
+                            container_padding.top + container_padding.bottom + border_inset * 2;
+                        let stackbar_inset =
+                            if stackbar_manager::should_have_stackbar(container.windows().len()) {
+                                STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst) + container_padding.top
+                            } else {
+                                0
+                            };
+
+                        (
+                            size_constraints,
+                            (horizontal_inset, vertical_inset + stackbar_inset),
+                        )
+                    })
+                    .unzip();
+
+                let mut unsatisfied_size_constraints = vec![];
+                for idx in apply_size_constraints(
+                    &adjusted_work_area,
+                    &mut layouts,
+                    &size_constraints,
+                    &insets,
+                ) {
+                    if let Some(window) = self
+                        .containers()
+                        .get(idx)
+                        .and_then(Container::focused_window)
+                    {
+                        tracing::warn!(
+                            "there is not enough space to satisfy the size constraints of {}",
+                            window.exe().unwrap_or_default()
+                        );
+
+                        unsatisfied_size_constraints.push(window.hwnd);
+                    }
+                }
+
+                self.unsatisfied_size_constraints = unsatisfied_size_constraints;
+
+                // Columns of a scrolling layout which fall outside of the viewport are hidden,
+                // otherwise they would be drawn on top of any neighbouring monitor
+                let scrolling = matches!(self.layout(), Layout::Default(DefaultLayout::Scrolling));
+                let in_viewport = layouts
+                    .iter()
+                    .map(|rect| {
+                        let centre = rect.left + (rect.right / 2);
+                        !scrolling
+                            || (centre >= adjusted_work_area.left
+                                && centre < adjusted_work_area.left + adjusted_work_area.right)
+                    })
+                    .collect::<Vec<_>>();
+
+                layouts
+                    .iter_mut()
+                    .for_each(|layout| layout.add_side_padding(container_padding));
+
                 let should_remove_titlebars = REMOVE_TITLEBARS.load(Ordering::SeqCst);
                 let no_titlebar = NO_TITLEBAR.lock().clone();
+                let manage_action_no_titlebar = MANAGE_ACTION_NO_TITLEBAR.lock().clone();
+
+                let previously_scrolled_out = self.scrolled_out_hwnds.clone();
+                let mut scrolled_out = HashSet::new();
 
-                let container_padding = self.container_padding().unwrap_or(0);
                 let containers = self.containers_mut();
 
                 for (i, container) in containers.iter_mut().enumerate() {
+                    if !in_viewport.get(i).copied().unwrap_or(true) {
+                        container.hide(None);
+                        scrolled_out.extend(container.windows().iter().map(|window| window.hwnd));
+                        continue;
+                    }
+
+                    if container
+                        .windows()
+                        .iter()
+                        .any(|window| previously_scrolled_out.contains(&window.hwnd))
+                    {
+                        container.restore();
+                    }
+
                     let window_count = container.windows().len();
 
                     if let (Some(window), Some(layout)) =
@@ -362,14 +545,16 @@ impl Workspace {
                     {
                         if should_remove_titlebars && no_titlebar.contains(&window.exe()?) {
                             window.remove_title_bar()?;
-                        } else if no_titlebar.contains(&window.exe()?) {
+                        } else if no_titlebar.contains(&window.exe()?)
+                            && !manage_action_no_titlebar.contains(&window.hwnd)
+                        {
                             window.add_title_bar()?;
                         }
 
                         // If a window has been unmaximized via toggle-maximize, this block
                         // will make sure that it is unmaximized via restore_window
                         if window.is_maximized() && !managed_maximized_window {
-                            WindowsApi::restore_window(window.hwnd);
+                            platform::current().restore_window(window.hwnd);
                         }
 
                         {
@@ -382,7 +567,7 @@ impl Workspace {
 
                         if stackbar_manager::should_have_stackbar(window_count) {
                             let tab_height = STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst);
-                            let total_height = tab_height + container_padding;
+                            let total_height = tab_height + container_padding.top;
 
                             layout.top += total_height;
                             layout.bottom -= total_height;
@@ -392,6 +577,7 @@ impl Workspace {
                     }
                 }
 
+                self.scrolled_out_hwnds = scrolled_out;
                 self.set_latest_layout(layouts);
             }
         }
@@ -405,6 +591,42 @@ impl Workspace {
         Ok(())
     }
 
+    /// The padding applied to each side of the work area and the padding applied to each
+    /// container. When inner or outer gaps are set, the padding is derived so that adjacent
+    /// containers are separated by the inner gap and the outermost containers are separated from
+    /// the work area edges by the outer gap
+    #[must_use]
+    pub fn gaps(&self) -> (SidePadding, SidePadding) {
+        if self.smart_gaps && (self.containers().len() == 1 || self.monocle_container().is_some()) {
+            return (SidePadding::default(), SidePadding::default());
+        }
+
+        let workspace_padding = self.workspace_padding().unwrap_or_default();
+        let container_padding = self.container_padding().unwrap_or_default();
+
+        if self.inner_gap.is_none() && self.outer_gap.is_none() {
+            return (
+                SidePadding::uniform(workspace_padding),
+                SidePadding::uniform(container_padding),
+            );
+        }
+
+        // Without an explicit outer gap, the outermost containers keep the same distance from the
+        // work area edges that the workspace and container padding would have given them
+        let outer_gap = self
+            .outer_gap
+            .unwrap_or_else(|| SidePadding::uniform(workspace_padding + container_padding));
+
+        // Every container is padded by half of the inner gap, so the work area padding has to make
+        // up the difference to reach the outer gap
+        let container_padding = self.inner_gap.map_or_else(
+            || SidePadding::uniform(container_padding),
+            SidePadding::split_gap,
+        );
+
+        (outer_gap.shrink(container_padding), container_padding)
+    }
+
     pub fn reap_orphans(&mut self) -> Result<(usize, usize)> {
         let mut hwnds = vec![];
         let mut floating_hwnds = vec![];
@@ -513,11 +735,11 @@ impl Workspace {
     pub fn container_idx_from_current_point(&self) -> Option<usize> {
         let mut idx = None;
 
-        let point = WindowsApi::cursor_pos().ok()?;
+        let point = platform::current().cursor_pos().ok()?;
 
         for (i, _container) in self.containers().iter().enumerate() {
             if let Some(rect) = self.latest_layout().get(i) {
-                if rect.contains_point((point.x, point.y)) {
+                if rect.contains_point(point) {
                     idx = Option::from(i);
                 }
             }
@@ -581,7 +803,7 @@ impl Workspace {
     }
 
     pub fn is_focused_window_monocle_or_maximized(&self) -> Result<bool> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = platform::current().foreground_window()?;
         if let Some(window) = self.maximized_window() {
             if hwnd == window.hwnd {
                 return Ok(true);
@@ -639,7 +861,7 @@ impl Workspace {
             .ok_or_else(|| anyhow!("there is no container"))?;
 
         let primary_idx = match self.layout() {
-            Layout::Default(_) => 0,
+            Layout::Default(_) | Layout::CustomTree(_) | Layout::External(_) => 0,
             Layout::Custom(layout) => layout.first_container_idx(
                 layout
                     .primary_idx()
@@ -789,13 +1011,32 @@ impl Workspace {
     pub fn new_idx_for_direction(&self, direction: OperationDirection) -> Option<usize> {
         let len = NonZeroUsize::new(self.containers().len())?;
 
+        // External layouts can only be navigated based on where their containers were placed
+        if matches!(self.layout(), Layout::External(_))
+            && self.latest_layout().len() == self.containers().len()
+        {
+            return direction.closest_rect(self.focused_container_idx(), self.latest_layout());
+        }
+
         direction.destination(
             self.layout().as_boxed_direction().as_ref(),
             self.layout_flip(),
             self.focused_container_idx(),
             len,
+            self.layout_options(),
         )
     }
+
+    /// The index of the container in the given direction based on the positions of the containers
+    /// when the workspace was last laid out, falling back to the layout if they are not known
+    pub fn geometric_idx_for_direction(&self, direction: OperationDirection) -> Option<usize> {
+        if self.latest_layout().len() != self.containers().len() {
+            return self.new_idx_for_direction(direction);
+        }
+
+        direction.closest_rect(self.focused_container_idx(), self.latest_layout())
+    }
+
     pub fn new_idx_for_cycle_direction(&self, direction: CycleDirection) -> Option<usize> {
         Option::from(direction.next_idx(
             self.focused_container_idx(),
@@ -974,6 +1215,11 @@ impl Workspace {
             Layout::Default(DefaultLayout::UltrawideVerticalStack) => {
                 self.enforce_resize_for_ultrawide();
             }
+            Layout::Default(DefaultLayout::CenteredMaster) => {
+                self.enforce_resize_for_centered_master();
+            }
+            Layout::Default(DefaultLayout::Grid) => self.enforce_resize_for_grid(),
+            Layout::Custom(_) => self.enforce_resize_for_custom(),
             _ => self.enforce_no_resize(),
         }
     }
@@ -983,24 +1229,24 @@ impl Workspace {
             if let Some(rect) = rect {
                 // Even containers can't be resized to the bottom
                 if i % 2 == 0 {
-                    rect.bottom = 0;
+                    rect.bottom = 0.0;
                     // Odd containers can't be resized to the right
                 } else {
-                    rect.right = 0;
+                    rect.right = 0.0;
                 }
             }
         }
 
         // The first container can never be resized to the left or the top
         if let Some(Some(first)) = self.resize_dimensions_mut().first_mut() {
-            first.top = 0;
-            first.left = 0;
+            first.top = 0.0;
+            first.left = 0.0;
         }
 
         // The last container can never be resized to the bottom or the right
         if let Some(Some(last)) = self.resize_dimensions_mut().last_mut() {
-            last.bottom = 0;
-            last.right = 0;
+            last.bottom = 0.0;
+            last.right = 0.0;
         }
     }
 
@@ -1012,14 +1258,14 @@ impl Workspace {
                 let len = resize_dimensions.len();
                 for (i, rect) in resize_dimensions.iter_mut().enumerate() {
                     if let Some(rect) = rect {
-                        rect.top = 0;
-                        rect.bottom = 0;
+                        rect.top = 0.0;
+                        rect.bottom = 0.0;
 
                         if i == 0 {
-                            rect.left = 0;
+                            rect.left = 0.0;
                         }
                         if i == len - 1 {
-                            rect.right = 0;
+                            rect.right = 0.0;
                         }
                     }
                 }
@@ -1035,14 +1281,14 @@ impl Workspace {
                 let len = resize_dimensions.len();
                 for (i, rect) in resize_dimensions.iter_mut().enumerate() {
                     if let Some(rect) = rect {
-                        rect.left = 0;
-                        rect.right = 0;
+                        rect.left = 0.0;
+                        rect.right = 0.0;
 
                         if i == 0 {
-                            rect.top = 0;
+                            rect.top = 0.0;
                         }
                         if i == len - 1 {
-                            rect.bottom = 0;
+                            rect.bottom = 0.0;
                         }
                     }
                 }
@@ -1058,9 +1304,9 @@ impl Workspace {
             _ => {
                 // Zero is actually on the left
                 if let Some(mut left) = resize_dimensions[0] {
-                    left.top = 0;
-                    left.bottom = 0;
-                    left.left = 0;
+                    left.top = 0.0;
+                    left.bottom = 0.0;
+                    left.left = 0.0;
                 }
 
                 // Handle stack on the right
@@ -1068,14 +1314,14 @@ impl Workspace {
                 for (i, rect) in resize_dimensions[1..].iter_mut().enumerate() {
                     if let Some(rect) = rect {
                         // No containers can resize to the right
-                        rect.right = 0;
+                        rect.right = 0.0;
 
                         // First container in stack cant resize up
                         if i == 0 {
-                            rect.top = 0;
+                            rect.top = 0.0;
                         } else if i == stack_size - 1 {
                             // Last cant be resized to the bottom
-                            rect.bottom = 0;
+                            rect.bottom = 0.0;
                         }
                     }
                 }
@@ -1091,9 +1337,9 @@ impl Workspace {
             _ => {
                 // Zero is actually on the right
                 if let Some(mut left) = resize_dimensions[1] {
-                    left.top = 0;
-                    left.bottom = 0;
-                    left.right = 0;
+                    left.top = 0.0;
+                    left.bottom = 0.0;
+                    left.right = 0.0;
                 }
 
                 // Handle stack on the right
@@ -1101,14 +1347,14 @@ impl Workspace {
                 for (i, rect) in resize_dimensions[1..].iter_mut().enumerate() {
                     if let Some(rect) = rect {
                         // No containers can resize to the left
-                        rect.left = 0;
+                        rect.left = 0.0;
 
                         // First container in stack cant resize up
                         if i == 0 {
-                            rect.top = 0;
+                            rect.top = 0.0;
                         } else if i == stack_size - 1 {
                             // Last cant be resized to the bottom
-                            rect.bottom = 0;
+                            rect.bottom = 0.0;
                         }
                     }
                 }
@@ -1122,21 +1368,21 @@ impl Workspace {
             0 | 1 => self.enforce_no_resize(),
             _ => {
                 if let Some(mut left) = resize_dimensions[0] {
-                    left.top = 0;
-                    left.left = 0;
-                    left.right = 0;
+                    left.top = 0.0;
+                    left.left = 0.0;
+                    left.right = 0.0;
                 }
 
                 let stack_size = resize_dimensions[1..].len();
                 for (i, rect) in resize_dimensions[1..].iter_mut().enumerate() {
                     if let Some(rect) = rect {
-                        rect.bottom = 0;
+                        rect.bottom = 0.0;
 
                         if i == 0 {
-                            rect.left = 0;
+                            rect.left = 0.0;
                         }
                         if i == stack_size - 1 {
-                            rect.right = 0;
+                            rect.right = 0.0;
                         }
                     }
                 }
@@ -1153,16 +1399,16 @@ impl Workspace {
             2 => {
                 // Zero is actually on the right
                 if let Some(mut right) = resize_dimensions[0] {
-                    right.top = 0;
-                    right.bottom = 0;
-                    right.right = 0;
+                    right.top = 0.0;
+                    right.bottom = 0.0;
+                    right.right = 0.0;
                 }
 
                 // One is on the left
                 if let Some(mut left) = resize_dimensions[1] {
-                    left.top = 0;
-                    left.bottom = 0;
-                    left.left = 0;
+                    left.top = 0.0;
+                    left.bottom = 0.0;
+                    left.left = 0.0;
                 }
             }
             // Three or more windows means 0 is in center, 1 is at the left, 2.. are a vertical
@@ -1170,15 +1416,15 @@ impl Workspace {
             _ => {
                 // Central can be resized left or right
                 if let Some(mut right) = resize_dimensions[0] {
-                    right.top = 0;
-                    right.bottom = 0;
+                    right.top = 0.0;
+                    right.bottom = 0.0;
                 }
 
                 // Left one can only be resized to the right
                 if let Some(mut left) = resize_dimensions[1] {
-                    left.top = 0;
-                    left.bottom = 0;
-                    left.left = 0;
+                    left.top = 0.0;
+                    left.bottom = 0.0;
+                    left.left = 0.0;
                 }
 
                 // Handle stack on the right
@@ -1186,14 +1432,135 @@ impl Workspace {
                 for (i, rect) in resize_dimensions[2..].iter_mut().enumerate() {
                     if let Some(rect) = rect {
                         // No containers can resize to the right
-                        rect.right = 0;
+                        rect.right = 0.0;
 
                         // First container in stack cant resize up
                         if i == 0 {
-                            rect.top = 0;
+                            rect.top = 0.0;
                         } else if i == stack_size - 1 {
                             // Last cant be resized to the bottom
-                            rect.bottom = 0;
+                            rect.bottom = 0.0;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn enforce_resize_for_centered_master(&mut self) {
+        let (left_stack, right_stack) = self
+            .layout_options()
+            .unwrap_or_default()
+            .centered_master_fill()
+            .stacks(self.resize_dimensions().len());
+
+        let resize_dimensions = self.resize_dimensions_mut();
+        match resize_dimensions.len() {
+            // Single window can not be resized at all
+            0 | 1 => self.enforce_no_resize(),
+            _ => {
+                // Zero is in the centre and can only be resized towards the stacks
+                if let Some(centre) = &mut resize_dimensions[0] {
+                    centre.top = 0.0;
+                    centre.bottom = 0.0;
+
+                    if left_stack.is_empty() {
+                        centre.left = 0.0;
+                    }
+
+                    if right_stack.is_empty() {
+                        centre.right = 0.0;
+                    }
+                }
+
+                for (stack, is_left) in [(&left_stack, true), (&right_stack, false)] {
+                    for (i, idx) in stack.iter().enumerate() {
+                        if let Some(rect) = &mut resize_dimensions[*idx] {
+                            // Stacks can't be resized towards the edges of the work area
+                            if is_left {
+                                rect.left = 0.0;
+                            } else {
+                                rect.right = 0.0;
+                            }
+
+                            // First container in a stack cant resize up
+                            if i == 0 {
+                                rect.top = 0.0;
+                            }
+
+                            // Last cant be resized to the bottom
+                            if i == stack.len() - 1 {
+                                rect.bottom = 0.0;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn enforce_resize_for_grid(&mut self) {
+        let columns = DefaultLayout::grid_columns(self.resize_dimensions().len())
+            .into_iter()
+            .map(|rows| (rows, false))
+            .collect::<Vec<_>>();
+
+        self.enforce_resize_for_column_groups(&columns);
+    }
+
+    fn enforce_resize_for_custom(&mut self) {
+        let columns = match &self.layout {
+            Layout::Custom(custom) => custom.column_ranges(self.resize_dimensions().len()),
+            _ => return,
+        };
+
+        self.enforce_resize_for_column_groups(&columns);
+    }
+
+    /// Containers can't be resized towards the edges of the work area, or towards the top and
+    /// bottom of a column in which they are placed side-by-side
+    fn enforce_resize_for_column_groups(&mut self, columns: &[(Range<usize>, bool)]) {
+        if self.resize_dimensions().len() <= 1 {
+            self.enforce_no_resize();
+            return;
+        }
+
+        let resize_dimensions = self.resize_dimensions_mut();
+        for (c, (range, vertical)) in columns.iter().enumerate() {
+            let first_column = c == 0;
+            let last_column = c == columns.len() - 1;
+
+            for (position, idx) in range.clone().enumerate() {
+                if let Some(Some(rect)) = resize_dimensions.get_mut(idx) {
+                    let first = position == 0;
+                    let last = position == range.len() - 1;
+
+                    if *vertical {
+                        rect.top = 0.0;
+                        rect.bottom = 0.0;
+
+                        if first_column && first {
+                            rect.left = 0.0;
+                        }
+
+                        if last_column && last {
+                            rect.right = 0.0;
+                        }
+                    } else {
+                        if first {
+                            rect.top = 0.0;
+                        }
+
+                        if last {
+                            rect.bottom = 0.0;
+                        }
+
+                        if first_column {
+                            rect.left = 0.0;
+                        }
+
+                        if last_column {
+                            rect.right = 0.0;
                         }
                     }
                 }
@@ -1203,10 +1570,10 @@ impl Workspace {
 
     fn enforce_no_resize(&mut self) {
         for rect in self.resize_dimensions_mut().iter_mut().flatten() {
-            rect.left = 0;
-            rect.right = 0;
-            rect.top = 0;
-            rect.bottom = 0;
+            rect.left = 0.0;
+            rect.right = 0.0;
+            rect.top = 0.0;
+            rect.bottom = 0.0;
         }
     }
 
@@ -1263,7 +1630,7 @@ impl Workspace {
 
     pub fn new_maximized_window(&mut self) -> Result<()> {
         let focused_idx = self.focused_container_idx();
-        let foreground_hwnd = WindowsApi::foreground_window()?;
+        let foreground_hwnd = platform::current().foreground_window()?;
         let mut floating_window = None;
 
         if !self.floating_windows().is_empty() {
@@ -1386,7 +1753,7 @@ impl Workspace {
     }
 
     pub fn remove_focused_floating_window(&mut self) -> Option<Window> {
-        let hwnd = WindowsApi::foreground_window().ok()?;
+        let hwnd = platform::current().foreground_window().ok()?;
 
         let mut idx = None;
         for (i, window) in self.floating_windows.iter().enumerate() {
@@ -1416,6 +1783,21 @@ impl Workspace {
         vec
     }
 
+    /// The tiled windows which are currently shown on the workspace, which is only the window in
+    /// the monocle container when there is one
+    pub fn shown_tiled_windows(&self) -> Vec<Window> {
+        self.monocle_container().as_ref().map_or_else(
+            || {
+                self.containers()
+                    .iter()
+                    .filter_map(Container::focused_window)
+                    .copied()
+                    .collect()
+            },
+            |monocle| monocle.focused_window().copied().into_iter().collect(),
+        )
+    }
+
     pub fn visible_window_details(&self) -> Vec<WindowDetails> {
         let mut vec: Vec<WindowDetails> = vec![];
 
@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::animation::Animation;
+use crate::animation::AnimationSettings;
+use crate::core::HidingBehaviour;
+use crate::core::Rect;
+use crate::core::WorkspaceTransition;
+use crate::platform;
+use crate::transparency_manager;
+use crate::window::Window;
+use crate::HIDDEN_HWNDS;
+use crate::HIDING_BEHAVIOUR;
+
+/// Incremented every time a transition starts, so that a transition which is still running can
+/// tell that it has been superseded and finish immediately
+static TRANSITION_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static! {
+    pub static ref WORKSPACE_TRANSITION: Arc<Mutex<Option<WorkspaceTransition>>> =
+        Arc::new(Mutex::new(None));
+    /// The windows on their way out of a workspace, with the generation of the transition which
+    /// is moving them
+    static ref OUTGOING_HWNDS: Arc<Mutex<HashMap<isize, usize>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// Whether a window is on its way out of a workspace, in which case hiding it is left to the
+/// transition once it has finished
+pub fn is_outgoing(hwnd: isize) -> bool {
+    OUTGOING_HWNDS.lock().contains_key(&hwnd)
+}
+
+/// Whether a window has been claimed by a transition which started after the given generation
+fn claimed_by_newer_transition(hwnd: isize, generation: usize) -> bool {
+    OUTGOING_HWNDS
+        .lock()
+        .get(&hwnd)
+        .is_some_and(|claimed| *claimed > generation)
+}
+
+/// Stop a window from being hidden at the end of a transition, eg. because the workspace it is on
+/// has been focused again before the transition finished
+pub fn cancel_outgoing(hwnd: isize) {
+    OUTGOING_HWNDS.lock().remove(&hwnd);
+}
+
+/// The position of a window just outside of the monitor in the direction that its workspace is
+/// sliding in from (when incoming) or out towards (when outgoing). Moving forwards to a workspace
+/// with a higher index slides the workspaces left or up, and moving backwards slides them right or
+/// down. Fading workspaces don't move, so the window keeps its position
+pub const fn staging_rect(
+    transition: WorkspaceTransition,
+    rect: &Rect,
+    monitor: &Rect,
+    forwards: bool,
+    incoming: bool,
+) -> Rect {
+    // incoming windows come from the side that outgoing windows are heading towards
+    let sign = if forwards == incoming { 1 } else { -1 };
+
+    match transition {
+        WorkspaceTransition::SlideHorizontal => Rect {
+            left: rect.left + sign * monitor.right,
+            ..*rect
+        },
+        WorkspaceTransition::SlideVertical => Rect {
+            top: rect.top + sign * monitor.bottom,
+            ..*rect
+        },
+        WorkspaceTransition::Fade => *rect,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TransitionWindow {
+    window: Window,
+    from: Rect,
+    to: Rect,
+}
+
+/// A transition between two workspaces on the same monitor. Tiled windows on the incoming
+/// workspace are staged off-screen and then slide into place when the workspace is retiled, every
+/// other window is moved or faded by the transition itself
+#[derive(Debug)]
+pub struct Transition {
+    generation: usize,
+    transition: WorkspaceTransition,
+    monitor: Rect,
+    forwards: bool,
+    settings: AnimationSettings,
+    outgoing: Vec<TransitionWindow>,
+    incoming: Vec<TransitionWindow>,
+    staged: Vec<(Window, Rect)>,
+}
+
+impl Transition {
+    pub const fn new(
+        transition: WorkspaceTransition,
+        monitor: Rect,
+        forwards: bool,
+        settings: AnimationSettings,
+    ) -> Self {
+        Self {
+            generation: 0,
+            transition,
+            monitor,
+            forwards,
+            settings,
+            outgoing: vec![],
+            incoming: vec![],
+            staged: vec![],
+        }
+    }
+
+    const fn slides(&self) -> bool {
+        !matches!(self.transition, WorkspaceTransition::Fade)
+    }
+
+    /// Prepare the visible windows of both workspaces before the previous workspace is hidden and
+    /// the next workspace is restored. Tiled windows are positioned by the layout of the incoming
+    /// workspace, so they are only staged, while floating windows are moved back into place by the
+    /// transition. Maximized windows are never moved
+    pub fn prepare(
+        &mut self,
+        outgoing: &[Window],
+        outgoing_maximized: Option<Window>,
+        incoming_tiled: &[Window],
+        incoming_floating: &[Window],
+        incoming_maximized: Option<Window>,
+    ) {
+        self.generation = TRANSITION_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let platform = platform::current();
+        let slides = self.slides();
+
+        for window in outgoing.iter().chain(outgoing_maximized.iter()) {
+            let Ok(from) = platform.window_rect(window.hwnd) else {
+                continue;
+            };
+
+            let to = if slides && Some(*window) != outgoing_maximized {
+                staging_rect(self.transition, &from, &self.monitor, self.forwards, false)
+            } else {
+                from
+            };
+
+            OUTGOING_HWNDS.lock().insert(window.hwnd, self.generation);
+            self.outgoing.push(TransitionWindow {
+                window: *window,
+                from,
+                to,
+            });
+        }
+
+        for window in incoming_tiled {
+            if let Ok(rect) = platform.window_rect(window.hwnd) {
+                if slides {
+                    self.staged.push((*window, rect));
+                } else {
+                    self.incoming.push(TransitionWindow {
+                        window: *window,
+                        from: rect,
+                        to: rect,
+                    });
+                }
+            }
+        }
+
+        for window in incoming_floating.iter().chain(incoming_maximized.iter()) {
+            if let Ok(rect) = platform.window_rect(window.hwnd) {
+                let from = if slides && Some(*window) != incoming_maximized {
+                    staging_rect(self.transition, &rect, &self.monitor, self.forwards, true)
+                } else {
+                    rect
+                };
+
+                self.incoming.push(TransitionWindow {
+                    window: *window,
+                    from,
+                    to: rect,
+                });
+            }
+        }
+
+        if !slides {
+            for incoming in &self.incoming {
+                incoming.window.set_alpha(0).ok();
+            }
+        }
+
+        // minimized windows can't be moved until they have been restored
+        if !matches!(*HIDING_BEHAVIOUR.lock(), HidingBehaviour::Minimize) {
+            self.stage_incoming();
+        }
+    }
+
+    fn stage_incoming(&self) {
+        if !self.slides() {
+            return;
+        }
+
+        let platform = platform::current();
+
+        for (window, rect) in &self.staged {
+            let staged = staging_rect(self.transition, rect, &self.monitor, self.forwards, true);
+            platform.position_window(window.hwnd, &staged, false).ok();
+        }
+
+        for incoming in &self.incoming {
+            platform
+                .position_window(incoming.window.hwnd, &incoming.from, false)
+                .ok();
+        }
+    }
+
+    /// Start the transition once the next workspace has been restored, returning immediately while
+    /// the windows are moved in the background
+    pub fn start(self) {
+        if matches!(*HIDING_BEHAVIOUR.lock(), HidingBehaviour::Minimize) {
+            self.stage_incoming();
+        }
+
+        std::thread::spawn(move || {
+            self.run();
+            self.finish();
+        });
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn run(&self) {
+        let platform = platform::current();
+        let target_frame_time = Duration::from_millis(1000 / self.settings.fps);
+        let started = Instant::now();
+
+        loop {
+            if TRANSITION_GENERATION.load(Ordering::SeqCst) != self.generation {
+                return;
+            }
+
+            let frame_start = Instant::now();
+            let progress = if self.settings.duration.is_zero() {
+                1.0
+            } else {
+                (started.elapsed().as_secs_f64() / self.settings.duration.as_secs_f64()).min(1.0)
+            };
+
+            let alpha = Animation::lerp(0, i32::from(u8::MAX), progress, self.settings.style)
+                .clamp(0, 255) as u8;
+
+            for outgoing in &self.outgoing {
+                if !is_outgoing(outgoing.window.hwnd) {
+                    continue;
+                }
+
+                if self.slides() {
+                    // maximized windows stay where they are
+                    if outgoing.from == outgoing.to {
+                        continue;
+                    }
+
+                    let rect = Animation::lerp_rect(
+                        &outgoing.from,
+                        &outgoing.to,
+                        progress,
+                        self.settings.style,
+                    );
+                    platform
+                        .position_window(outgoing.window.hwnd, &rect, false)
+                        .ok();
+                } else {
+                    outgoing.window.set_alpha(u8::MAX - alpha).ok();
+                }
+            }
+
+            for incoming in &self.incoming {
+                if self.slides() {
+                    // maximized windows stay where they are
+                    if incoming.from == incoming.to {
+                        continue;
+                    }
+
+                    let rect = Animation::lerp_rect(
+                        &incoming.from,
+                        &incoming.to,
+                        progress,
+                        self.settings.style,
+                    );
+                    platform
+                        .position_window(incoming.window.hwnd, &rect, false)
+                        .ok();
+                } else {
+                    incoming.window.set_alpha(alpha).ok();
+                }
+            }
+
+            if progress >= 1.0 {
+                return;
+            }
+
+            let frame_time_elapsed = frame_start.elapsed();
+            if frame_time_elapsed < target_frame_time {
+                std::thread::sleep(target_frame_time - frame_time_elapsed);
+            }
+        }
+    }
+
+    /// Hide the windows which have left their workspace and put every window back in its resting
+    /// state, whether the transition completed or was superseded. Windows which a newer transition
+    /// has claimed are left to that transition
+    fn finish(&self) {
+        let platform = platform::current();
+        let minimizes = matches!(*HIDING_BEHAVIOUR.lock(), HidingBehaviour::Minimize);
+
+        for outgoing in &self.outgoing {
+            let hwnd = outgoing.window.hwnd;
+            let claimed_by = {
+                let mut outgoing_hwnds = OUTGOING_HWNDS.lock();
+                let claimed_by = outgoing_hwnds.get(&hwnd).copied();
+                if claimed_by == Some(self.generation) {
+                    outgoing_hwnds.remove(&hwnd);
+                }
+
+                claimed_by
+            };
+
+            if claimed_by.is_some_and(|claimed| claimed > self.generation) {
+                continue;
+            }
+
+            let still_outgoing = claimed_by == Some(self.generation);
+
+            if still_outgoing && HIDDEN_HWNDS.lock().contains(&hwnd) {
+                outgoing.window.apply_hiding_behaviour();
+
+                // put the window back where it was so that it can be restored in place
+                if self.slides() && !minimizes && outgoing.from != outgoing.to {
+                    platform.position_window(hwnd, &outgoing.from, false).ok();
+                }
+            }
+
+            if !self.slides() {
+                outgoing.window.opaque().ok();
+            }
+        }
+
+        for incoming in &self.incoming {
+            if claimed_by_newer_transition(incoming.window.hwnd, self.generation) {
+                continue;
+            }
+
+            if self.slides() {
+                if incoming.from != incoming.to {
+                    platform
+                        .position_window(incoming.window.hwnd, &incoming.to, false)
+                        .ok();
+                }
+            } else {
+                incoming.window.opaque().ok();
+            }
+        }
+
+        transparency_manager::send_notification();
+    }
+}
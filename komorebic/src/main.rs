@@ -7,6 +7,7 @@ use std::fs::OpenOptions;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Write;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
@@ -50,9 +51,11 @@ use komorebi_client::DefaultLayout;
 use komorebi_client::FocusFollowsMouseImplementation;
 use komorebi_client::HidingBehaviour;
 use komorebi_client::MoveBehaviour;
+use komorebi_client::NavigationBehaviour;
 use komorebi_client::OperationBehaviour;
 use komorebi_client::OperationDirection;
 use komorebi_client::Rect;
+use komorebi_client::SidePadding;
 use komorebi_client::Sizing;
 use komorebi_client::SocketMessage;
 use komorebi_client::StateQuery;
@@ -161,6 +164,8 @@ gen_enum_subcommand_args! {
     Stack: OperationDirection,
     CycleStack: CycleDirection,
     FlipLayout: Axis,
+    AdjustMasterCount: Sizing,
+    AdjustMasterRatio: Sizing,
     ChangeLayout: DefaultLayout,
     CycleLayout: CycleDirection,
     WatchConfiguration: BooleanState,
@@ -169,6 +174,7 @@ gen_enum_subcommand_args! {
     WindowHidingBehaviour: HidingBehaviour,
     CrossMonitorMoveBehaviour: MoveBehaviour,
     UnmanagedWindowOperationBehaviour: OperationBehaviour,
+    ContainerNavigationBehaviour: NavigationBehaviour,
     PromoteWindow: OperationDirection,
 }
 
@@ -250,6 +256,7 @@ gen_workspace_subcommand_args! {
     Name: String,
     Layout: #[enum] DefaultLayout,
     Tiling: #[enum] BooleanState,
+    SmartGaps: #[enum] BooleanState,
 }
 
 macro_rules! gen_named_workspace_subcommand_args {
@@ -278,6 +285,7 @@ macro_rules! gen_named_workspace_subcommand_args {
 gen_named_workspace_subcommand_args! {
     Layout: #[enum] DefaultLayout,
     Tiling: #[enum] BooleanState,
+    SmartGaps: #[enum] BooleanState,
 }
 
 #[derive(Parser)]
@@ -380,6 +388,12 @@ struct ResizeAxis {
     sizing: Sizing,
 }
 
+#[derive(Parser)]
+struct ScrollingLayoutColumns {
+    /// Desired number of visible columns
+    count: NonZeroUsize,
+}
+
 #[derive(Parser)]
 struct ResizeDelta {
     /// The delta of pixels by which to increase or decrease window dimensions when resizing
@@ -524,6 +538,7 @@ macro_rules! gen_padding_subcommand_args {
 gen_padding_subcommand_args! {
     ContainerPadding,
     WorkspacePadding,
+    WorkspaceInnerGap,
 }
 
 macro_rules! gen_named_padding_subcommand_args {
@@ -545,6 +560,37 @@ macro_rules! gen_named_padding_subcommand_args {
 gen_named_padding_subcommand_args! {
     NamedWorkspaceContainerPadding,
     NamedWorkspacePadding,
+    NamedWorkspaceInnerGap,
+}
+
+#[derive(Parser)]
+pub struct WorkspaceOuterGap {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    /// Workspace index on the specified monitor (zero-indexed)
+    workspace: usize,
+    /// Pixels between the containers and the top of the work area
+    top: i32,
+    /// Pixels between the containers and the bottom of the work area
+    bottom: i32,
+    /// Pixels between the containers and the left of the work area
+    left: i32,
+    /// Pixels between the containers and the right of the work area
+    right: i32,
+}
+
+#[derive(Parser)]
+pub struct NamedWorkspaceOuterGap {
+    /// Target workspace name
+    workspace: String,
+    /// Pixels between the containers and the top of the work area
+    top: i32,
+    /// Pixels between the containers and the bottom of the work area
+    bottom: i32,
+    /// Pixels between the containers and the left of the work area
+    left: i32,
+    /// Pixels between the containers and the right of the work area
+    right: i32,
 }
 
 macro_rules! gen_padding_adjustment_subcommand_args {
@@ -565,6 +611,7 @@ macro_rules! gen_padding_adjustment_subcommand_args {
 gen_padding_adjustment_subcommand_args! {
     AdjustContainerPadding,
     AdjustWorkspacePadding,
+    AdjustInnerGap,
 }
 
 macro_rules! gen_application_target_subcommand_args {
@@ -744,6 +791,26 @@ struct AnimationStyle {
     style: komorebi_client::AnimationStyle,
 }
 
+#[derive(Parser)]
+struct AnimationCubicBezier {
+    /// X coordinate of the first control point (0.0 to 1.0)
+    x1: f64,
+    /// Y coordinate of the first control point
+    y1: f64,
+    /// X coordinate of the second control point (0.0 to 1.0)
+    x2: f64,
+    /// Y coordinate of the second control point
+    y2: f64,
+}
+
+#[derive(Parser)]
+struct AnimationSpring {
+    /// Stiffness of the spring
+    stiffness: f64,
+    /// Damping of the spring
+    damping: f64,
+}
+
 #[derive(Parser)]
 #[allow(clippy::struct_excessive_bools)]
 struct Start {
@@ -770,6 +837,25 @@ struct Start {
     bar: bool,
 }
 
+#[derive(Parser)]
+struct EvaluateRules {
+    /// Title of the window
+    #[clap(long, default_value = "")]
+    title: String,
+    /// Executable name of the window
+    #[clap(long, default_value = "")]
+    exe: String,
+    /// Class of the window
+    #[clap(long, default_value = "")]
+    class: String,
+    /// Full path of the executable of the window
+    #[clap(long, default_value = "")]
+    path: String,
+    /// Evaluate the rules from a static configuration JSON file instead of a running komorebi
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+}
+
 #[derive(Parser)]
 struct Stop {
     /// Stop whkd if it is running as a background process
@@ -798,6 +884,12 @@ struct LoadCustomLayout {
     path: PathBuf,
 }
 
+#[derive(Parser)]
+struct ExternalLayout {
+    /// Name that the external layout process was registered under
+    name: String,
+}
+
 #[derive(Parser)]
 struct SubscribeSocket {
     /// Name of the socket to send event notifications to
@@ -915,6 +1007,9 @@ enum SubCommand {
     /// Show information about connected monitors
     #[clap(alias = "monitor-info")]
     MonitorInformation,
+    /// Show which rules match a window with the given properties, and whether it will be managed
+    #[clap(arg_required_else_help = true)]
+    EvaluateRules(EvaluateRules),
     /// Query the current window manager state
     #[clap(arg_required_else_help = true)]
     Query(Query),
@@ -1082,6 +1177,11 @@ enum SubCommand {
     /// Adjust workspace padding on the focused workspace
     #[clap(arg_required_else_help = true)]
     AdjustWorkspacePadding(AdjustWorkspacePadding),
+    /// Adjust the gap between containers on the focused workspace
+    #[clap(arg_required_else_help = true)]
+    AdjustInnerGap(AdjustInnerGap),
+    /// Toggle smart gaps, which remove all gaps when only one container is visible, on the focused workspace
+    ToggleSmartGaps,
     /// Set the layout on the focused workspace
     #[clap(arg_required_else_help = true)]
     ChangeLayout(ChangeLayout),
@@ -1091,9 +1191,21 @@ enum SubCommand {
     /// Load a custom layout from file for the focused workspace
     #[clap(arg_required_else_help = true)]
     LoadCustomLayout(LoadCustomLayout),
+    /// Use a registered external layout process for the focused workspace
+    #[clap(arg_required_else_help = true)]
+    ExternalLayout(ExternalLayout),
     /// Flip the layout on the focused workspace (BSP only)
     #[clap(arg_required_else_help = true)]
     FlipLayout(FlipLayout),
+    /// Set the number of visible columns for the Scrolling layout on the focused workspace
+    #[clap(arg_required_else_help = true)]
+    ScrollingLayoutColumns(ScrollingLayoutColumns),
+    /// Increase or decrease the number of containers in the master area of the focused workspace
+    #[clap(arg_required_else_help = true)]
+    AdjustMasterCount(AdjustMasterCount),
+    /// Increase or decrease the proportion of the work area given to the master area of the focused workspace
+    #[clap(arg_required_else_help = true)]
+    AdjustMasterRatio(AdjustMasterRatio),
     /// Promote the focused window to the top of the tree
     Promote,
     /// Promote the user focus to the top of the tree
@@ -1126,6 +1238,24 @@ enum SubCommand {
     /// Set the workspace padding for the specified workspace
     #[clap(arg_required_else_help = true)]
     NamedWorkspacePadding(NamedWorkspacePadding),
+    /// Set the gap between containers for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    WorkspaceInnerGap(WorkspaceInnerGap),
+    /// Set the gap between containers for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    NamedWorkspaceInnerGap(NamedWorkspaceInnerGap),
+    /// Set the gap between the containers and each edge of the work area for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    WorkspaceOuterGap(WorkspaceOuterGap),
+    /// Set the gap between the containers and each edge of the work area for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    NamedWorkspaceOuterGap(NamedWorkspaceOuterGap),
+    /// Enable or disable smart gaps, which remove all gaps when only one container is visible, for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    WorkspaceSmartGaps(WorkspaceSmartGaps),
+    /// Enable or disable smart gaps, which remove all gaps when only one container is visible, for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    NamedWorkspaceSmartGaps(NamedWorkspaceSmartGaps),
     /// Set the layout for the specified workspace
     #[clap(arg_required_else_help = true)]
     WorkspaceLayout(WorkspaceLayout),
@@ -1218,6 +1348,9 @@ enum SubCommand {
     /// Set the operation behaviour when the focused window is not managed
     #[clap(arg_required_else_help = true)]
     UnmanagedWindowOperationBehaviour(UnmanagedWindowOperationBehaviour),
+    /// Set how the container in a direction is found when focusing or moving containers
+    #[clap(arg_required_else_help = true)]
+    ContainerNavigationBehaviour(ContainerNavigationBehaviour),
     /// Add a rule to ignore the specified application
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "float-rule")]
@@ -1305,6 +1438,12 @@ enum SubCommand {
     /// Set the ease function for movement animations
     #[clap(arg_required_else_help = true)]
     AnimationStyle(AnimationStyle),
+    /// Set a cubic bezier curve as the ease function for movement animations
+    #[clap(arg_required_else_help = true)]
+    AnimationCubicBezier(AnimationCubicBezier),
+    /// Set a damped spring as the ease function for movement animations
+    #[clap(arg_required_else_help = true)]
+    AnimationSpring(AnimationSpring),
     /// Enable or disable focus follows mouse for the operating system
     #[clap(arg_required_else_help = true)]
     FocusFollowsMouse(FocusFollowsMouse),
@@ -1758,6 +1897,55 @@ fn main() -> Result<()> {
         SubCommand::FocusedWorkspacePadding(arg) => {
             send_message(&SocketMessage::FocusedWorkspacePadding(arg.size))?;
         }
+        SubCommand::WorkspaceInnerGap(arg) => {
+            send_message(&SocketMessage::WorkspaceInnerGap(
+                arg.monitor,
+                arg.workspace,
+                arg.size,
+            ))?;
+        }
+        SubCommand::NamedWorkspaceInnerGap(arg) => {
+            send_message(&SocketMessage::NamedWorkspaceInnerGap(
+                arg.workspace,
+                arg.size,
+            ))?;
+        }
+        SubCommand::WorkspaceOuterGap(arg) => {
+            send_message(&SocketMessage::WorkspaceOuterGap(
+                arg.monitor,
+                arg.workspace,
+                SidePadding {
+                    top: arg.top,
+                    bottom: arg.bottom,
+                    left: arg.left,
+                    right: arg.right,
+                },
+            ))?;
+        }
+        SubCommand::NamedWorkspaceOuterGap(arg) => {
+            send_message(&SocketMessage::NamedWorkspaceOuterGap(
+                arg.workspace,
+                SidePadding {
+                    top: arg.top,
+                    bottom: arg.bottom,
+                    left: arg.left,
+                    right: arg.right,
+                },
+            ))?;
+        }
+        SubCommand::WorkspaceSmartGaps(arg) => {
+            send_message(&SocketMessage::WorkspaceSmartGaps(
+                arg.monitor,
+                arg.workspace,
+                arg.value.into(),
+            ))?;
+        }
+        SubCommand::NamedWorkspaceSmartGaps(arg) => {
+            send_message(&SocketMessage::NamedWorkspaceSmartGaps(
+                arg.workspace,
+                arg.value.into(),
+            ))?;
+        }
         SubCommand::FocusedWorkspaceContainerPadding(arg) => {
             send_message(&SocketMessage::FocusedWorkspaceContainerPadding(arg.size))?;
         }
@@ -1767,6 +1955,12 @@ fn main() -> Result<()> {
                 arg.adjustment,
             ))?;
         }
+        SubCommand::AdjustInnerGap(arg) => {
+            send_message(&SocketMessage::AdjustInnerGap(arg.sizing, arg.adjustment))?;
+        }
+        SubCommand::ToggleSmartGaps => {
+            send_message(&SocketMessage::ToggleSmartGaps)?;
+        }
         SubCommand::AdjustContainerPadding(arg) => {
             send_message(&SocketMessage::AdjustContainerPadding(
                 arg.sizing,
@@ -2249,9 +2443,21 @@ Stop-Process -Name:komorebi -ErrorAction SilentlyContinue
                 arg.path,
             )?))?;
         }
+        SubCommand::ExternalLayout(arg) => {
+            send_message(&SocketMessage::ChangeLayoutExternal(arg.name))?;
+        }
         SubCommand::FlipLayout(arg) => {
             send_message(&SocketMessage::FlipLayout(arg.axis))?;
         }
+        SubCommand::ScrollingLayoutColumns(arg) => {
+            send_message(&SocketMessage::ScrollingLayoutColumns(arg.count))?;
+        }
+        SubCommand::AdjustMasterCount(arg) => {
+            send_message(&SocketMessage::AdjustMasterCount(arg.sizing))?;
+        }
+        SubCommand::AdjustMasterRatio(arg) => {
+            send_message(&SocketMessage::AdjustMasterRatio(arg.sizing))?;
+        }
         SubCommand::FocusMonitor(arg) => {
             send_message(&SocketMessage::FocusMonitorNumber(arg.target))?;
         }
@@ -2331,6 +2537,20 @@ Stop-Process -Name:komorebi -ErrorAction SilentlyContinue
         SubCommand::MonitorInformation => {
             print_query(&SocketMessage::MonitorInformation);
         }
+        SubCommand::EvaluateRules(arg) => match arg.config {
+            Some(config) => {
+                let evaluation = StaticConfig::read(&resolve_home_path(config)?)?
+                    .rule_set()?
+                    .evaluate(&arg.title, &arg.exe, &arg.class, &arg.path);
+
+                println!("{}", serde_json::to_string_pretty(&evaluation)?);
+            }
+            None => {
+                print_query(&SocketMessage::EvaluateRules(
+                    arg.title, arg.exe, arg.class, arg.path,
+                ));
+            }
+        },
         SubCommand::Query(arg) => {
             print_query(&SocketMessage::Query(arg.state_query));
         }
@@ -2480,6 +2700,19 @@ Stop-Process -Name:komorebi -ErrorAction SilentlyContinue
         SubCommand::AnimationStyle(arg) => {
             send_message(&SocketMessage::AnimationStyle(arg.style))?;
         }
+        SubCommand::AnimationCubicBezier(arg) => {
+            send_message(&SocketMessage::AnimationStyle(
+                komorebi_client::AnimationStyle::CubicBezier(arg.x1, arg.y1, arg.x2, arg.y2),
+            ))?;
+        }
+        SubCommand::AnimationSpring(arg) => {
+            send_message(&SocketMessage::AnimationStyle(
+                komorebi_client::AnimationStyle::Spring {
+                    stiffness: arg.stiffness,
+                    damping: arg.damping,
+                },
+            ))?;
+        }
 
         SubCommand::ResizeDelta(arg) => {
             send_message(&SocketMessage::ResizeDelta(arg.pixels))?;
@@ -2512,6 +2745,11 @@ Stop-Process -Name:komorebi -ErrorAction SilentlyContinue
                 arg.operation_behaviour,
             ))?;
         }
+        SubCommand::ContainerNavigationBehaviour(arg) => {
+            send_message(&SocketMessage::NavigationBehaviour(
+                arg.navigation_behaviour,
+            ))?;
+        }
         SubCommand::AhkAppSpecificConfiguration(arg) => {
             let content = std::fs::read_to_string(resolve_home_path(arg.path)?)?;
             let lines = if let Some(override_path) = arg.override_path {